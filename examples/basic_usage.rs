@@ -15,12 +15,8 @@
 //! cargo run --example basic_usage
 //! ```
 
-use squad_connect::{
-    client::squad_connect::SquadConnect,
-    service::{dtos::Network, types::ServiceError},
-};
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
 use std::{env, path::PathBuf};
-use sui_sdk::SuiClientBuilder;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -32,18 +28,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("🚀 Starting Squad Connect Example");
 
-    // Initialize Sui client for testnet
+    // Connect Squad Connect to Sui testnet
     println!("📡 Connecting to Sui testnet...");
-    let sui_client = SuiClientBuilder::default().build_testnet().await?;
+    let mut squad_connect =
+        SquadConnect::connect_to_testnet(google_client_id, enoki_api_key).await?;
     println!("✅ Connected to Sui testnet");
-
-    // Create Squad Connect instance
-    let mut squad_connect = SquadConnect::new(
-        sui_client,
-        google_client_id,
-        Network::Testnet,
-        enoki_api_key,
-    );
+    println!("🧭 Next step: {:?}", squad_connect.get_zklogin_flow_state());
 
     // Step 1: Initialize zkLogin parameters
     println!("\n🔑 Setting up zkLogin parameters...");
@@ -60,6 +50,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    println!("🧭 Next step: {:?}", squad_connect.get_zklogin_flow_state());
+
+    match squad_connect.get_zklogin_nonce_expiry_advice().await {
+        Ok(advice) => println!("⏰ {}", advice),
+        Err(e) => println!("⚠️  Could not estimate session expiry: {}", e),
+    }
+
     // Step 2: Generate OAuth URL
     println!("\n🌐 Generating OAuth URL...");
     let redirect_url = "http://localhost:3000/callback".to_string();
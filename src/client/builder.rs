@@ -0,0 +1,221 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use sui_sdk::SuiClient;
+
+use crate::service::{
+    dtos::Network,
+    services::Services,
+    tls::{TlsPinConfig, pinned_client},
+    types::Result,
+};
+
+use super::{
+    resilient::{DEFAULT_HEALTH_CHECK_INTERVAL, ResilientSuiClient},
+    squad_connect::SquadConnect,
+};
+
+/// Builder for [`SquadConnect`]
+///
+/// Holds the required connection settings and any optional configuration,
+/// so constructors and callers share the same defaults.
+///
+/// # Example
+/// ```rust
+/// let squad_connect = SquadConnectBuilder::new(
+///     sui_client,
+///     "your-google-client-id".to_string(),
+///     Network::Testnet,
+///     "your-api-key".to_string(),
+/// )
+/// .build();
+/// ```
+pub struct SquadConnectBuilder {
+    node: SuiClient,
+    client_id: String,
+    network: Network,
+    api_key: String,
+    http_client: Option<Client>,
+    max_response_size_bytes: Option<u64>,
+    max_request_size_bytes: Option<u64>,
+    request_timeout: Option<Duration>,
+    enoki_base_url: Option<String>,
+    dry_run: bool,
+    portfolio_ttl: Option<Duration>,
+    fallback_rpc: Vec<String>,
+    health_check_interval: Option<Duration>,
+    validate_move_calls: bool,
+    reauth_threshold_epochs: Option<u64>,
+    max_concurrent_sponsors: Option<usize>,
+    explorer_base_url: Option<String>,
+}
+
+impl SquadConnectBuilder {
+    pub fn new(node: SuiClient, client_id: String, network: Network, api_key: String) -> Self {
+        Self {
+            node,
+            client_id,
+            network,
+            api_key,
+            http_client: None,
+            max_response_size_bytes: None,
+            max_request_size_bytes: None,
+            request_timeout: None,
+            enoki_base_url: None,
+            dry_run: false,
+            portfolio_ttl: None,
+            fallback_rpc: Vec::new(),
+            health_check_interval: None,
+            validate_move_calls: false,
+            reauth_threshold_epochs: None,
+            max_concurrent_sponsors: None,
+            explorer_base_url: None,
+        }
+    }
+
+    /// Pins the TLS certificate presented by the Enoki API
+    ///
+    /// Fails if any pin is not a base64-encoded SHA-256 hash. See
+    /// [`TlsPinConfig`] for rotation caveats.
+    pub fn tls_pin(mut self, config: TlsPinConfig) -> Result<Self> {
+        self.http_client = Some(pinned_client(&config)?);
+        Ok(self)
+    }
+
+    /// Caps the size of Enoki response bodies (default 1 MB)
+    pub fn max_response_size_bytes(mut self, max_response_size_bytes: u64) -> Self {
+        self.max_response_size_bytes = Some(max_response_size_bytes);
+        self
+    }
+
+    /// Caps the size of serialized sponsor transaction payloads (default 512 KB)
+    pub fn max_request_size_bytes(mut self, max_request_size_bytes: u64) -> Self {
+        self.max_request_size_bytes = Some(max_request_size_bytes);
+        self
+    }
+
+    /// Caps how long each Enoki request of the `get_multiple_*` calls may take (default 30 seconds)
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = Some(request_timeout);
+        self
+    }
+
+    /// Uses a self-hosted or staging Enoki deployment instead of production
+    pub fn enoki_base_url(mut self, enoki_base_url: String) -> Self {
+        self.enoki_base_url = Some(enoki_base_url);
+        self
+    }
+
+    /// Dry-runs transactions before sponsoring them so failures do not spend Enoki quota
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Sets how long portfolio balances are cached (default 30 seconds)
+    pub fn portfolio_ttl(mut self, portfolio_ttl: Duration) -> Self {
+        self.portfolio_ttl = Some(portfolio_ttl);
+        self
+    }
+
+    /// Fullnode URLs to fall over to, in order, when the primary node fails
+    ///
+    /// A background task, started with the first request, also probes the
+    /// endpoints and moves back to the primary once it recovers.
+    pub fn with_fallback_rpc(mut self, fallback_rpc: Vec<String>) -> Self {
+        self.fallback_rpc = fallback_rpc;
+        self
+    }
+
+    /// Sets how often fallback endpoints are health-checked (default 30 seconds)
+    pub fn health_check_interval(mut self, health_check_interval: Duration) -> Self {
+        self.health_check_interval = Some(health_check_interval);
+        self
+    }
+
+    /// Checks Move call arguments against the function ABI before building the transaction
+    ///
+    /// Meant for debugging, as every `call_move_function` then fetches the
+    /// package ABI first.
+    pub fn validate_move_calls(mut self, validate_move_calls: bool) -> Self {
+        self.validate_move_calls = validate_move_calls;
+        self
+    }
+
+    /// Epochs before `max_epoch` at which `rotate_ephemeral_key` replaces the key (default 1)
+    pub fn reauth_threshold_epochs(mut self, reauth_threshold_epochs: u64) -> Self {
+        self.reauth_threshold_epochs = Some(reauth_threshold_epochs);
+        self
+    }
+
+    /// Caps how many sponsor requests `batch_sponsor_transactions` sends at once (default 5)
+    pub fn max_concurrent_sponsors(mut self, max_concurrent_sponsors: usize) -> Self {
+        self.max_concurrent_sponsors = Some(max_concurrent_sponsors);
+        self
+    }
+
+    /// Block explorer links point to (default: the network's, see `Network::default_explorer_url`)
+    pub fn explorer_base_url(mut self, explorer_base_url: String) -> Self {
+        self.explorer_base_url = Some(explorer_base_url);
+        self
+    }
+
+    pub fn build(self) -> SquadConnect {
+        let mut services = Services::new(self.node, self.network, self.api_key, self.client_id);
+
+        if let Some(http_client) = self.http_client {
+            services = services.with_http_client(http_client);
+        }
+
+        if let Some(max_response_size_bytes) = self.max_response_size_bytes {
+            services = services.with_max_response_size_bytes(max_response_size_bytes);
+        }
+
+        if let Some(max_request_size_bytes) = self.max_request_size_bytes {
+            services = services.with_max_request_size_bytes(max_request_size_bytes);
+        }
+
+        if let Some(request_timeout) = self.request_timeout {
+            services = services.with_request_timeout(request_timeout);
+        }
+
+        if let Some(enoki_base_url) = self.enoki_base_url {
+            services = services.with_enoki_base_url(enoki_base_url);
+        }
+
+        services = services.with_dry_run(self.dry_run);
+
+        if !self.fallback_rpc.is_empty() {
+            let node = ResilientSuiClient::with_fallbacks_and_health_check(
+                services.get_node().clone(),
+                self.fallback_rpc,
+                self.health_check_interval
+                    .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL),
+            );
+
+            services = services.with_node(node);
+        }
+
+        let mut squad_connect = SquadConnect::from_services(services);
+
+        if let Some(portfolio_ttl) = self.portfolio_ttl {
+            squad_connect.set_portfolio_ttl(portfolio_ttl);
+        }
+
+        squad_connect.set_validate_move_calls(self.validate_move_calls);
+
+        if let Some(reauth_threshold_epochs) = self.reauth_threshold_epochs {
+            squad_connect.set_reauth_threshold_epochs(reauth_threshold_epochs);
+        }
+
+        if let Some(max_concurrent_sponsors) = self.max_concurrent_sponsors {
+            squad_connect.set_max_concurrent_sponsors(max_concurrent_sponsors);
+        }
+
+        if let Some(explorer_base_url) = self.explorer_base_url {
+            squad_connect.set_explorer_base_url(explorer_base_url);
+        }
+
+        squad_connect
+    }
+}
@@ -0,0 +1,413 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use serde::{Deserialize, Serialize};
+use sui_sdk::{
+    rpc_types::{EventFilter, SuiObjectDataFilter, SuiObjectDataOptions},
+    types::{
+        Identifier, TypeTag,
+        base_types::{ObjectID, SuiAddress},
+        parse_sui_struct_tag,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{CallArg, ObjectArg, TransactionKind},
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::squad_connect::{SquadConnect, account_address, clock_object, ptb_error};
+
+/// Address DeepBook v2 is published at on Mainnet and Testnet
+const DEEPBOOK_PACKAGE: &str = "0xdee9";
+
+const CLOB_MODULE: &str = "clob_v2";
+
+/// Event `clob_v2::create_pool` emits with the new pool and its asset types
+const POOL_CREATED_EVENT: &str = "PoolCreated";
+
+/// `self_matching_prevention` value cancelling the oldest of two crossing orders
+const CANCEL_OLDEST: u8 = 0;
+
+/// `restriction` value for a plain limit order that rests on the book
+const NO_RESTRICTION: u8 = 0;
+
+/// DeepBook package and the pools an application trades on
+///
+/// Pool IDs differ per network. Register the ones you use with `with_pool`
+/// and look them up by name, or register every pool of the connected
+/// network with `SquadConnect::load_deepbook_pools` and look them up by
+/// asset pair.
+#[derive(Debug, Clone)]
+pub struct DeepBookConfig {
+    pub package_id: ObjectID,
+    pub pools: HashMap<String, ObjectID>,
+}
+
+impl DeepBookConfig {
+    /// DeepBook v2 lives at `0xdee9` on both Mainnet and Testnet
+    pub fn mainnet() -> Self {
+        Self::default()
+    }
+
+    pub fn testnet() -> Self {
+        Self::default()
+    }
+
+    pub fn with_pool(mut self, name: &str, pool_id: ObjectID) -> Self {
+        self.pools.insert(name.to_string(), pool_id);
+        self
+    }
+
+    pub fn pool(&self, name: &str) -> Option<ObjectID> {
+        self.pools.get(name).copied()
+    }
+
+    /// Pool trading `base_asset` against `quote_asset`, once loaded with `load_deepbook_pools`
+    ///
+    /// Asset types may be given in short or long form, e.g. `0x2::sui::SUI`.
+    pub fn pair_pool(&self, base_asset: &str, quote_asset: &str) -> Option<ObjectID> {
+        self.pool(&pair_name(base_asset, quote_asset).ok()?)
+    }
+}
+
+impl Default for DeepBookConfig {
+    fn default() -> Self {
+        Self {
+            package_id: ObjectID::from_hex_literal(DEEPBOOK_PACKAGE)
+                .expect("DeepBook package address is valid"),
+            pools: HashMap::new(),
+        }
+    }
+}
+
+/// Aggregated depth of a pool, as `(price, total_quantity)` levels
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderbookSummary {
+    /// Bid levels, lowest price first
+    pub bids: Vec<(u64, u64)>,
+    /// Ask levels, lowest price first
+    pub asks: Vec<(u64, u64)>,
+}
+
+impl SquadConnect {
+    /// Places a limit order on a DeepBook pool from `account`
+    ///
+    /// Uses the first `custodian_v2::AccountCap` owned by the account, so
+    /// one must have been created with `clob_v2::create_account` first.
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn place_limit_order(
+        &self,
+        config: &DeepBookConfig,
+        pool_id: ObjectID,
+        client_order_id: u64,
+        price: u64,
+        quantity: u64,
+        is_bid: bool,
+        expire_timestamp: u64,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let type_args = self.pool_type_args(pool_id).await?;
+        let pool = self.get_shared_object_arg(pool_id, true).await?;
+        let account_cap = self.account_cap(config, account_address(&account)?).await?;
+
+        let args = vec![
+            CallArg::Object(pool),
+            pure_arg(&client_order_id)?,
+            pure_arg(&price)?,
+            pure_arg(&quantity)?,
+            pure_arg(&CANCEL_OLDEST)?,
+            pure_arg(&is_bid)?,
+            pure_arg(&expire_timestamp)?,
+            pure_arg(&NO_RESTRICTION)?,
+            CallArg::Object(clock_object()),
+            CallArg::Object(account_cap),
+        ];
+
+        let response = self
+            .call_move_function(
+                config.package_id,
+                CLOB_MODULE,
+                "place_limit_order",
+                type_args,
+                args,
+                gas_budget,
+                account,
+                zk_inputs,
+                max_epoch,
+                path,
+            )
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Cancels one of `account`'s open orders on a DeepBook pool
+    ///
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn cancel_order(
+        &self,
+        config: &DeepBookConfig,
+        pool_id: ObjectID,
+        order_id: u64,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let type_args = self.pool_type_args(pool_id).await?;
+        let pool = self.get_shared_object_arg(pool_id, true).await?;
+        let account_cap = self.account_cap(config, account_address(&account)?).await?;
+
+        let args = vec![
+            CallArg::Object(pool),
+            pure_arg(&order_id)?,
+            CallArg::Object(account_cap),
+        ];
+
+        let response = self
+            .call_move_function(
+                config.package_id,
+                CLOB_MODULE,
+                "cancel_order",
+                type_args,
+                args,
+                gas_budget,
+                account,
+                zk_inputs,
+                max_epoch,
+                path,
+            )
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Reads the full depth of a DeepBook pool
+    ///
+    /// The level-2 book getters are dev-inspected, so this costs no gas.
+    pub async fn get_orderbook(
+        &self,
+        config: &DeepBookConfig,
+        pool_id: ObjectID,
+    ) -> Result<OrderbookSummary> {
+        let type_args = self.pool_type_args(pool_id).await?;
+        let pool = self.get_shared_object_arg(pool_id, false).await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let pool = builder.obj(pool).map_err(ptb_error)?;
+        let clock = builder.obj(clock_object()).map_err(ptb_error)?;
+        let price_low = builder.pure(0u64).map_err(ptb_error)?;
+        let price_high = builder.pure(u64::MAX).map_err(ptb_error)?;
+
+        for function in [
+            "get_level2_book_status_bid_side",
+            "get_level2_book_status_ask_side",
+        ] {
+            builder.programmable_move_call(
+                config.package_id,
+                Identifier::new(CLOB_MODULE).map_err(ptb_error)?,
+                Identifier::new(function).map_err(ptb_error)?,
+                type_args.clone(),
+                vec![pool, price_low, price_high, clock],
+            );
+        }
+
+        let results = self
+            .get_node()
+            .read_api()
+            .dev_inspect_transaction_block(
+                SuiAddress::ZERO,
+                TransactionKind::ProgrammableTransaction(builder.finish()),
+                None,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to inspect orderbook: {}", e)))?;
+
+        if let Some(error) = results.error {
+            return Err(ServiceError::Service(format!(
+                "Orderbook query failed: {}",
+                error
+            )));
+        }
+
+        let mut sides = results
+            .results
+            .unwrap_or_default()
+            .into_iter()
+            .map(|result| {
+                let levels = result
+                    .return_values
+                    .iter()
+                    .map(|(bytes, _)| bcs::from_bytes::<Vec<u64>>(bytes))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .map_err(|e| {
+                        ServiceError::InvalidResponse(format!("Invalid orderbook level: {}", e))
+                    })?;
+
+                match levels.as_slice() {
+                    [prices, quantities] => Ok(prices
+                        .iter()
+                        .copied()
+                        .zip(quantities.iter().copied())
+                        .collect()),
+                    _ => Err(ServiceError::InvalidResponse(
+                        "Orderbook query returned unexpected values".to_string(),
+                    )),
+                }
+            })
+            .collect::<Result<Vec<Vec<(u64, u64)>>>>()?;
+
+        let asks = sides.pop();
+        let bids = sides.pop();
+
+        match (bids, asks) {
+            (Some(bids), Some(asks)) => Ok(OrderbookSummary { bids, asks }),
+            _ => Err(ServiceError::InvalidResponse(
+                "Orderbook query returned no levels".to_string(),
+            )),
+        }
+    }
+
+    /// Registers every DeepBook pool of the connected network in `config`
+    ///
+    /// Pools are read from the `clob_v2::PoolCreated` events of
+    /// `config.package_id`, so the IDs always belong to the connected
+    /// network. Each pool is registered under its asset pair, see
+    /// `DeepBookConfig::pair_pool`; when a pair has several pools, the
+    /// newest one is kept.
+    pub async fn load_deepbook_pools(&self, mut config: DeepBookConfig) -> Result<DeepBookConfig> {
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}::{}::{}",
+            config.package_id.to_hex_literal(),
+            CLOB_MODULE,
+            POOL_CREATED_EVENT
+        ))
+        .map_err(|e| ServiceError::Service(format!("Invalid event type: {}", e)))?;
+
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .get_node()
+                .event_api()
+                .query_events(
+                    EventFilter::MoveEventType(event_type.clone()),
+                    cursor,
+                    None,
+                    false,
+                )
+                .await
+                .map_err(|e| ServiceError::Network(format!("Failed to query events: {}", e)))?;
+
+            for event in &page.data {
+                let json = &event.parsed_json;
+                let pool_id = json["pool_id"]
+                    .as_str()
+                    .and_then(|id| ObjectID::from_hex_literal(id).ok())
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse("Pool event has no pool ID".to_string())
+                    })?;
+                let (Some(base_asset), Some(quote_asset)) = (
+                    json["base_asset"]["name"].as_str(),
+                    json["quote_asset"]["name"].as_str(),
+                ) else {
+                    return Err(ServiceError::InvalidResponse(format!(
+                        "Pool event of {} has no asset types",
+                        pool_id
+                    )));
+                };
+
+                config
+                    .pools
+                    .insert(pair_name(base_asset, quote_asset)?, pool_id);
+            }
+
+            if !page.has_next_page {
+                return Ok(config);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Base and quote asset types of a `Pool<BaseAsset, QuoteAsset>`
+    async fn pool_type_args(&self, pool_id: ObjectID) -> Result<Vec<TypeTag>> {
+        let response = self
+            .get_node()
+            .read_api()
+            .get_object_with_options(pool_id, SuiObjectDataOptions::new().with_type())
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get pool: {}", e)))?;
+
+        let pool_type = response
+            .data
+            .ok_or_else(|| ServiceError::InvalidResponse(format!("Pool {} not found", pool_id)))?
+            .object_type()
+            .map_err(|e| ServiceError::InvalidResponse(format!("Pool has no type: {}", e)))?;
+
+        let pool_type = parse_sui_struct_tag(&pool_type.to_string())
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid pool type: {}", e)))?;
+
+        Ok(pool_type.type_params)
+    }
+
+    async fn account_cap(&self, config: &DeepBookConfig, owner: SuiAddress) -> Result<ObjectArg> {
+        let cap_type = parse_sui_struct_tag(&format!(
+            "{}::custodian_v2::AccountCap",
+            config.package_id.to_hex_literal()
+        ))
+        .map_err(|e| ServiceError::Service(format!("Invalid account cap type: {}", e)))?;
+
+        let caps = self
+            .get_owned_objects(
+                owner,
+                Some(SuiObjectDataFilter::StructType(cap_type)),
+                SuiObjectDataOptions::new(),
+            )
+            .await?;
+
+        caps.first()
+            .map(|cap| ObjectArg::ImmOrOwnedObject(cap.object_ref()))
+            .ok_or_else(|| ServiceError::Service(format!("{} owns no DeepBook account cap", owner)))
+    }
+}
+
+/// Name a pool is registered under by `load_deepbook_pools`
+///
+/// `TypeName`s in events have no `0x` prefix, so it is added before the
+/// types are normalized.
+fn pair_name(base_asset: &str, quote_asset: &str) -> Result<String> {
+    let canonical = |asset: &str| {
+        let asset = if asset.starts_with("0x") {
+            asset.to_string()
+        } else {
+            format!("0x{}", asset)
+        };
+
+        parse_sui_struct_tag(&asset)
+            .map(|tag| tag.to_canonical_string(true))
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid asset type: {}", e)))
+    };
+
+    Ok(format!(
+        "{}/{}",
+        canonical(base_asset)?,
+        canonical(quote_asset)?
+    ))
+}
+
+fn pure_arg<T: Serialize>(value: &T) -> Result<CallArg> {
+    bcs::to_bytes(value).map(CallArg::Pure).map_err(ptb_error)
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use serde::{Deserialize, Serialize};
+use sui_sdk::{
+    rpc_types::{SuiEvent, SuiTransactionBlockEffects, SuiTypeTag},
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        transaction::{CallArg, Transaction},
+    },
+};
+
+use super::nft::NftInfo;
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+pub use sui_sdk::rpc_types::{Checkpoint, CheckpointId};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PublisherCapInfo {
+    pub cap_id: ObjectID,
+    pub package: ObjectID,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TransferPolicyRule {
+    pub rule_type: String,
+    pub config: serde_json::Value,
+}
+
+/// Active validator with the figures needed to pick one to stake with
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ValidatorSummary {
+    pub name: String,
+    pub address: SuiAddress,
+    /// SUI in the validator's staking pool, in MIST
+    pub stake: u64,
+    /// Commission in basis points
+    pub commission_rate: u16,
+    /// Estimated APY for stakers in basis points
+    pub apy_bps: u64,
+    /// Share of voting power out of 10 000
+    pub voting_power: u64,
+}
+
+/// State of a validator's staking pool in the current epoch
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StakingPoolInfo {
+    pub pool_id: ObjectID,
+    /// SUI in the pool, in MIST
+    pub active_stake: u64,
+    /// SUI joining the pool at the next epoch, in MIST
+    pub pending_stake: u64,
+    /// SUI leaving the pool at the next epoch, in MIST
+    pub pending_withdrawal: u64,
+    /// SUI per pool token
+    pub exchange_rate: f64,
+    pub epoch: u64,
+}
+
+/// A `StakedSui` object held by a staker
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StakePosition {
+    pub staked_sui_id: ObjectID,
+    pub validator: SuiAddress,
+    /// Staked SUI, in MIST
+    pub principal: u64,
+    /// Reward earned so far as estimated by the node, 0 until the stake is active
+    pub estimated_reward: u64,
+    pub activation_epoch: u64,
+}
+
+/// Every stake position of a staker, with totals
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DelegationPortfolio {
+    pub positions: Vec<StakePosition>,
+    pub total_principal_mist: u64,
+    pub total_estimated_reward_mist: u64,
+    /// Distinct validators staked with
+    pub validator_count: usize,
+}
+
+/// Move of stake suggested by `utils::staking::rebalance_suggestion`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RebalanceAction {
+    pub from_validator: SuiAddress,
+    pub to_validator: SuiAddress,
+    /// Principal to withdraw from `from_validator` and stake again, in MIST
+    pub amount_mist: u64,
+}
+
+/// Display details of a coin type, from its `CoinMetadata` object
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CoinMetadata {
+    pub name: String,
+    pub symbol: String,
+    /// Digits after the decimal point, e.g. 9 for SUI
+    pub decimals: u8,
+    pub description: String,
+    pub icon_url: Option<String>,
+}
+
+/// A transaction together with the checkpoint that includes it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CheckpointTransaction {
+    pub digest: String,
+    pub checkpoint: u64,
+    /// Timestamp of the checkpoint, in Unix milliseconds
+    pub timestamp_ms: u64,
+    pub effects: SuiTransactionBlockEffects,
+}
+
+/// Transaction executed by a fullnode but not yet in a checkpoint
+///
+/// Returned by `get_pending_transactions`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingTransaction {
+    pub digest: String,
+    pub sender: SuiAddress,
+    pub gas_price: u64,
+    pub gas_budget: u64,
+    /// Unix milliseconds, if the node reports a timestamp
+    pub submitted_at: Option<u64>,
+}
+
+/// Transactions of an address over a time range
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AddressActivity {
+    /// Transactions sent or received, each counted once
+    pub total_tx: u64,
+    pub sent_tx: u64,
+    /// Transactions sent by others that gave the address objects
+    pub received_tx: u64,
+    /// Net gas of the transactions sent, in MIST
+    pub total_gas_spent_mist: u64,
+    /// Unix milliseconds of the oldest transaction in the range
+    pub first_tx_ms: Option<u64>,
+    /// Unix milliseconds of the newest transaction in the range
+    pub last_tx_ms: Option<u64>,
+}
+
+/// Summary of one epoch
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EpochInfo {
+    pub epoch: u64,
+    pub start_timestamp_ms: u64,
+    /// `None` for the current epoch
+    pub end_timestamp_ms: Option<u64>,
+    /// Stake backing the committee, in MIST
+    pub total_stake: u64,
+    /// Storage fund balance, in MIST
+    pub storage_fund: u64,
+    pub reference_gas_price: u64,
+}
+
+/// Coin balances of an address at a point in time
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    /// Total balance in base units, keyed by coin type
+    pub balances: HashMap<String, u64>,
+    pub owner: SuiAddress,
+    pub fetched_at: Instant,
+}
+
+impl Portfolio {
+    /// Whether the balances are older than `max_age`
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.fetched_at.elapsed() >= max_age
+    }
+}
+
+/// Balances, NFTs and stakes of an account at a point in time
+///
+/// Returned by `SquadConnect::get_full_account_state`.
+#[derive(Debug, Clone)]
+pub struct AccountState {
+    pub address: SuiAddress,
+    /// SUI balance, in MIST
+    pub sui_balance: u64,
+    /// Total balance in base units, keyed by coin type
+    pub all_balances: HashMap<String, u64>,
+    pub nfts: Vec<NftInfo>,
+    pub stake_positions: Vec<StakePosition>,
+    /// Rewards earned by all stake positions, in MIST
+    pub pending_rewards_mist: u64,
+    pub fetched_at: Instant,
+}
+
+impl AccountState {
+    /// Whether the snapshot is older than `max_age`
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.fetched_at.elapsed() >= max_age
+    }
+}
+
+/// Everything needed to sign as a zkLogin account
+#[derive(Debug, Clone)]
+pub struct ZkLoginResult {
+    pub inputs: ZkLoginInputs,
+    pub account: AccountResponse,
+    pub address: SuiAddress,
+}
+
+impl TryFrom<(ZkLoginInputs, AccountResponse)> for ZkLoginResult {
+    type Error = ServiceError;
+
+    fn try_from((inputs, account): (ZkLoginInputs, AccountResponse)) -> Result<Self> {
+        let address = account.address.parse().map_err(|e| {
+            ServiceError::InvalidResponse(format!("Invalid account address: {}", e))
+        })?;
+
+        Ok(Self {
+            inputs,
+            account,
+            address,
+        })
+    }
+}
+
+/// A signed-in zkLogin account, as returned by `handle_oauth_redirect`
+///
+/// `address` has been checked against the one derived from `zk_inputs`.
+#[derive(Debug, Clone)]
+pub struct AuthResult {
+    pub zk_inputs: ZkLoginInputs,
+    pub account: AccountResponse,
+    pub address: SuiAddress,
+}
+
+/// Argument of a call in a multi-package PTB
+#[derive(Debug, Clone)]
+pub enum MoveCallArg {
+    /// An object or pure value passed as a transaction input
+    Input(CallArg),
+    /// The single value returned by the call at this index
+    Result(u16),
+    /// The value at the second index of the tuple returned by the call at the first
+    NestedResult(u16, u16),
+}
+
+impl From<CallArg> for MoveCallArg {
+    fn from(arg: CallArg) -> Self {
+        MoveCallArg::Input(arg)
+    }
+}
+
+/// One Move call of a multi-package PTB
+#[derive(Debug, Clone)]
+pub struct MoveCallSpec {
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+    pub type_args: Vec<SuiTypeTag>,
+    pub args: Vec<MoveCallArg>,
+}
+
+/// Signature of a Move function, with types written as in Move source
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MoveCallInfo {
+    pub parameters: Vec<String>,
+    pub return_types: Vec<String>,
+    /// Abilities each type parameter is constrained by, e.g. `"key + store"`
+    pub type_parameters: Vec<String>,
+    pub is_entry: bool,
+    /// `"public"`, `"friend"` or `"private"`
+    pub visibility: String,
+}
+
+/// A struct of a package that looks like an event, see `get_move_event_types`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MoveEventTypeInfo {
+    /// Full Move type, e.g. `0x...::nft_squad::Minted`
+    pub event_type: String,
+    pub module: String,
+    /// Name and type of each field, with types written as in Move source
+    pub fields: Vec<(String, String)>,
+}
+
+/// Outcome of a dev-inspected Move call
+#[derive(Debug, Clone)]
+pub struct MoveCallSimulation {
+    /// Values returned by the call, each as `{ "type": .., "bcs": <base64> }`
+    pub return_values: Vec<serde_json::Value>,
+    /// Computation and storage cost, before the storage rebate
+    pub gas_used_mist: u64,
+    /// `"success"`, or `"failure: <reason>"`
+    pub effects_status: String,
+    pub events: Vec<SuiEvent>,
+    pub error: Option<String>,
+}
+
+/// Emitted by the key rotation monitor after a new ephemeral key is created
+#[derive(Debug, Clone)]
+pub struct KeyRotationEvent {
+    pub new_max_epoch: u64,
+    /// Base64 public key of the new ephemeral key pair
+    pub new_public_key: String,
+    pub rotated_at: Instant,
+}
+
+/// State needed to resume a zkLogin session after `recover_seed_address`
+///
+/// The ephemeral keystore is not part of it; move it separately with
+/// `export_keystore` if the session continues on another device.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ZkLoginSession {
+    pub randomness: String,
+    pub public_key: String,
+    pub max_epoch: u64,
+    pub jwt: String,
+}
+
+/// How long the zkLogin proof of a session remains usable
+///
+/// Validity is decided by epochs: a proof signs for as long as the network
+/// has not passed `max_epoch`. `estimated_seconds_remaining` converts that
+/// to wall-clock time assuming every epoch lasts as long as the current
+/// one, so it is only an estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ProofValidity {
+    pub current_epoch: u64,
+    pub max_epoch: u64,
+    /// Epochs after the current one the proof still covers
+    pub epochs_remaining: u64,
+    pub estimated_seconds_remaining: u64,
+    pub is_valid: bool,
+}
+
+/// Who paid the gas of a transaction sent with `sponsor_with_fallback`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionFunding {
+    /// Enoki sponsored the transaction
+    Sponsored,
+    /// Enoki declined and the sender paid `gas_used_mist` itself
+    SelfPaid { gas_used_mist: u64 },
+}
+
+/// One transaction of a `batch_sponsor_transactions` batch
+///
+/// Takes the same arguments as `sponsor_transaction`.
+#[derive(Debug, Clone)]
+pub struct SponsorRequest {
+    pub tx: Transaction,
+    pub sender: SuiAddress,
+    pub allowed_addresses: Vec<String>,
+    pub allowed_move_call_targets: Vec<String>,
+}
+
+/// Next step of the zkLogin flow for a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZkLoginFlowState {
+    /// No ephemeral key or nonce yet; call `create_zkp_payload`
+    NeedsPayloadInit,
+    /// Waiting for the OAuth redirect; call `set_jwt` with its token
+    NeedsOauthCallback,
+    /// JWT set but no proof yet; call `recover_seed_address`
+    NeedsZkProof,
+    /// Everything needed to sign transactions is in place
+    ReadyToSign,
+}
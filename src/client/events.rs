@@ -0,0 +1,276 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use futures::StreamExt;
+use sui_sdk::{
+    SuiClient,
+    rpc_types::{EventFilter, SuiEvent, SuiTransactionBlockEffectsAPI, TransactionFilter},
+    types::base_types::SuiAddress,
+};
+use tokio::{
+    sync::broadcast::{self, Receiver, Sender},
+    task::JoinHandle,
+};
+
+use crate::service::types::{Result, ServiceError};
+
+use super::squad_connect::SquadConnect;
+
+/// Events buffered per receiver before slow consumers start lagging
+pub const DEFAULT_EVENT_BUFFER_SIZE: usize = 128;
+
+/// A live event subscription shared through a broadcast channel
+///
+/// The WebSocket connection lives in a background task that stops when the
+/// subscription is cancelled or dropped, or when every receiver is gone.
+/// Only the subscription holds the sender, so receivers see the channel
+/// close once it is dropped.
+pub struct EventSubscription {
+    pub receiver: Receiver<SuiEvent>,
+    pub handle: JoinHandle<()>,
+    sender: Sender<SuiEvent>,
+}
+
+impl EventSubscription {
+    /// Creates another receiver that gets every event from now on
+    pub fn subscribe(&self) -> Receiver<SuiEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Stops the background task and closes the WebSocket connection
+    pub fn cancel(self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for EventSubscription {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Change of one coin balance caused by a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChangeEvent {
+    pub coin_type: String,
+    pub old_balance: u64,
+    pub new_balance: u64,
+    /// `new_balance - old_balance`
+    pub delta: i128,
+    pub digest: String,
+    /// When the change was observed, in Unix milliseconds
+    pub timestamp_ms: u64,
+}
+
+/// Background watch of an address's balances
+///
+/// The task stops when `stop` is called or the monitor is dropped.
+pub struct BalanceMonitor {
+    pub handle: JoinHandle<()>,
+    pub sender: Arc<Sender<BalanceChangeEvent>>,
+}
+
+impl BalanceMonitor {
+    /// Creates another receiver that gets every change from now on
+    pub fn subscribe(&self) -> Receiver<BalanceChangeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Stops the background task and closes the WebSocket connection
+    pub fn stop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl Drop for BalanceMonitor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Balance changes between two snapshots keyed by coin type
+///
+/// A coin type missing from a snapshot has a zero balance. Changes are
+/// ordered by coin type; unchanged balances are skipped.
+pub fn diff_balances(
+    old: &HashMap<String, u64>,
+    new: &HashMap<String, u64>,
+    digest: &str,
+    timestamp_ms: u64,
+) -> Vec<BalanceChangeEvent> {
+    old.keys()
+        .chain(new.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|coin_type| {
+            let old_balance = old.get(coin_type).copied().unwrap_or_default();
+            let new_balance = new.get(coin_type).copied().unwrap_or_default();
+
+            (old_balance != new_balance).then(|| BalanceChangeEvent {
+                coin_type: coin_type.clone(),
+                old_balance,
+                new_balance,
+                delta: i128::from(new_balance) - i128::from(old_balance),
+                digest: digest.to_string(),
+                timestamp_ms,
+            })
+        })
+        .collect()
+}
+
+impl SquadConnect {
+    /// Watches the balances of `address` and broadcasts every change
+    ///
+    /// Sui emits no event for plain transfers, so the monitor subscribes to
+    /// transactions from or to `address` and re-reads its balances after
+    /// each one. `coin_type` limits the watch to one coin; `None` watches
+    /// every coin. The node must have been built with a WebSocket URL
+    /// (`SuiClientBuilder::ws_url`).
+    pub async fn subscribe_balance_changes(
+        &self,
+        address: SuiAddress,
+        coin_type: Option<&str>,
+    ) -> Result<BalanceMonitor> {
+        let node = self.get_node().clone();
+        let coin_type = coin_type.map(str::to_string);
+
+        let mut stream = node
+            .read_api()
+            .subscribe_transaction(TransactionFilter::FromOrToAddress { addr: address })
+            .await
+            .map_err(|e| {
+                ServiceError::Network(format!("Failed to subscribe to transactions: {}", e))
+            })?;
+
+        let mut balances = fetch_balances(&node, address, coin_type.as_deref()).await?;
+
+        let (sender, _) = broadcast::channel(DEFAULT_EVENT_BUFFER_SIZE);
+        let sender = Arc::new(sender);
+        let task_sender = sender.clone();
+
+        let handle = tokio::spawn(async move {
+            while let Some(effects) = stream.next().await {
+                let effects = match effects {
+                    Ok(effects) => effects,
+                    Err(e) => {
+                        log::error!("Transaction subscription failed: {}", e);
+                        break;
+                    }
+                };
+
+                let current = match fetch_balances(&node, address, coin_type.as_deref()).await {
+                    Ok(current) => current,
+                    Err(e) => {
+                        log::error!("Failed to refresh balances: {}", e);
+                        continue;
+                    }
+                };
+
+                let timestamp_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default();
+
+                for change in diff_balances(
+                    &balances,
+                    &current,
+                    &effects.transaction_digest().to_string(),
+                    timestamp_ms,
+                ) {
+                    // No receivers right now is fine; new ones can subscribe later
+                    let _ = task_sender.send(change);
+                }
+
+                balances = current;
+            }
+        });
+
+        Ok(BalanceMonitor { handle, sender })
+    }
+
+    /// Subscribes to events matching `filter`
+    ///
+    /// The node must have been built with a WebSocket URL
+    /// (`SuiClientBuilder::ws_url`).
+    pub async fn subscribe_to_events(&self, filter: EventFilter) -> Result<EventSubscription> {
+        self.subscribe_to_events_with_buffer(filter, DEFAULT_EVENT_BUFFER_SIZE)
+            .await
+    }
+
+    /// Same as `subscribe_to_events` with `buffer_size` events buffered per receiver
+    pub async fn subscribe_to_events_with_buffer(
+        &self,
+        filter: EventFilter,
+        buffer_size: usize,
+    ) -> Result<EventSubscription> {
+        let mut stream = self
+            .get_node()
+            .event_api()
+            .subscribe_event(filter)
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to subscribe to events: {}", e)))?;
+
+        let (sender, receiver) = broadcast::channel(buffer_size);
+        let weak_sender = sender.downgrade();
+
+        let handle = tokio::spawn(async move {
+            while let Some(event) = stream.next().await {
+                let Some(sender) = weak_sender.upgrade() else {
+                    break;
+                };
+
+                match event {
+                    Ok(event) => {
+                        if sender.send(event).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Event subscription failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(EventSubscription {
+            receiver,
+            handle,
+            sender,
+        })
+    }
+}
+
+/// Balances of `address`, for `coin_type` only if given
+async fn fetch_balances(
+    node: &SuiClient,
+    address: SuiAddress,
+    coin_type: Option<&str>,
+) -> Result<HashMap<String, u64>> {
+    let balances = match coin_type {
+        Some(coin_type) => vec![
+            node.coin_read_api()
+                .get_balance(address, Some(coin_type.to_string()))
+                .await
+                .map_err(|e| ServiceError::Network(format!("Failed to get balance: {}", e)))?,
+        ],
+        None => node
+            .coin_read_api()
+            .get_all_balances(address)
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get balances: {}", e)))?,
+    };
+
+    Ok(balances
+        .into_iter()
+        .map(|balance| {
+            (
+                balance.coin_type,
+                u64::try_from(balance.total_balance).unwrap_or(u64::MAX),
+            )
+        })
+        .collect())
+}
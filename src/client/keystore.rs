@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+use aes_gcm::{
+    Aes256Gcm, Key, KeyInit, Nonce,
+    aead::{Aead, AeadCore, OsRng},
+};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt};
+
+use crate::service::types::{Result, ServiceError};
+
+use super::squad_connect::{SquadConnect, unix_time_ms};
+
+/// Length of the AES-256 key expected by `export_keystore` and `import_keystore`
+pub const KEYSTORE_KEY_LEN: usize = 32;
+
+/// AES-GCM nonce length
+const NONCE_LEN: usize = 12;
+
+/// Owner read and write only, as the keystore holds a private key
+#[cfg(unix)]
+const KEYSTORE_MODE: u32 = 0o600;
+
+/// Ephemeral keystore encrypted with AES-256-GCM
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeystoreExport {
+    pub ciphertext: Vec<u8>,
+    pub nonce: Vec<u8>,
+    /// When the export was made, in Unix milliseconds
+    pub created_at: u64,
+}
+
+impl SquadConnect {
+    /// Encrypts the keystore file at `path` for moving it to another device
+    ///
+    /// Returns a JSON-encoded `KeystoreExport`; pass it unchanged to
+    /// `import_keystore` along with the same 32-byte `encryption_key`. The
+    /// key is never stored, so keep it out of the exported session state.
+    pub async fn export_keystore(&self, path: PathBuf, encryption_key: &[u8]) -> Result<Vec<u8>> {
+        let cipher = keystore_cipher(encryption_key)?;
+        let keystore = fs::read(&path)
+            .await
+            .map_err(|e| ServiceError::Service(format!("Failed to read keystore: {}", e)))?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, keystore.as_slice())
+            .map_err(|e| ServiceError::Service(format!("Failed to encrypt keystore: {}", e)))?;
+
+        serde_json::to_vec(&KeystoreExport {
+            ciphertext,
+            nonce: nonce.to_vec(),
+            created_at: unix_time_ms(),
+        })
+        .map_err(|e| ServiceError::Service(format!("Failed to serialize keystore: {}", e)))
+    }
+
+    /// Decrypts an `export_keystore` result and writes the keystore to `destination_path`
+    ///
+    /// On Unix the file is only readable and writable by its owner. Fails
+    /// with `ServiceError::InvalidProof` if the key is wrong or the export
+    /// was tampered with; nothing is written in that case.
+    pub async fn import_keystore(
+        &mut self,
+        ciphertext: &[u8],
+        encryption_key: &[u8],
+        destination_path: PathBuf,
+    ) -> Result<()> {
+        let cipher = keystore_cipher(encryption_key)?;
+        let export: KeystoreExport = serde_json::from_slice(ciphertext)
+            .map_err(|e| ServiceError::Service(format!("Invalid keystore export: {}", e)))?;
+
+        if export.nonce.len() != NONCE_LEN {
+            return Err(ServiceError::InvalidProof(
+                "Invalid keystore nonce".to_string(),
+            ));
+        }
+
+        let keystore = cipher
+            .decrypt(
+                Nonce::from_slice(&export.nonce),
+                export.ciphertext.as_slice(),
+            )
+            .map_err(|_| ServiceError::InvalidProof("Failed to decrypt keystore".to_string()))?;
+
+        if let Some(parent) = destination_path.parent() {
+            fs::create_dir_all(parent).await.map_err(|e| {
+                ServiceError::Service(format!("Failed to create keystore directory: {}", e))
+            })?;
+        }
+
+        write_keystore(&destination_path, &keystore)
+            .await
+            .map_err(|e| ServiceError::Service(format!("Failed to write keystore: {}", e)))
+    }
+}
+
+fn keystore_cipher(encryption_key: &[u8]) -> Result<Aes256Gcm> {
+    if encryption_key.len() != KEYSTORE_KEY_LEN {
+        return Err(ServiceError::Service(format!(
+            "Keystore encryption key must be {} bytes, got {}",
+            KEYSTORE_KEY_LEN,
+            encryption_key.len()
+        )));
+    }
+
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(encryption_key)))
+}
+
+/// Writes `keystore` to `path`, restricting access before any byte is written
+async fn write_keystore(path: &Path, keystore: &[u8]) -> std::io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(KEYSTORE_MODE);
+
+    let mut file = options.open(path).await?;
+    // `mode` only applies to new files
+    #[cfg(unix)]
+    file.set_permissions(Permissions::from_mode(KEYSTORE_MODE))
+        .await?;
+
+    file.write_all(keystore).await?;
+    file.flush().await
+}
@@ -0,0 +1,261 @@
+use std::{path::PathBuf, str::FromStr};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use move_binary_format::file_format::{Ability, AbilitySet, Bytecode, SignatureToken, Visibility};
+use sui_sdk::{
+    rpc_types::SuiTransactionBlockResponseOptions,
+    types::{
+        Identifier, MOVE_STDLIB_ADDRESS, MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_ADDRESS,
+        SUI_FRAMEWORK_PACKAGE_ID,
+        base_types::{ObjectID, SuiAddress},
+        digests::TransactionDigest,
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::{
+    move_package::{ModuleBuilder, published_package_id},
+    squad_connect::{SquadConnect, account_address, ptb_error},
+};
+
+/// Module of the memo helper package, also its package name
+const MEMO_MODULE: &str = "squad_memo";
+
+const MEMO_EVENT: &str = "MemoEvent";
+
+/// Serialized bytecode of the memo helper package, equivalent to:
+///
+/// ```move
+/// module squad_memo::squad_memo;
+///
+/// use std::string::String;
+/// use sui::event;
+///
+/// public struct MemoEvent has copy, drop {
+///     sender: address,
+///     recipient: address,
+///     memo: String,
+/// }
+///
+/// public fun emit(recipient: address, memo: String, ctx: &TxContext) {
+///     event::emit(MemoEvent { sender: ctx.sender(), recipient, memo });
+/// }
+/// ```
+///
+/// `emit` only records the memo; the payment itself is a separate command
+/// of the same transaction.
+fn memo_module_bytes() -> Result<Vec<u8>> {
+    let mut builder = ModuleBuilder::new(MEMO_MODULE)?;
+    let copy_drop = AbilitySet::EMPTY | Ability::Copy | Ability::Drop;
+
+    let string_module = builder.module_handle(MOVE_STDLIB_ADDRESS, "string")?;
+    let event_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "event")?;
+    let tx_context_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "tx_context")?;
+
+    let string_type =
+        builder.datatype(string_module, "String", copy_drop | Ability::Store, vec![])?;
+    let tx_context_type = builder.datatype(
+        tx_context_module,
+        "TxContext",
+        AbilitySet::EMPTY | Ability::Drop,
+        vec![],
+    )?;
+    let self_module = builder.self_module();
+    let memo_event_type = builder.datatype(self_module, MEMO_EVENT, copy_drop, vec![])?;
+
+    let string = SignatureToken::Datatype(string_type);
+    let ctx_ref = SignatureToken::Reference(Box::new(SignatureToken::Datatype(tx_context_type)));
+    let memo_event = SignatureToken::Datatype(memo_event_type);
+
+    let sender = builder.function(
+        tx_context_module,
+        "sender",
+        vec![ctx_ref.clone()],
+        vec![SignatureToken::Address],
+        vec![],
+    )?;
+    let event_emit = builder.function(
+        event_module,
+        "emit",
+        vec![SignatureToken::TypeParameter(0)],
+        vec![],
+        vec![copy_drop],
+    )?;
+    let emit_memo_event = builder.instantiate(event_emit, vec![memo_event]);
+
+    let memo_event_def = builder.struct_def(
+        memo_event_type,
+        vec![
+            ("sender", SignatureToken::Address),
+            ("recipient", SignatureToken::Address),
+            ("memo", string.clone()),
+        ],
+    )?;
+
+    let emit = builder.function(
+        self_module,
+        "emit",
+        vec![SignatureToken::Address, string, ctx_ref],
+        vec![],
+        vec![],
+    )?;
+    let locals = builder.signature(vec![]);
+    builder.function_def(
+        emit,
+        Visibility::Public,
+        false,
+        locals,
+        vec![
+            Bytecode::MoveLoc(2),
+            Bytecode::Call(sender),
+            Bytecode::MoveLoc(0),
+            Bytecode::MoveLoc(1),
+            Bytecode::Pack(memo_event_def),
+            Bytecode::CallGeneric(emit_memo_event),
+            Bytecode::Ret,
+        ],
+    );
+
+    builder.into_bytes()
+}
+
+impl SquadConnect {
+    /// Sends `amount_mist` SUI to `recipient` with a memo attached
+    ///
+    /// Sui transactions have no memo field, so the memo is emitted as a
+    /// `MemoEvent` in the same transaction as the transfer. The helper
+    /// package emitting it is published by `account` on first use; pass a
+    /// package published earlier to `set_memo_package_id` to skip that.
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_with_memo(
+        &mut self,
+        recipient: SuiAddress,
+        amount_mist: u64,
+        memo: &str,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let package_id = match self.memo_package_id() {
+            Some(package_id) => package_id,
+            None => {
+                self.publish_memo_package(
+                    sender,
+                    gas_budget,
+                    zk_inputs.clone(),
+                    max_epoch,
+                    path.clone(),
+                )
+                .await?
+            }
+        };
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                builder
+                    .pay_sui(vec![recipient], vec![amount_mist])
+                    .map_err(ptb_error)?;
+
+                let recipient = builder.pure(recipient).map_err(ptb_error)?;
+                let memo = builder.pure(memo.to_string()).map_err(ptb_error)?;
+
+                builder.programmable_move_call(
+                    package_id,
+                    Identifier::new(MEMO_MODULE).map_err(ptb_error)?,
+                    Identifier::new("emit").map_err(ptb_error)?,
+                    vec![],
+                    vec![recipient, memo],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Memos attached to transaction `digest` by `send_with_memo`
+    ///
+    /// Any `squad_memo::MemoEvent` counts, so memos sent through a package
+    /// published by another instance are found too.
+    pub async fn find_memo_events(&self, digest: &str) -> Result<Vec<String>> {
+        let digest = TransactionDigest::from_str(digest)
+            .map_err(|e| ServiceError::Service(format!("Invalid transaction digest: {}", e)))?;
+
+        let response = self
+            .get_node()
+            .read_api()
+            .get_transaction_with_options(
+                digest,
+                SuiTransactionBlockResponseOptions::new().with_events(),
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get transaction: {}", e)))?;
+
+        let events = response
+            .events
+            .map(|events| events.data)
+            .unwrap_or_default();
+
+        events
+            .iter()
+            .filter(|event| {
+                event.type_.module.as_str() == MEMO_MODULE
+                    && event.type_.name.as_str() == MEMO_EVENT
+            })
+            .map(|event| {
+                event.parsed_json["memo"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse("Memo event has no memo field".to_string())
+                    })
+            })
+            .collect()
+    }
+
+    /// Publishes the memo helper package and caches its ID
+    async fn publish_memo_package(
+        &mut self,
+        sender: SuiAddress,
+        gas_budget: u64,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<ObjectID> {
+        let bytes = memo_module_bytes()?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let upgrade_cap = builder.publish_upgradeable(
+                    vec![bytes],
+                    vec![MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID],
+                );
+                builder.transfer_arg(sender, upgrade_cap);
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        let package_id = published_package_id(&response)?;
+        self.set_memo_package_id(Some(package_id));
+
+        Ok(package_id)
+    }
+}
@@ -1 +1,28 @@
+pub mod builder;
+#[cfg(feature = "deepbook")]
+pub mod deepbook;
+pub mod dtos;
+#[cfg(feature = "ws")]
+pub mod events;
+#[cfg(feature = "encrypted-keystore")]
+pub mod keystore;
+pub mod memo;
+pub(crate) mod move_package;
+pub mod nft;
+pub(crate) mod nft_template;
+pub mod pipeline;
+#[cfg(feature = "portfolio-sim")]
+pub mod portfolio_sim;
+#[cfg(feature = "price-oracle")]
+pub mod price_oracle;
+#[cfg(feature = "randomness")]
+pub mod randomness;
+pub mod readonly;
+#[cfg(feature = "regulated-currency")]
+pub mod regulated_currency;
+pub mod resilient;
+#[cfg(feature = "cookie-session")]
+pub mod session;
 pub mod squad_connect;
+pub mod suins;
+pub mod upgrade;
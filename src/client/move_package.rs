@@ -0,0 +1,384 @@
+#[cfg(feature = "regulated-currency")]
+use std::{fs, process::Command};
+
+#[cfg(feature = "regulated-currency")]
+use fastcrypto::encoding::{Base64, Encoding};
+use move_binary_format::file_format::{
+    AbilitySet, AddressIdentifierIndex, Bytecode, CodeUnit, CompiledModule, Constant,
+    ConstantPoolIndex, DatatypeHandle, DatatypeHandleIndex, DatatypeTyParameter, FieldDefinition,
+    FunctionDefinition, FunctionHandle, FunctionHandleIndex, FunctionInstantiation,
+    FunctionInstantiationIndex, IdentifierIndex, ModuleHandle, ModuleHandleIndex, Signature,
+    SignatureIndex, SignatureToken, StructDefinition, StructDefinitionIndex,
+    StructFieldInformation, TypeSignature, Visibility, empty_module,
+};
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+#[cfg(feature = "regulated-currency")]
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::{ObjectChange, SuiTransactionBlockResponse},
+    types::base_types::ObjectID,
+};
+
+use crate::service::types::{Result, ServiceError};
+
+use super::squad_connect::ptb_error;
+
+/// Bytecode version of assembled modules, accepted by every Sui network
+const BYTECODE_VERSION: u32 = 6;
+
+/// Sui framework dependencies are implicit, so only the package address is declared
+#[cfg(feature = "regulated-currency")]
+const MOVE_TOML_TEMPLATE: &str = r#"[package]
+name = "{module}"
+edition = "2024"
+
+[addresses]
+{module} = "0x0"
+"#;
+
+/// Bytecode and dependencies of a compiled package, ready to publish
+#[cfg(feature = "regulated-currency")]
+pub(crate) struct CompiledPackage {
+    pub modules: Vec<Vec<u8>>,
+    pub dependencies: Vec<ObjectID>,
+}
+
+/// Output of `sui move build --dump-bytecode-as-base64`
+#[cfg(feature = "regulated-currency")]
+#[derive(Deserialize)]
+struct BuildOutput {
+    modules: Vec<String>,
+    dependencies: Vec<ObjectID>,
+}
+
+/// Compiles a single-module package named `module` with the `sui` CLI
+///
+/// The package is written to a temporary directory that is removed
+/// afterwards. The `sui` binary must be on `PATH`. The file writes and the
+/// compiler run on the blocking thread pool, off the async executor. Only
+/// modules generated from user input need this; fixed modules are
+/// assembled with `ModuleBuilder` instead.
+#[cfg(feature = "regulated-currency")]
+pub(crate) async fn compile_package(module: &str, source: &str) -> Result<CompiledPackage> {
+    let module = module.to_string();
+    let source = source.to_string();
+
+    tokio::task::spawn_blocking(move || compile_package_blocking(&module, &source))
+        .await
+        .map_err(|e| ServiceError::Service(format!("Move compiler task failed: {}", e)))?
+}
+
+#[cfg(feature = "regulated-currency")]
+fn compile_package_blocking(module: &str, source: &str) -> Result<CompiledPackage> {
+    let package_dir =
+        std::env::temp_dir().join(format!("squad_connect_{}_{}", module, uuid::Uuid::new_v4()));
+    let sources_dir = package_dir.join("sources");
+
+    let result = fs::create_dir_all(&sources_dir)
+        .and_then(|_| {
+            fs::write(
+                package_dir.join("Move.toml"),
+                MOVE_TOML_TEMPLATE.replace("{module}", module),
+            )
+        })
+        .and_then(|_| fs::write(sources_dir.join(format!("{}.move", module)), source))
+        .map_err(|e| ServiceError::Service(format!("Failed to write Move package: {}", e)))
+        .and_then(|_| {
+            Command::new("sui")
+                .args(["move", "build", "--dump-bytecode-as-base64", "--path"])
+                .arg(&package_dir)
+                .output()
+                .map_err(|e| ServiceError::Service(format!("Failed to run the sui CLI: {}", e)))
+        });
+
+    let _ = fs::remove_dir_all(&package_dir);
+    let output = result?;
+
+    if !output.status.success() {
+        return Err(ServiceError::Service(format!(
+            "Failed to compile Move package: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with('{'))
+        .ok_or_else(|| {
+            ServiceError::InvalidResponse("sui move build printed no bytecode".to_string())
+        })?;
+
+    let build: BuildOutput = serde_json::from_str(json)
+        .map_err(|e| ServiceError::InvalidResponse(format!("Invalid compiled package: {}", e)))?;
+
+    let modules = build
+        .modules
+        .iter()
+        .map(|module| {
+            Base64::decode(module)
+                .map_err(|e| ServiceError::InvalidResponse(format!("Invalid module bytes: {}", e)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CompiledPackage {
+        modules,
+        dependencies: build.dependencies,
+    })
+}
+
+/// Escapes `value` for a Move `b"..."` literal
+///
+/// Everything but letters, digits and spaces is written as a `\xHH` escape,
+/// so user input can't close the literal or inject code.
+#[cfg(feature = "regulated-currency")]
+pub(crate) fn move_byte_string(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || byte == b' ' {
+                (byte as char).to_string()
+            } else {
+                format!("\\x{:02x}", byte)
+            }
+        })
+        .collect()
+}
+
+/// ID of the package published by `response`
+pub(crate) fn published_package_id(response: &SuiTransactionBlockResponse) -> Result<ObjectID> {
+    response
+        .object_changes
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Published { package_id, .. } => Some(*package_id),
+            _ => None,
+        })
+        .ok_or_else(|| ServiceError::InvalidResponse("No package was published".to_string()))
+}
+
+/// ID of the first object of type `address::module::name<..>` created by `response`
+pub(crate) fn created_object_id(
+    response: &SuiTransactionBlockResponse,
+    address: AccountAddress,
+    module: &str,
+    name: &str,
+) -> Result<ObjectID> {
+    response
+        .object_changes
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Created {
+                object_type,
+                object_id,
+                ..
+            } if object_type.address == address
+                && object_type.module.as_str() == module
+                && object_type.name.as_str() == name =>
+            {
+                Some(*object_id)
+            }
+            _ => None,
+        })
+        .ok_or_else(|| {
+            ServiceError::InvalidResponse(format!("No {}::{} was created", module, name))
+        })
+}
+
+/// Appends to a `CompiledModule`, reusing pool entries so the module has no duplicates
+///
+/// Modules are assembled instruction by instruction, so publishing them
+/// needs no Move compiler at runtime.
+pub(crate) struct ModuleBuilder {
+    module: CompiledModule,
+}
+
+impl ModuleBuilder {
+    pub(crate) fn new(name: &str) -> Result<Self> {
+        let mut module = empty_module();
+        module.version = BYTECODE_VERSION;
+        module.identifiers[0] = Identifier::new(name).map_err(ptb_error)?;
+
+        Ok(Self { module })
+    }
+
+    /// Handle of the module being built, for the types and functions it declares
+    pub(crate) fn self_module(&self) -> ModuleHandleIndex {
+        self.module.self_module_handle_idx
+    }
+
+    /// Serialized bytecode of the module, ready to publish
+    pub(crate) fn into_bytes(self) -> Result<Vec<u8>> {
+        let mut bytes = vec![];
+        self.module
+            .serialize_with_version(BYTECODE_VERSION, &mut bytes)
+            .map_err(ptb_error)?;
+
+        Ok(bytes)
+    }
+
+    fn identifier(&mut self, name: &str) -> Result<IdentifierIndex> {
+        let identifier = Identifier::new(name).map_err(ptb_error)?;
+        let pool = &mut self.module.identifiers;
+
+        Ok(IdentifierIndex::new(intern(pool, identifier)))
+    }
+
+    pub(crate) fn module_handle(
+        &mut self,
+        address: AccountAddress,
+        name: &str,
+    ) -> Result<ModuleHandleIndex> {
+        let address =
+            AddressIdentifierIndex::new(intern(&mut self.module.address_identifiers, address));
+        let name = self.identifier(name)?;
+
+        Ok(ModuleHandleIndex::new(intern(
+            &mut self.module.module_handles,
+            ModuleHandle { address, name },
+        )))
+    }
+
+    pub(crate) fn datatype(
+        &mut self,
+        module: ModuleHandleIndex,
+        name: &str,
+        abilities: AbilitySet,
+        type_parameters: Vec<DatatypeTyParameter>,
+    ) -> Result<DatatypeHandleIndex> {
+        let name = self.identifier(name)?;
+
+        Ok(DatatypeHandleIndex::new(intern(
+            &mut self.module.datatype_handles,
+            DatatypeHandle {
+                module,
+                name,
+                abilities,
+                type_parameters,
+            },
+        )))
+    }
+
+    pub(crate) fn signature(&mut self, tokens: Vec<SignatureToken>) -> SignatureIndex {
+        SignatureIndex::new(intern(&mut self.module.signatures, Signature(tokens)))
+    }
+
+    pub(crate) fn function(
+        &mut self,
+        module: ModuleHandleIndex,
+        name: &str,
+        parameters: Vec<SignatureToken>,
+        return_: Vec<SignatureToken>,
+        type_parameters: Vec<AbilitySet>,
+    ) -> Result<FunctionHandleIndex> {
+        let name = self.identifier(name)?;
+        let parameters = self.signature(parameters);
+        let return_ = self.signature(return_);
+
+        Ok(FunctionHandleIndex::new(intern(
+            &mut self.module.function_handles,
+            FunctionHandle {
+                module,
+                name,
+                parameters,
+                return_,
+                type_parameters,
+            },
+        )))
+    }
+
+    pub(crate) fn instantiate(
+        &mut self,
+        handle: FunctionHandleIndex,
+        type_arguments: Vec<SignatureToken>,
+    ) -> FunctionInstantiationIndex {
+        let type_parameters = self.signature(type_arguments);
+
+        FunctionInstantiationIndex::new(intern(
+            &mut self.module.function_instantiations,
+            FunctionInstantiation {
+                handle,
+                type_parameters,
+            },
+        ))
+    }
+
+    pub(crate) fn struct_def(
+        &mut self,
+        handle: DatatypeHandleIndex,
+        fields: Vec<(&str, SignatureToken)>,
+    ) -> Result<StructDefinitionIndex> {
+        let fields = fields
+            .into_iter()
+            .map(|(name, token)| {
+                Ok(FieldDefinition {
+                    name: self.identifier(name)?,
+                    signature: TypeSignature(token),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.module.struct_defs.push(StructDefinition {
+            struct_handle: handle,
+            field_information: StructFieldInformation::Declared(fields),
+        });
+
+        Ok(StructDefinitionIndex::new(
+            (self.module.struct_defs.len() - 1) as u16,
+        ))
+    }
+
+    pub(crate) fn function_def(
+        &mut self,
+        function: FunctionHandleIndex,
+        visibility: Visibility,
+        is_entry: bool,
+        locals: SignatureIndex,
+        code: Vec<Bytecode>,
+    ) {
+        self.module.function_defs.push(FunctionDefinition {
+            function,
+            visibility,
+            is_entry,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals,
+                code,
+                ..Default::default()
+            }),
+        });
+    }
+
+    /// Instructions pushing `value` as a `std::string::String`
+    pub(crate) fn load_string(
+        &mut self,
+        value: &str,
+        utf8: FunctionHandleIndex,
+    ) -> Result<[Bytecode; 2]> {
+        let data = bcs::to_bytes(value.as_bytes()).map_err(ptb_error)?;
+        let constant = ConstantPoolIndex::new(intern(
+            &mut self.module.constant_pool,
+            Constant {
+                type_: SignatureToken::Vector(Box::new(SignatureToken::U8)),
+                data,
+            },
+        ));
+
+        Ok([Bytecode::LdConst(constant), Bytecode::Call(utf8)])
+    }
+}
+
+/// Index of `value` in `pool`, appending it if it isn't there yet
+fn intern<T: PartialEq>(pool: &mut Vec<T>, value: T) -> u16 {
+    match pool.iter().position(|existing| *existing == value) {
+        Some(index) => index as u16,
+        None => {
+            pool.push(value);
+            (pool.len() - 1) as u16
+        }
+    }
+}
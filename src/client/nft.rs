@@ -0,0 +1,625 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use move_core_types::account_address::AccountAddress;
+use sui_sdk::{
+    rpc_types::{
+        ObjectChange, SuiObjectData, SuiObjectDataOptions, SuiRawData,
+        SuiTransactionBlockResponseOptions,
+    },
+    types::{
+        Identifier, MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_PACKAGE_ID,
+        base_types::{ObjectID, ObjectRef, ObjectType, SuiAddress},
+        digests::TransactionDigest,
+        object::Owner,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{ObjectArg, TransactionData},
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::{
+    move_package::{created_object_id, published_package_id},
+    nft_template::{CollectionTemplate, collection_module_bytes},
+    squad_connect::{
+        SquadConnect, account_address, move_object_fields, parse_object_id, ptb_error,
+    },
+};
+
+/// Longest module name derived from a collection name
+const MAX_MODULE_NAME_LEN: usize = 64;
+
+/// Templates of the `Display<Nft>` fields, in Sui's `{field}` syntax
+///
+/// Each value may mix literal text with the NFT's `name`, `description`
+/// and `image_url` fields, e.g. `"{name} #1"` or
+/// `"https://cdn.example.com/{image_url}"`.
+#[derive(Debug, Clone)]
+pub struct NftDisplayConfig {
+    pub name_field: String,
+    pub description_field: String,
+    pub image_url_field: String,
+}
+
+impl Default for NftDisplayConfig {
+    /// Shows each NFT's own fields unchanged
+    fn default() -> Self {
+        Self {
+            name_field: "{name}".to_string(),
+            description_field: "{description}".to_string(),
+            image_url_field: "{image_url}".to_string(),
+        }
+    }
+}
+
+/// Objects created by `create_nft_collection`
+#[derive(Debug, Clone)]
+pub struct CollectionResult {
+    pub package_id: ObjectID,
+    /// `Publisher` to pass to `mint_nft`
+    pub publisher_id: ObjectID,
+    pub display_id: ObjectID,
+    pub collection_id: ObjectID,
+    pub digest: String,
+}
+
+/// Outcome of `create_sponsored_nft_mint`
+#[derive(Debug, Clone)]
+pub struct MintResult {
+    pub digest: String,
+    pub nft_id: ObjectID,
+}
+
+/// An owned object with a `Display`, as returned by `get_owned_nfts`
+#[derive(Debug, Clone)]
+pub struct NftInfo {
+    pub object_id: ObjectID,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    /// Full Move type, e.g. `0x...::nft_squad::Nft`
+    pub type_: String,
+}
+
+impl SquadConnect {
+    /// Publishes an NFT collection with the default `NftDisplayConfig`
+    ///
+    /// See `create_nft_collection_with_display`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_nft_collection(
+        &mut self,
+        name: &str,
+        description: &str,
+        image_url: &str,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<CollectionResult> {
+        self.create_nft_collection_with_display(
+            name,
+            description,
+            image_url,
+            &NftDisplayConfig::default(),
+            gas_budget,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+        )
+        .await
+    }
+
+    /// Publishes an NFT collection implementing the Sui `Display` standard
+    ///
+    /// A module is generated from a bytecode template, see
+    /// `collection_module_bytes`: the `Display` needs the `Publisher` of the
+    /// NFT type, which only a freshly published module can claim. The
+    /// module is named after `name`. The publisher receives the
+    /// `Publisher`, the `Display<Nft>`, the `Collection` and the package's
+    /// `UpgradeCap`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_nft_collection_with_display(
+        &mut self,
+        name: &str,
+        description: &str,
+        image_url: &str,
+        display: &NftDisplayConfig,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<CollectionResult> {
+        let sender = account_address(&account)?;
+        let module = collection_module_name(name);
+        let bytes = collection_module_bytes(&CollectionTemplate {
+            module: &module,
+            name,
+            description,
+            image_url,
+            display,
+        })?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let upgrade_cap = builder.publish_upgradeable(
+                    vec![bytes],
+                    vec![MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID],
+                );
+                builder.transfer_arg(sender, upgrade_cap);
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        let package_id = published_package_id(&response)?;
+
+        Ok(CollectionResult {
+            package_id,
+            publisher_id: created_object_id(
+                &response,
+                SUI_FRAMEWORK_ADDRESS,
+                "package",
+                "Publisher",
+            )?,
+            display_id: created_object_id(&response, SUI_FRAMEWORK_ADDRESS, "display", "Display")?,
+            collection_id: created_object_id(
+                &response,
+                AccountAddress::from(package_id),
+                &module,
+                "Collection",
+            )?,
+            digest: response.digest.to_string(),
+        })
+    }
+
+    /// Mints an NFT of the collection `publisher` belongs to and sends it to `recipient`
+    ///
+    /// The package and module are read from the `Publisher` object. Returns
+    /// the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn mint_nft(
+        &mut self,
+        publisher: ObjectRef,
+        name: &str,
+        description: &str,
+        url: &str,
+        recipient: SuiAddress,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let (package_id, module) = self.publisher_module(publisher.0).await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let publisher = builder
+                    .obj(ObjectArg::ImmOrOwnedObject(publisher))
+                    .map_err(ptb_error)?;
+                let name = builder.pure(name.to_string()).map_err(ptb_error)?;
+                let description = builder.pure(description.to_string()).map_err(ptb_error)?;
+                let url = builder.pure(url.to_string()).map_err(ptb_error)?;
+                let recipient = builder.pure(recipient).map_err(ptb_error)?;
+
+                builder.programmable_move_call(
+                    package_id,
+                    Identifier::new(module).map_err(ptb_error)?,
+                    Identifier::new("mint").map_err(ptb_error)?,
+                    vec![],
+                    vec![publisher, name, description, url, recipient],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Mints an NFT with `mint_function` and has Enoki pay the gas
+    ///
+    /// `mint_function` is `module::function` in `package`, taking the
+    /// name, description and URL as strings and the recipient address, in
+    /// that order. The call is sponsored and executed like
+    /// `sponsor_and_wait`, with `allowed_addresses` passed to Enoki and the
+    /// mint function as the only allowed Move call target. The NFT is the
+    /// first object created for `recipient` whose type is defined in the
+    /// mint function's module, matched through the package's type origins so
+    /// that upgraded packages are handled.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_sponsored_nft_mint(
+        &mut self,
+        package: ObjectID,
+        mint_function: &str,
+        nft_name: &str,
+        nft_description: &str,
+        nft_url: &str,
+        recipient: SuiAddress,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+    ) -> Result<MintResult> {
+        let sender = account_address(&account)?;
+        let (module, function) = mint_function.split_once("::").ok_or_else(|| {
+            ServiceError::Service(format!(
+                "Invalid mint function {}, expected module::function",
+                mint_function
+            ))
+        })?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let name = builder.pure(nft_name.to_string()).map_err(ptb_error)?;
+        let description = builder
+            .pure(nft_description.to_string())
+            .map_err(ptb_error)?;
+        let url = builder.pure(nft_url.to_string()).map_err(ptb_error)?;
+        let recipient_arg = builder.pure(recipient).map_err(ptb_error)?;
+
+        builder.programmable_move_call(
+            package,
+            Identifier::new(module).map_err(ptb_error)?,
+            Identifier::new(function).map_err(ptb_error)?,
+            vec![],
+            vec![name, description, url, recipient_arg],
+        );
+
+        let tx_data = TransactionData::new_programmable(sender, vec![], builder.finish(), 0, 0);
+        let type_origins = self.type_origins(package, module).await?;
+
+        let digest = self
+            .sponsor_and_wait(
+                tx_data,
+                account,
+                zk_inputs,
+                max_epoch,
+                path,
+                allowed_addresses,
+                vec![format!("{}::{}::{}", package, module, function)],
+            )
+            .await?;
+
+        let response = self
+            .get_node()
+            .read_api()
+            .get_transaction_with_options(
+                TransactionDigest::from_str(&digest)
+                    .map_err(|e| ServiceError::InvalidResponse(format!("Invalid digest: {}", e)))?,
+                SuiTransactionBlockResponseOptions::new().with_object_changes(),
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get transaction: {}", e)))?;
+
+        let nft_id = response
+            .object_changes
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .find_map(|change| match change {
+                ObjectChange::Created {
+                    owner: Owner::AddressOwner(owner),
+                    object_type,
+                    object_id,
+                    ..
+                } if *owner == recipient
+                    && object_type.module.as_str() == module
+                    && type_origins.contains(&ObjectID::from(object_type.address)) =>
+                {
+                    Some(*object_id)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("No NFT was minted in {}", digest))
+            })?;
+
+        Ok(MintResult { digest, nft_id })
+    }
+
+    /// IDs of the package versions that defined the types of `module`
+    ///
+    /// Types keep the ID of the version that introduced them, so after an
+    /// upgrade they no longer match the ID of the package being called.
+    async fn type_origins(&self, package: ObjectID, module: &str) -> Result<Vec<ObjectID>> {
+        let response = self
+            .get_object_with_options(package, SuiObjectDataOptions::new().with_bcs())
+            .await?;
+
+        match response.data.and_then(|data| data.bcs) {
+            Some(SuiRawData::Package(package)) => Ok(package
+                .type_origin_table
+                .into_iter()
+                .filter(|origin| origin.module_name == module)
+                .map(|origin| origin.package)
+                .collect()),
+            _ => Err(ServiceError::InvalidResponse(format!(
+                "Package {} not found",
+                package
+            ))),
+        }
+    }
+
+    /// Transfers an NFT owned by `account` to `recipient`
+    ///
+    /// The object reference is looked up first, so only the ID is needed.
+    /// Gas is paid from the sender's SUI coins. Fails with
+    /// `ServiceError::InvalidProof` if `account` does not own the NFT.
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_nft(
+        &mut self,
+        nft_id: ObjectID,
+        recipient: SuiAddress,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let nft = self.owned_object_ref(nft_id, sender).await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                builder.transfer_object(recipient, nft).map_err(ptb_error)
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Transfers an NFT owned by `account` to `recipient` with gas paid by Enoki
+    ///
+    /// See `transfer_object_with_sponsor`. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_nft_sponsored(
+        &mut self,
+        nft_id: ObjectID,
+        recipient: SuiAddress,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        self.assert_owned_by(nft_id, account_address(&account)?)
+            .await?;
+
+        self.transfer_object_with_sponsor(
+            nft_id,
+            recipient,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
+    }
+
+    /// Transfers several NFTs owned by `account` in a single transaction
+    ///
+    /// Each `(nft_id, recipient)` pair becomes one transfer command, so
+    /// either every NFT moves or none does. Returns the transaction digest.
+    pub async fn transfer_nft_batch(
+        &mut self,
+        transfers: Vec<(ObjectID, SuiAddress)>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        if transfers.is_empty() {
+            return Err(ServiceError::Service("No NFTs to transfer".to_string()));
+        }
+
+        let sender = account_address(&account)?;
+        let mut nfts = Vec::with_capacity(transfers.len());
+
+        for (nft_id, recipient) in transfers {
+            nfts.push((self.owned_object_ref(nft_id, sender).await?, recipient));
+        }
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                for (nft, recipient) in nfts {
+                    builder.transfer_object(recipient, nft).map_err(ptb_error)?;
+                }
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Current reference of `object_id`, read together with its owner
+    ///
+    /// Fails with `ServiceError::InvalidProof` unless `owner` owns it.
+    async fn owned_object_ref(&self, object_id: ObjectID, owner: SuiAddress) -> Result<ObjectRef> {
+        let object = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?
+            .data
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", object_id))
+            })?;
+
+        match object.owner {
+            Some(Owner::AddressOwner(address)) if address == owner => Ok(object.object_ref()),
+            _ => Err(ServiceError::InvalidProof(
+                "Object not owned by expected address".to_string(),
+            )),
+        }
+    }
+
+    /// Package and module a `Publisher` was claimed for
+    async fn publisher_module(&self, publisher_id: ObjectID) -> Result<(ObjectID, String)> {
+        let response = self
+            .get_node()
+            .read_api()
+            .get_object_with_options(publisher_id, SuiObjectDataOptions::new().with_content())
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get object: {}", e)))?;
+
+        let fields = response
+            .data
+            .as_ref()
+            .and_then(move_object_fields)
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", publisher_id))
+            })?;
+
+        let package = fields["package"].as_str().ok_or_else(|| {
+            ServiceError::InvalidResponse("Publisher has no package field".to_string())
+        })?;
+        let module = fields["module_name"].as_str().ok_or_else(|| {
+            ServiceError::InvalidResponse("Publisher has no module_name field".to_string())
+        })?;
+
+        Ok((parse_object_id(package)?, module.to_string()))
+    }
+
+    /// Objects of `owner` that render through the Display standard
+    ///
+    /// Coins and objects whose type has no `Display` registered are left
+    /// out. Missing display fields are `None`.
+    pub async fn get_owned_nfts(&self, owner: SuiAddress) -> Result<Vec<NftInfo>> {
+        let objects = self
+            .get_owned_objects(
+                owner,
+                None,
+                SuiObjectDataOptions::new().with_type().with_display(),
+            )
+            .await?;
+
+        Ok(objects.iter().filter_map(nft_info).collect())
+    }
+
+    /// Display fields of object `id`, rendered by the node
+    ///
+    /// Under the Sui Display standard, a package registers a
+    /// `0x2::display::Display<T>` for its type `T`, claimed with the
+    /// package's `Publisher`: `display::new_with_fields<T>(&publisher,
+    /// keys, values, ctx)`, then `display.update_version()` to publish it.
+    /// Values are templates such as `"{name}"` or
+    /// `"https://example.com/{id}"`, filled in from the object's fields when
+    /// it is read. `build_set_display_transaction` builds that call from
+    /// Rust. Field names are up to the package; `utils::display` has
+    /// accessors for the common `name`, `description`, `image_url` and
+    /// `link`. Objects whose type has no `Display` give an empty map.
+    pub async fn get_object_display_fields(&self, id: ObjectID) -> Result<HashMap<String, String>> {
+        let response = self
+            .get_node()
+            .read_api()
+            .get_object_with_options(id, SuiObjectDataOptions::new().with_display())
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get object: {}", e)))?;
+
+        let object = response
+            .data
+            .ok_or_else(|| ServiceError::InvalidResponse(format!("Object {} not found", id)))?;
+
+        Ok(object
+            .display
+            .and_then(|display| display.data)
+            .unwrap_or_default()
+            .into_iter()
+            .collect())
+    }
+
+    /// `get_owned_nfts` for the account Enoki reports for the stored JWT
+    pub async fn get_my_nfts(&self) -> Result<Vec<NftInfo>> {
+        let account = self.get_address().await?;
+
+        self.get_owned_nfts(account_address(&account)?).await
+    }
+}
+
+/// NFTs whose type is defined in `package_id`
+///
+/// Matches on the address part of the type, so `0x2` and its padded form
+/// select the same NFTs.
+pub fn filter_by_package<'a>(nfts: &'a [NftInfo], package_id: &str) -> Vec<&'a NftInfo> {
+    let Ok(package_id) = ObjectID::from_hex_literal(package_id) else {
+        return Vec::new();
+    };
+
+    nfts.iter()
+        .filter(|nft| {
+            nft.type_
+                .split("::")
+                .next()
+                .and_then(|address| ObjectID::from_hex_literal(address).ok())
+                == Some(package_id)
+        })
+        .collect()
+}
+
+fn nft_info(object: &SuiObjectData) -> Option<NftInfo> {
+    let type_ = match object.type_.as_ref()? {
+        ObjectType::Struct(type_) if !type_.is_coin() => type_.to_string(),
+        _ => return None,
+    };
+    let fields = object.display.as_ref()?.data.as_ref()?;
+
+    Some(NftInfo {
+        object_id: object.object_id,
+        name: fields.get("name").cloned(),
+        description: fields.get("description").cloned(),
+        image_url: fields.get("image_url").cloned(),
+        type_,
+    })
+}
+
+/// Module name for the collection `name`
+///
+/// Prefixed with `nft_` so it always starts with a letter and never
+/// collides with a Move keyword; anything but ASCII letters and digits
+/// becomes `_`.
+fn collection_module_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("nft_{}", sanitized.trim_matches('_'))
+        .chars()
+        .take(MAX_MODULE_NAME_LEN)
+        .collect()
+}
@@ -0,0 +1,310 @@
+use move_binary_format::file_format::{
+    Ability, AbilitySet, Bytecode, DatatypeTyParameter, SignatureToken, Visibility,
+};
+use sui_sdk::types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+use crate::service::types::Result;
+
+use super::{move_package::ModuleBuilder, nft::NftDisplayConfig};
+
+/// Values filled into the collection module template
+pub(crate) struct CollectionTemplate<'a> {
+    pub module: &'a str,
+    pub name: &'a str,
+    pub description: &'a str,
+    pub image_url: &'a str,
+    pub display: &'a NftDisplayConfig,
+}
+
+/// Serialized bytecode of the collection module, equivalent to:
+///
+/// ```move
+/// module {module}::{module};
+///
+/// public struct {MODULE} has drop {}
+/// public struct Collection has key, store { id: UID, name: String, description: String, image_url: String }
+/// public struct Nft has key, store { id: UID, name: String, description: String, image_url: String }
+///
+/// fun init(witness: {MODULE}, ctx: &mut TxContext) {
+///     let publisher = package::claim(witness, ctx);
+///     let mut display = display::new_with_fields<Nft>(&publisher, .., .., ctx);
+///     display.update_version();
+///     let collection = Collection { id: object::new(ctx), .. };
+///     transfer::public_transfer(display, ctx.sender());
+///     transfer::public_transfer(collection, ctx.sender());
+///     transfer::public_transfer(publisher, ctx.sender());
+/// }
+///
+/// public fun mint(publisher: &Publisher, name: String, description: String,
+///                 image_url: String, recipient: address, ctx: &mut TxContext) {
+///     assert!(publisher.from_module<Nft>(), 0);
+///     transfer::public_transfer(Nft { id: object::new(ctx), name, description, image_url }, recipient);
+/// }
+/// ```
+///
+/// The instructions are fixed; only the module and witness names and the
+/// string constants change, so no Move compiler is needed at runtime.
+pub(crate) fn collection_module_bytes(template: &CollectionTemplate) -> Result<Vec<u8>> {
+    let mut builder = ModuleBuilder::new(template.module)?;
+
+    let key_store = AbilitySet::EMPTY | Ability::Key | Ability::Store;
+
+    // Framework types and functions
+    let string_module = builder.module_handle(MOVE_STDLIB_ADDRESS, "string")?;
+    let package_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "package")?;
+    let display_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "display")?;
+    let object_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "object")?;
+    let transfer_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "transfer")?;
+    let tx_context_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "tx_context")?;
+
+    let string_type = builder.datatype(
+        string_module,
+        "String",
+        AbilitySet::EMPTY | Ability::Copy | Ability::Drop | Ability::Store,
+        vec![],
+    )?;
+    let uid_type = builder.datatype(
+        object_module,
+        "UID",
+        AbilitySet::EMPTY | Ability::Store,
+        vec![],
+    )?;
+    let publisher_type = builder.datatype(package_module, "Publisher", key_store, vec![])?;
+    let display_type = builder.datatype(
+        display_module,
+        "Display",
+        key_store,
+        vec![DatatypeTyParameter {
+            constraints: AbilitySet::EMPTY | Ability::Key,
+            is_phantom: true,
+        }],
+    )?;
+    let tx_context_type = builder.datatype(
+        tx_context_module,
+        "TxContext",
+        AbilitySet::EMPTY | Ability::Drop,
+        vec![],
+    )?;
+
+    // Types declared by the module itself
+    let self_module = builder.self_module();
+    let witness_type = builder.datatype(
+        self_module,
+        &template.module.to_ascii_uppercase(),
+        AbilitySet::EMPTY | Ability::Drop,
+        vec![],
+    )?;
+    let collection_type = builder.datatype(self_module, "Collection", key_store, vec![])?;
+    let nft_type = builder.datatype(self_module, "Nft", key_store, vec![])?;
+
+    let string = SignatureToken::Datatype(string_type);
+    let uid = SignatureToken::Datatype(uid_type);
+    let publisher = SignatureToken::Datatype(publisher_type);
+    let publisher_ref = SignatureToken::Reference(Box::new(publisher.clone()));
+    let ctx_ref = SignatureToken::Reference(Box::new(SignatureToken::Datatype(tx_context_type)));
+    let ctx_mut =
+        SignatureToken::MutableReference(Box::new(SignatureToken::Datatype(tx_context_type)));
+    let display_of = |type_arg: SignatureToken| {
+        SignatureToken::DatatypeInstantiation(Box::new((display_type, vec![type_arg])))
+    };
+    let strings = SignatureToken::Vector(Box::new(string.clone()));
+    let type_param = SignatureToken::TypeParameter(0);
+
+    let utf8 = builder.function(
+        string_module,
+        "utf8",
+        vec![SignatureToken::Vector(Box::new(SignatureToken::U8))],
+        vec![string.clone()],
+        vec![],
+    )?;
+    let claim = builder.function(
+        package_module,
+        "claim",
+        vec![type_param.clone(), ctx_mut.clone()],
+        vec![publisher.clone()],
+        vec![AbilitySet::EMPTY | Ability::Drop],
+    )?;
+    let from_module = builder.function(
+        package_module,
+        "from_module",
+        vec![publisher_ref.clone()],
+        vec![SignatureToken::Bool],
+        vec![AbilitySet::EMPTY],
+    )?;
+    let new_with_fields = builder.function(
+        display_module,
+        "new_with_fields",
+        vec![
+            publisher_ref.clone(),
+            strings.clone(),
+            strings,
+            ctx_mut.clone(),
+        ],
+        vec![display_of(type_param.clone())],
+        vec![AbilitySet::EMPTY | Ability::Key],
+    )?;
+    let update_version = builder.function(
+        display_module,
+        "update_version",
+        vec![SignatureToken::MutableReference(Box::new(display_of(
+            type_param.clone(),
+        )))],
+        vec![],
+        vec![AbilitySet::EMPTY | Ability::Key],
+    )?;
+    let object_new = builder.function(
+        object_module,
+        "new",
+        vec![ctx_mut.clone()],
+        vec![uid.clone()],
+        vec![],
+    )?;
+    let sender = builder.function(
+        tx_context_module,
+        "sender",
+        vec![ctx_ref],
+        vec![SignatureToken::Address],
+        vec![],
+    )?;
+    let public_transfer = builder.function(
+        transfer_module,
+        "public_transfer",
+        vec![type_param, SignatureToken::Address],
+        vec![],
+        vec![key_store],
+    )?;
+
+    let witness = SignatureToken::Datatype(witness_type);
+    let collection = SignatureToken::Datatype(collection_type);
+    let nft = SignatureToken::Datatype(nft_type);
+
+    let claim_witness = builder.instantiate(claim, vec![witness.clone()]);
+    let from_module_nft = builder.instantiate(from_module, vec![nft.clone()]);
+    let new_with_fields_nft = builder.instantiate(new_with_fields, vec![nft.clone()]);
+    let update_version_nft = builder.instantiate(update_version, vec![nft.clone()]);
+    let transfer_display = builder.instantiate(public_transfer, vec![display_of(nft.clone())]);
+    let transfer_collection = builder.instantiate(public_transfer, vec![collection.clone()]);
+    let transfer_publisher = builder.instantiate(public_transfer, vec![publisher.clone()]);
+    let transfer_nft = builder.instantiate(public_transfer, vec![nft]);
+
+    // Struct definitions, in the same order as their handles
+    builder.struct_def(witness_type, vec![("dummy_field", SignatureToken::Bool)])?;
+    let object_fields = vec![
+        ("id", uid),
+        ("name", string.clone()),
+        ("description", string.clone()),
+        ("image_url", string.clone()),
+    ];
+    let collection_def = builder.struct_def(collection_type, object_fields.clone())?;
+    let nft_def = builder.struct_def(nft_type, object_fields)?;
+
+    let string_element = builder.signature(vec![string.clone()]);
+
+    // init
+    let mut code = vec![
+        Bytecode::MoveLoc(0),
+        Bytecode::CopyLoc(1),
+        Bytecode::CallGeneric(claim_witness),
+        Bytecode::StLoc(2),
+        Bytecode::ImmBorrowLoc(2),
+    ];
+    for value in ["name", "description", "image_url"] {
+        code.extend(builder.load_string(value, utf8)?);
+    }
+    code.push(Bytecode::VecPack(string_element, 3));
+    for value in [
+        &template.display.name_field,
+        &template.display.description_field,
+        &template.display.image_url_field,
+    ] {
+        code.extend(builder.load_string(value, utf8)?);
+    }
+    code.extend([
+        Bytecode::VecPack(string_element, 3),
+        Bytecode::CopyLoc(1),
+        Bytecode::CallGeneric(new_with_fields_nft),
+        Bytecode::StLoc(3),
+        Bytecode::MutBorrowLoc(3),
+        Bytecode::CallGeneric(update_version_nft),
+        Bytecode::CopyLoc(1),
+        Bytecode::Call(object_new),
+    ]);
+    for value in [template.name, template.description, template.image_url] {
+        code.extend(builder.load_string(value, utf8)?);
+    }
+    code.extend([
+        Bytecode::Pack(collection_def),
+        Bytecode::StLoc(4),
+        Bytecode::MoveLoc(3),
+        Bytecode::CopyLoc(1),
+        Bytecode::FreezeRef,
+        Bytecode::Call(sender),
+        Bytecode::CallGeneric(transfer_display),
+        Bytecode::MoveLoc(4),
+        Bytecode::CopyLoc(1),
+        Bytecode::FreezeRef,
+        Bytecode::Call(sender),
+        Bytecode::CallGeneric(transfer_collection),
+        Bytecode::MoveLoc(2),
+        Bytecode::MoveLoc(1),
+        Bytecode::FreezeRef,
+        Bytecode::Call(sender),
+        Bytecode::CallGeneric(transfer_publisher),
+        Bytecode::Ret,
+    ]);
+
+    let init = builder.function(
+        self_module,
+        "init",
+        vec![witness, ctx_mut.clone()],
+        vec![],
+        vec![],
+    )?;
+    let init_locals = builder.signature(vec![
+        publisher,
+        display_of(SignatureToken::Datatype(nft_type)),
+        collection,
+    ]);
+    builder.function_def(init, Visibility::Private, false, init_locals, code);
+
+    // mint
+    let mint = builder.function(
+        self_module,
+        "mint",
+        vec![
+            publisher_ref,
+            string.clone(),
+            string.clone(),
+            string,
+            SignatureToken::Address,
+            ctx_mut,
+        ],
+        vec![],
+        vec![],
+    )?;
+    let mint_locals = builder.signature(vec![]);
+    builder.function_def(
+        mint,
+        Visibility::Public,
+        false,
+        mint_locals,
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::CallGeneric(from_module_nft),
+            Bytecode::BrTrue(5),
+            Bytecode::LdU64(0),
+            Bytecode::Abort,
+            Bytecode::MoveLoc(5),
+            Bytecode::Call(object_new),
+            Bytecode::MoveLoc(1),
+            Bytecode::MoveLoc(2),
+            Bytecode::MoveLoc(3),
+            Bytecode::Pack(nft_def),
+            Bytecode::MoveLoc(4),
+            Bytecode::CallGeneric(transfer_nft),
+            Bytecode::Ret,
+        ],
+    );
+
+    builder.into_bytes()
+}
@@ -0,0 +1,267 @@
+use std::{
+    ops::{Deref, DerefMut},
+    path::PathBuf,
+};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use sui_sdk::{
+    rpc_types::SuiTransactionBlockResponse,
+    types::{
+        Identifier, TypeTag,
+        base_types::{ObjectID, SuiAddress},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{Argument, CallArg, Command, ProgrammableTransaction},
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::squad_connect::{SquadConnect, account_address};
+
+#[derive(Debug, Clone)]
+enum PipelineStep {
+    MoveCall {
+        package: ObjectID,
+        module: String,
+        function: String,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+    },
+    Transfer {
+        recipient: SuiAddress,
+        object: Argument,
+    },
+    MergeCoins {
+        destination: Argument,
+        sources: Vec<Argument>,
+    },
+}
+
+/// Chains several commands into a single programmable transaction
+///
+/// Commands run atomically in the order they were added. The output of the
+/// `n`-th command can be fed into later ones as `Argument::Result(n)`.
+///
+/// # Example
+/// ```rust
+/// let response = TransactionPipeline::new()
+///     .add_move_call(package, "shop", "buy", vec![], vec![item, payment])
+///     .add_transfer(recipient, Argument::Result(0))
+///     .execute(&mut squad_connect, gas_budget, account, zk_inputs, max_epoch, path)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TransactionPipeline {
+    steps: Vec<PipelineStep>,
+}
+
+impl TransactionPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_move_call(
+        mut self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+    ) -> Self {
+        self.steps.push(PipelineStep::MoveCall {
+            package,
+            module: module.to_string(),
+            function: function.to_string(),
+            type_args,
+            args,
+        });
+        self
+    }
+
+    pub fn add_transfer(mut self, recipient: SuiAddress, object: Argument) -> Self {
+        self.steps
+            .push(PipelineStep::Transfer { recipient, object });
+        self
+    }
+
+    pub fn add_merge_coins(mut self, destination: Argument, sources: Vec<Argument>) -> Self {
+        self.steps.push(PipelineStep::MergeCoins {
+            destination,
+            sources,
+        });
+        self
+    }
+
+    /// Builds the programmable transaction from the accumulated commands
+    pub fn build(&self) -> Result<ProgrammableTransaction> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+
+        for step in &self.steps {
+            match step {
+                PipelineStep::MoveCall {
+                    package,
+                    module,
+                    function,
+                    type_args,
+                    args,
+                } => {
+                    let arguments = args
+                        .iter()
+                        .map(|arg| builder.input(arg.clone()))
+                        .collect::<std::result::Result<Vec<_>, _>>()
+                        .map_err(|e| {
+                            ServiceError::Service(format!("Failed to add call argument: {}", e))
+                        })?;
+
+                    builder.programmable_move_call(
+                        *package,
+                        identifier(module)?,
+                        identifier(function)?,
+                        type_args.clone(),
+                        arguments,
+                    );
+                }
+                PipelineStep::Transfer { recipient, object } => {
+                    builder.transfer_arg(*recipient, *object);
+                }
+                PipelineStep::MergeCoins {
+                    destination,
+                    sources,
+                } => {
+                    builder.command(Command::MergeCoins(*destination, sources.clone()));
+                }
+            }
+        }
+
+        Ok(builder.finish())
+    }
+
+    /// Builds, signs and executes the pipeline as one transaction from `account`
+    pub async fn execute(
+        &self,
+        squad_connect: &mut SquadConnect,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let sender = account_address(&account)?;
+
+        let tx_data = squad_connect
+            .programmable_transaction_data(sender, self.build()?, gas_budget)
+            .await?;
+
+        squad_connect
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+}
+
+/// `ProgrammableTransactionBuilder` with shortcuts for common commands
+///
+/// Dereferences to the wrapped builder, so inputs are still added with
+/// `pure` and `obj`. Get one from `SquadConnect::create_transaction_builder`
+/// and run it with `SquadConnect::execute_builder`.
+///
+/// # Example
+/// ```rust
+/// let mut builder = squad_connect.create_transaction_builder();
+/// let amount = builder.pure(1_000_000u64)?;
+/// builder.add_sui_transfer(recipient, amount);
+///
+/// let response = squad_connect
+///     .execute_builder(builder, gas_budget, account, zk_inputs, max_epoch, path)
+///     .await?;
+/// ```
+#[derive(Default)]
+pub struct ProgrammableTxBuilder(ProgrammableTransactionBuilder);
+
+impl ProgrammableTxBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Splits `amount` MIST off the gas coin and sends it to `recipient`
+    pub fn add_sui_transfer(&mut self, recipient: SuiAddress, amount: Argument) {
+        let coin = self
+            .0
+            .command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+        self.0.transfer_arg(recipient, coin);
+    }
+
+    pub fn add_object_transfer(&mut self, recipient: SuiAddress, obj: Argument) {
+        self.0.transfer_arg(recipient, obj);
+    }
+
+    /// Adds a Move call and returns its result for use in later commands
+    pub fn add_move_call(
+        &mut self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<Argument>,
+    ) -> Result<Argument> {
+        Ok(self.0.programmable_move_call(
+            package,
+            identifier(module)?,
+            identifier(function)?,
+            type_args,
+            args,
+        ))
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        self.0.finish()
+    }
+}
+
+impl Deref for ProgrammableTxBuilder {
+    type Target = ProgrammableTransactionBuilder;
+
+    fn deref(&self) -> &ProgrammableTransactionBuilder {
+        &self.0
+    }
+}
+
+impl DerefMut for ProgrammableTxBuilder {
+    fn deref_mut(&mut self) -> &mut ProgrammableTransactionBuilder {
+        &mut self.0
+    }
+}
+
+impl SquadConnect {
+    /// Starts a programmable transaction, see `ProgrammableTxBuilder`
+    pub fn create_transaction_builder(&self) -> ProgrammableTxBuilder {
+        ProgrammableTxBuilder::new()
+    }
+
+    /// Signs and executes the commands of `builder` as one transaction from `account`
+    pub async fn execute_builder(
+        &mut self,
+        builder: ProgrammableTxBuilder,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let sender = account_address(&account)?;
+
+        let tx_data = self
+            .programmable_transaction_data(sender, builder.finish(), gas_budget)
+            .await?;
+
+        self.sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+}
+
+fn identifier(name: &str) -> Result<Identifier> {
+    Identifier::new(name)
+        .map_err(|e| ServiceError::Service(format!("Invalid Move identifier {}: {}", name, e)))
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use sui_sdk::{
+    rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI},
+    types::{
+        base_types::{ObjectID, SuiAddress},
+        object::Owner,
+        transaction::ProgrammableTransaction,
+    },
+};
+
+use crate::{
+    service::types::{Result, ServiceError},
+    utils::coins::coin_type_to_move_type,
+};
+
+use super::squad_connect::SquadConnect;
+
+/// Gas budget the simulated transaction is given, in MIST
+///
+/// Only needs to cover the dry run; `sender` must own this much SUI.
+const SIMULATION_GAS_BUDGET: u64 = 50_000_000;
+
+/// What a PTB would do to its sender's portfolio
+#[derive(Debug, Clone)]
+pub struct PortfolioSimResult {
+    /// Net change per coin type in base units, gas included for SUI
+    pub balance_changes: HashMap<String, i128>,
+    pub gas_cost_mist: u64,
+    pub objects_created: Vec<ObjectID>,
+    pub objects_deleted: Vec<ObjectID>,
+}
+
+impl SquadConnect {
+    /// Previews the balance changes of `ptb` for `sender` without executing it
+    ///
+    /// Rebalancing through a DEX means building the swap calls yourself:
+    /// with DeepBook, split the input coin and pass it to
+    /// `clob_v2::swap_exact_base_for_quote` or `swap_exact_quote_for_base`
+    /// on the pool, then transfer the outputs back to `sender`; aggregators
+    /// and AMMs such as Cetus take a coin and return the other side in the
+    /// same way through their router modules. Chain one swap per leg into a
+    /// single `ProgrammableTransactionBuilder` and pass `builder.finish()`.
+    ///
+    /// The PTB is dry-run with up to `SIMULATION_GAS_BUDGET` of `sender`'s
+    /// SUI as gas, since only a dry run reports balance changes. Coin types
+    /// are keyed in canonical form, as `utils::coins::coin_type_to_move_type`
+    /// writes them. A failing PTB gives `ServiceError::DryRunFailed`.
+    pub async fn simulate_portfolio_transaction(
+        &self,
+        ptb: ProgrammableTransaction,
+        sender: SuiAddress,
+    ) -> Result<PortfolioSimResult> {
+        let tx_data = self
+            .programmable_transaction_data(sender, ptb, SIMULATION_GAS_BUDGET)
+            .await?;
+
+        let dry_run = self
+            .get_node()
+            .read_api()
+            .dry_run_transaction_block(tx_data)
+            .await
+            .map_err(|e| ServiceError::DryRunFailed(e.to_string()))?;
+
+        if let SuiExecutionStatus::Failure { error } = dry_run.effects.status() {
+            return Err(ServiceError::DryRunFailed(error.clone()));
+        }
+
+        let mut balance_changes: HashMap<String, i128> = HashMap::new();
+
+        for change in &dry_run.balance_changes {
+            if change.owner == Owner::AddressOwner(sender) {
+                *balance_changes
+                    .entry(change.coin_type.to_canonical_string(true))
+                    .or_default() += change.amount;
+            }
+        }
+
+        Ok(PortfolioSimResult {
+            balance_changes,
+            gas_cost_mist: u64::try_from(dry_run.effects.gas_cost_summary().net_gas_usage())
+                .unwrap_or_default(),
+            objects_created: dry_run
+                .effects
+                .created()
+                .iter()
+                .map(|object| object.reference.object_id)
+                .collect(),
+            objects_deleted: dry_run
+                .effects
+                .deleted()
+                .iter()
+                .map(|object| object.object_id)
+                .collect(),
+        })
+    }
+}
+
+/// Balances after applying `changes` to `before`
+///
+/// Keys of both maps may be written in any form `coin_type_to_move_type`
+/// accepts; the result uses the canonical form. Balances are clamped to
+/// `0..=u64::MAX`, and coin types missing from `before` start at 0.
+pub fn compute_net_balance_change(
+    before: &HashMap<String, u64>,
+    changes: &HashMap<String, i128>,
+) -> HashMap<String, u64> {
+    let mut after: HashMap<String, i128> = HashMap::new();
+
+    for (coin_type, balance) in before {
+        *after.entry(coin_type_to_move_type(coin_type)).or_default() += i128::from(*balance);
+    }
+
+    for (coin_type, change) in changes {
+        *after.entry(coin_type_to_move_type(coin_type)).or_default() += change;
+    }
+
+    after
+        .into_iter()
+        .map(|(coin_type, balance)| (coin_type, u64::try_from(balance.max(0)).unwrap_or(u64::MAX)))
+        .collect()
+}
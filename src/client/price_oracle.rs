@@ -0,0 +1,237 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::Deserialize;
+use sui_sdk::{
+    rpc_types::SuiObjectDataOptions,
+    types::{
+        Identifier,
+        base_types::{ObjectID, ObjectType, SuiAddress},
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::TransactionKind,
+    },
+};
+
+use crate::service::types::{Result, ServiceError};
+
+use super::squad_connect::{SquadConnect, ptb_error};
+
+/// Pyth SUI/USD price feed ID on mainnet (stable channel)
+///
+/// Feed IDs are listed at https://pyth.network/developers/price-feed-ids.
+const SUI_USD_MAINNET_FEED_ID: &str =
+    "0x23d7315113f5b1d3ba7a83604c44b94d79f4fd69af77f804fc7f920a6dc65744";
+
+/// Pyth SUI/USD price feed ID on testnet (beta channel)
+const SUI_USD_TESTNET_FEED_ID: &str =
+    "0x50c67b3fd225db8912a424dd4baed60ffdde625ed2feaaf283724f9608fea266";
+
+const DEFAULT_MAX_AGE_SECS: u64 = 60;
+
+const MIST_PER_SUI: f64 = 1_000_000_000.0;
+
+/// Prices read from Pyth, keyed by price info object, with when each was read
+pub(crate) type PriceCache = HashMap<ObjectID, (f64, Instant)>;
+
+/// Pyth price feed to read and how old its price may be
+///
+/// Pyth identifies feeds by `pyth_feed_id` across chains, but on Sui each
+/// feed lives in a shared `PriceInfoObject` whose ID differs per network
+/// and is not derivable from the feed ID. Look it up in the Pyth Sui
+/// contract addresses documentation and set it with `with_price_info_object`.
+#[derive(Debug, Clone)]
+pub struct PriceConfig {
+    pub pyth_feed_id: String,
+    pub price_info_object_id: Option<ObjectID>,
+    /// Oldest on-chain price accepted, also how long a read price is cached
+    pub max_age_secs: u64,
+}
+
+impl PriceConfig {
+    pub fn sui_usd_mainnet() -> Self {
+        Self {
+            pyth_feed_id: SUI_USD_MAINNET_FEED_ID.to_string(),
+            price_info_object_id: None,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+        }
+    }
+
+    pub fn sui_usd_testnet() -> Self {
+        Self {
+            pyth_feed_id: SUI_USD_TESTNET_FEED_ID.to_string(),
+            price_info_object_id: None,
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+        }
+    }
+
+    pub fn with_price_info_object(mut self, price_info_object_id: ObjectID) -> Self {
+        self.price_info_object_id = Some(price_info_object_id);
+        self
+    }
+}
+
+/// Pyth's signed 64-bit integer
+#[derive(Debug, Deserialize)]
+struct PythI64 {
+    negative: bool,
+    magnitude: u64,
+}
+
+impl PythI64 {
+    fn to_f64(&self) -> f64 {
+        if self.negative {
+            -(self.magnitude as f64)
+        } else {
+            self.magnitude as f64
+        }
+    }
+}
+
+/// `pyth::price::Price`, as returned by `pyth::get_price_unsafe`
+#[derive(Debug, Deserialize)]
+struct PythPrice {
+    price: PythI64,
+    _conf: u64,
+    expo: PythI64,
+    /// Publish time in Unix seconds
+    timestamp: u64,
+}
+
+impl SquadConnect {
+    /// Current SUI price in USD from the Pyth oracle
+    ///
+    /// The price is read with a dev-inspect of `pyth::get_price_unsafe` run
+    /// as `sender`, so no gas is charged. Prices published more than
+    /// `max_age_secs` ago are rejected, and a read price is reused for that
+    /// long before the oracle is queried again.
+    pub async fn get_sui_price_usd(&self, config: &PriceConfig, sender: SuiAddress) -> Result<f64> {
+        let object_id = config.price_info_object_id.ok_or_else(|| {
+            ServiceError::Service(format!(
+                "No Pyth price info object configured for feed {}",
+                config.pyth_feed_id
+            ))
+        })?;
+        let max_age = Duration::from_secs(config.max_age_secs);
+
+        if let Some((price, read_at)) = self
+            .price_cache()
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&object_id).copied())
+            && read_at.elapsed() < max_age
+        {
+            return Ok(price);
+        }
+
+        let price = self.read_pyth_price(object_id, sender).await?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now.saturating_sub(price.timestamp) > config.max_age_secs {
+            return Err(ServiceError::Service(format!(
+                "Pyth price for feed {} is {} seconds old",
+                config.pyth_feed_id,
+                now.saturating_sub(price.timestamp)
+            )));
+        }
+
+        let price_usd = price.price.to_f64() * 10f64.powf(price.expo.to_f64());
+        if let Ok(mut cache) = self.price_cache().write() {
+            cache.insert(object_id, (price_usd, Instant::now()));
+        }
+
+        Ok(price_usd)
+    }
+
+    /// Dev-inspects `pyth::get_price_unsafe` on the price info object
+    ///
+    /// The Pyth package is taken from the object's type, so the same code
+    /// works on every network.
+    async fn read_pyth_price(&self, object_id: ObjectID, sender: SuiAddress) -> Result<PythPrice> {
+        let response = self
+            .get_node()
+            .read_api()
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_type())
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get object: {}", e)))?;
+
+        let object_type = response
+            .data
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", object_id))
+            })?
+            .object_type()
+            .map_err(ptb_error)?;
+
+        let ObjectType::Struct(object_type) = object_type else {
+            return Err(ServiceError::Service(format!(
+                "Object {} is not a Pyth price info object",
+                object_id
+            )));
+        };
+
+        let price_info = self.get_shared_object_arg(object_id, false).await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let price_info = builder.obj(price_info).map_err(ptb_error)?;
+
+        builder.programmable_move_call(
+            ObjectID::from(object_type.address()),
+            Identifier::new("pyth").map_err(ptb_error)?,
+            Identifier::new("get_price_unsafe").map_err(ptb_error)?,
+            vec![],
+            vec![price_info],
+        );
+
+        let results = self
+            .get_node()
+            .read_api()
+            .dev_inspect_transaction_block(
+                sender,
+                TransactionKind::ProgrammableTransaction(builder.finish()),
+                None,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to inspect transaction: {}", e)))?;
+
+        if let Some(error) = results.error {
+            return Err(ServiceError::Service(format!(
+                "Reading Pyth price failed: {}",
+                error
+            )));
+        }
+
+        let (bytes, _) = results
+            .results
+            .and_then(|mut results| results.pop())
+            .and_then(|mut result| result.return_values.pop())
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Pyth price read returned no value".to_string())
+            })?;
+
+        bcs::from_bytes(&bytes)
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid Pyth price: {}", e)))
+    }
+}
+
+/// Value of `mist` in USD at `price_usd` per SUI
+pub fn convert_mist_to_usd(mist: u64, price_usd: f64) -> f64 {
+    mist as f64 / MIST_PER_SUI * price_usd
+}
+
+/// Amount of MIST worth `usd` at `price_usd` per SUI, rounded down
+///
+/// Returns 0 for a non-positive price rather than dividing by it.
+pub fn convert_usd_to_mist(usd: f64, price_usd: f64) -> u64 {
+    if price_usd <= 0.0 {
+        return 0;
+    }
+
+    (usd / price_usd * MIST_PER_SUI) as u64
+}
@@ -0,0 +1,314 @@
+use std::path::PathBuf;
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use move_binary_format::file_format::{Ability, AbilitySet, Bytecode, SignatureToken, Visibility};
+use sui_sdk::types::{
+    Identifier, MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_ADDRESS, SUI_FRAMEWORK_PACKAGE_ID,
+    SUI_RANDOMNESS_STATE_OBJECT_ID,
+    base_types::{ObjectID, SuiAddress},
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::{
+    move_package::{ModuleBuilder, published_package_id},
+    squad_connect::{SquadConnect, account_address, ptb_error},
+};
+
+/// Module of the randomness helper package, also its package name
+const RANDOM_MODULE: &str = "squad_random";
+
+const RANDOM_EVENT: &str = "RandomBytes";
+
+/// Serialized bytecode of the randomness helper package, equivalent to:
+///
+/// ```move
+/// module squad_random::squad_random;
+///
+/// use sui::event;
+/// use sui::random::Random;
+///
+/// public struct RandomBytes has copy, drop {
+///     bytes: vector<u8>,
+/// }
+///
+/// entry fun generate_bytes(random: &Random, length: u16, ctx: &mut TxContext) {
+///     let mut generator = random.new_generator(ctx);
+///     event::emit(RandomBytes { bytes: generator.generate_bytes(length) });
+/// }
+/// ```
+///
+/// Sui only allows `TransferObjects` and `MergeCoins` after a command that
+/// reads `Random`, so the bytes are emitted as an event by the same call
+/// that generates them rather than returned to a later command.
+fn random_module_bytes() -> Result<Vec<u8>> {
+    let mut builder = ModuleBuilder::new(RANDOM_MODULE)?;
+    let copy_drop = AbilitySet::EMPTY | Ability::Copy | Ability::Drop;
+
+    let event_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "event")?;
+    let random_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "random")?;
+    let tx_context_module = builder.module_handle(SUI_FRAMEWORK_ADDRESS, "tx_context")?;
+
+    let random_type = builder.datatype(
+        random_module,
+        "Random",
+        AbilitySet::EMPTY | Ability::Key,
+        vec![],
+    )?;
+    let generator_type = builder.datatype(
+        random_module,
+        "RandomGenerator",
+        AbilitySet::EMPTY | Ability::Drop,
+        vec![],
+    )?;
+    let tx_context_type = builder.datatype(
+        tx_context_module,
+        "TxContext",
+        AbilitySet::EMPTY | Ability::Drop,
+        vec![],
+    )?;
+    let self_module = builder.self_module();
+    let random_bytes_type = builder.datatype(self_module, RANDOM_EVENT, copy_drop, vec![])?;
+
+    let bytes = SignatureToken::Vector(Box::new(SignatureToken::U8));
+    let random_ref = SignatureToken::Reference(Box::new(SignatureToken::Datatype(random_type)));
+    let generator = SignatureToken::Datatype(generator_type);
+    let ctx_mut =
+        SignatureToken::MutableReference(Box::new(SignatureToken::Datatype(tx_context_type)));
+
+    let new_generator = builder.function(
+        random_module,
+        "new_generator",
+        vec![random_ref.clone(), ctx_mut.clone()],
+        vec![generator.clone()],
+        vec![],
+    )?;
+    let generate_bytes = builder.function(
+        random_module,
+        "generate_bytes",
+        vec![
+            SignatureToken::MutableReference(Box::new(generator.clone())),
+            SignatureToken::U16,
+        ],
+        vec![bytes.clone()],
+        vec![],
+    )?;
+    let event_emit = builder.function(
+        event_module,
+        "emit",
+        vec![SignatureToken::TypeParameter(0)],
+        vec![],
+        vec![copy_drop],
+    )?;
+    let emit_random_bytes = builder.instantiate(
+        event_emit,
+        vec![SignatureToken::Datatype(random_bytes_type)],
+    );
+
+    let random_bytes_def = builder.struct_def(random_bytes_type, vec![("bytes", bytes)])?;
+
+    let entry = builder.function(
+        self_module,
+        "generate_bytes",
+        vec![random_ref, SignatureToken::U16, ctx_mut],
+        vec![],
+        vec![],
+    )?;
+    let locals = builder.signature(vec![generator]);
+    builder.function_def(
+        entry,
+        Visibility::Private,
+        true,
+        locals,
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::MoveLoc(2),
+            Bytecode::Call(new_generator),
+            Bytecode::StLoc(3),
+            Bytecode::MutBorrowLoc(3),
+            Bytecode::MoveLoc(1),
+            Bytecode::Call(generate_bytes),
+            Bytecode::Pack(random_bytes_def),
+            Bytecode::CallGeneric(emit_random_bytes),
+            Bytecode::Ret,
+        ],
+    );
+
+    builder.into_bytes()
+}
+
+/// Location of the `0x2::random::Random` object and the helper package
+#[derive(Debug, Clone)]
+pub struct RandomnessConfig {
+    pub object_id: ObjectID,
+    /// Package emitting the generated bytes, published on first use if `None`
+    pub package_id: Option<ObjectID>,
+}
+
+impl Default for RandomnessConfig {
+    /// The system `Random` object, `0x8` on Mainnet, Testnet and Devnet
+    fn default() -> Self {
+        Self {
+            object_id: SUI_RANDOMNESS_STATE_OBJECT_ID,
+            package_id: None,
+        }
+    }
+}
+
+impl SquadConnect {
+    /// Draws `count` random bytes from Sui's randomness beacon
+    ///
+    /// The bytes are generated by a committed transaction from `account`,
+    /// which pays the gas, and read back from the `RandomBytes` event it
+    /// emits, so anyone can verify them from the transaction. The helper
+    /// package emitting the event is published by `account` on first use,
+    /// and its ID is stored in `config.package_id` for later calls.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_random_bytes(
+        &self,
+        config: &mut RandomnessConfig,
+        count: usize,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<Vec<u8>> {
+        let count = u16::try_from(count).map_err(|_| {
+            ServiceError::Service(format!("Cannot generate {} random bytes at once", count))
+        })?;
+        let sender = account_address(&account)?;
+
+        let package_id = match config.package_id {
+            Some(package_id) => package_id,
+            None => {
+                let package_id = self
+                    .publish_random_package(
+                        sender,
+                        gas_budget,
+                        zk_inputs.clone(),
+                        max_epoch,
+                        path.clone(),
+                    )
+                    .await?;
+                config.package_id = Some(package_id);
+                package_id
+            }
+        };
+
+        let random = self.get_shared_object_arg(config.object_id, false).await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let random = builder.obj(random).map_err(ptb_error)?;
+                let length = builder.pure(count).map_err(ptb_error)?;
+
+                // Must stay the last command, see `random_module_bytes`
+                builder.programmable_move_call(
+                    package_id,
+                    Identifier::new(RANDOM_MODULE).map_err(ptb_error)?,
+                    Identifier::new("generate_bytes").map_err(ptb_error)?,
+                    vec![],
+                    vec![random, length],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        let event = response
+            .events
+            .as_ref()
+            .and_then(|events| {
+                events.data.iter().find(|event| {
+                    ObjectID::from(event.type_.address) == package_id
+                        && event.type_.module.as_str() == RANDOM_MODULE
+                        && event.type_.name.as_str() == RANDOM_EVENT
+                })
+            })
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!(
+                    "No {} event in {}",
+                    RANDOM_EVENT, response.digest
+                ))
+            })?;
+
+        serde_json::from_value(event.parsed_json["bytes"].clone())
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid random bytes: {}", e)))
+    }
+
+    /// A random `u64` from `get_random_bytes`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_onchain_random_u64(
+        &self,
+        config: &mut RandomnessConfig,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<u64> {
+        let bytes = self
+            .get_random_bytes(config, 8, gas_budget, account, zk_inputs, max_epoch, path)
+            .await?;
+
+        bcs::from_bytes(&bytes)
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid random value: {}", e)))
+    }
+
+    /// A random `u128` from `get_random_bytes`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_onchain_random_u128(
+        &self,
+        config: &mut RandomnessConfig,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<u128> {
+        let bytes = self
+            .get_random_bytes(config, 16, gas_budget, account, zk_inputs, max_epoch, path)
+            .await?;
+
+        bcs::from_bytes(&bytes)
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid random value: {}", e)))
+    }
+
+    /// Publishes the randomness helper package
+    async fn publish_random_package(
+        &self,
+        sender: SuiAddress,
+        gas_budget: u64,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<ObjectID> {
+        let bytes = random_module_bytes()?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let upgrade_cap = builder.publish_upgradeable(
+                    vec![bytes],
+                    vec![MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID],
+                );
+                builder.transfer_arg(sender, upgrade_cap);
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        published_package_id(&response)
+    }
+}
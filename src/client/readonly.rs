@@ -0,0 +1,101 @@
+use std::{ops::Deref, path::PathBuf};
+
+use serde::Serialize;
+use sui_sdk::types::{base_types::SuiAddress, transaction::Transaction};
+
+use crate::service::types::Result;
+
+use super::squad_connect::SquadConnect;
+
+/// Borrowed, read-only view over a [`SquadConnect`]
+///
+/// Only the `&self` methods of `SquadConnect` are reachable through this
+/// handle, so it can be passed to code that must not touch session state.
+#[derive(Clone, Copy)]
+pub struct SquadConnectRef<'a> {
+    inner: &'a SquadConnect,
+}
+
+impl<'a> SquadConnectRef<'a> {
+    pub(crate) fn new(inner: &'a SquadConnect) -> Self {
+        Self { inner }
+    }
+}
+
+impl Deref for SquadConnectRef<'_> {
+    type Target = SquadConnect;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner
+    }
+}
+
+/// Owned, read-only copy of a [`SquadConnect`]
+///
+/// Reads are forwarded to the cloned instance. The mutating methods are kept
+/// so existing call sites still compile, but they panic when called.
+#[derive(Clone)]
+pub struct ReadOnlySquadConnect {
+    inner: SquadConnect,
+}
+
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ReadOnlySquadConnect>();
+};
+
+impl ReadOnlySquadConnect {
+    pub(crate) fn new(inner: SquadConnect) -> Self {
+        Self { inner }
+    }
+
+    pub fn set_jwt(&mut self, _jwt: String) {
+        read_only_violation("set_jwt")
+    }
+
+    pub fn set_zk_proof_params(
+        &mut self,
+        _randomness: String,
+        _public_key: String,
+        _max_epoch: u64,
+    ) {
+        read_only_violation("set_zk_proof_params")
+    }
+
+    pub async fn create_zkp_payload(&mut self, _path: PathBuf) -> Result<()> {
+        read_only_violation("create_zkp_payload")
+    }
+
+    pub async fn get_url<T: Send + Serialize>(
+        &mut self,
+        _redirect_url: String,
+        _state: Option<T>,
+    ) -> Result<String> {
+        read_only_violation("get_url")
+    }
+
+    pub async fn sponsor_transaction(
+        &mut self,
+        _tx: Transaction,
+        _sender: SuiAddress,
+        _allowed_addresses: Vec<String>,
+        _allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        read_only_violation("sponsor_transaction")
+    }
+}
+
+impl Deref for ReadOnlySquadConnect {
+    type Target = SquadConnect;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+fn read_only_violation(method: &str) -> ! {
+    panic!(
+        "SquadConnect::{} called on a ReadOnlySquadConnect; use the original SquadConnect to mutate session state",
+        method
+    )
+}
@@ -0,0 +1,223 @@
+use std::path::PathBuf;
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use sui_sdk::{
+    rpc_types::SuiTransactionBlockResponse,
+    types::{
+        Identifier, SUI_FRAMEWORK_ADDRESS,
+        base_types::{ObjectID, ObjectRef, SuiAddress},
+        transaction::ObjectArg,
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::{
+    move_package::{compile_package, created_object_id, move_byte_string, published_package_id},
+    squad_connect::{SquadConnect, account_address, ptb_error},
+};
+
+/// Move source of the currency package, filled in by `currency_package_source`
+///
+/// `init` runs once at publish time with the one-time witness, creates the
+/// regulated coin and a closed-loop `TokenPolicy` for it, and hands every
+/// capability to the publisher.
+const CURRENCY_TEMPLATE: &str = r#"module {module}::{module};
+
+use sui::coin;
+use sui::token;
+use sui::url;
+
+public struct {witness} has drop {}
+
+fun init(witness: {witness}, ctx: &mut TxContext) {
+    let (treasury_cap, deny_cap, metadata) = coin::create_regulated_currency_v2(
+        witness,
+        {decimals},
+        b"{symbol}",
+        b"{name}",
+        b"{description}",
+        {icon_url},
+        true,
+        ctx,
+    );
+    let (policy, policy_cap) = token::new_policy(&treasury_cap, ctx);
+
+    token::share_policy(policy);
+    transfer::public_freeze_object(metadata);
+    transfer::public_transfer(treasury_cap, ctx.sender());
+    transfer::public_transfer(deny_cap, ctx.sender());
+    transfer::public_transfer(policy_cap, ctx.sender());
+}
+"#;
+
+/// Objects created by `create_regulated_currency`
+#[derive(Debug, Clone)]
+pub struct RegulatedCurrencyResult {
+    pub treasury_cap_id: ObjectID,
+    /// Shared closed-loop `TokenPolicy` of the currency
+    pub policy_id: ObjectID,
+    pub deny_cap_id: ObjectID,
+    pub package_id: ObjectID,
+    pub digest: String,
+}
+
+impl SquadConnect {
+    /// Publishes a new regulated currency with a closed-loop token policy
+    ///
+    /// Regulated coins can only be created with the one-time witness of a
+    /// freshly published module, so a package is generated from a template
+    /// and compiled with the `sui` CLI. Unlike the memo and randomness
+    /// helpers the module differs per call, so it cannot ship precompiled:
+    /// the `sui` binary must be on `PATH` wherever this runs, or the call
+    /// fails before anything is submitted. The module is named after the
+    /// lowercased `symbol`. The publisher receives the `TreasuryCap`, the
+    /// `DenyCapV2`, the `TokenPolicyCap` and the package's `UpgradeCap`; the
+    /// `TokenPolicy` is shared and the metadata frozen.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_regulated_currency(
+        &mut self,
+        name: &str,
+        symbol: &str,
+        decimals: u8,
+        description: &str,
+        icon_url: Option<&str>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<RegulatedCurrencyResult> {
+        let sender = account_address(&account)?;
+        let module = currency_module_name(symbol)?;
+        let source =
+            currency_package_source(&module, name, symbol, decimals, description, icon_url);
+        let package = compile_package(&module, &source).await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let upgrade_cap =
+                    builder.publish_upgradeable(package.modules, package.dependencies);
+                builder.transfer_arg(sender, upgrade_cap);
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        currency_result(&response)
+    }
+
+    /// Mints `amount` of the currency controlled by `treasury_cap` to `recipient`
+    ///
+    /// The coin type is read from the treasury cap. Returns the transaction
+    /// digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn mint_regulated_coin(
+        &mut self,
+        treasury_cap: ObjectRef,
+        amount: u64,
+        recipient: SuiAddress,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let coin_type = self
+            .get_object_type_tag(treasury_cap.0)
+            .await?
+            .and_then(|tag| tag.type_params.into_iter().next())
+            .ok_or_else(|| {
+                ServiceError::Service(format!("Object {} is not a TreasuryCap", treasury_cap.0))
+            })?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let treasury_cap = builder
+                    .obj(ObjectArg::ImmOrOwnedObject(treasury_cap))
+                    .map_err(ptb_error)?;
+                let amount = builder.pure(amount).map_err(ptb_error)?;
+                let recipient = builder.pure(recipient).map_err(ptb_error)?;
+
+                builder.programmable_move_call(
+                    SUI_FRAMEWORK_ADDRESS.into(),
+                    Identifier::new("coin").map_err(ptb_error)?,
+                    Identifier::new("mint_and_transfer").map_err(ptb_error)?,
+                    vec![coin_type],
+                    vec![treasury_cap, amount, recipient],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+}
+
+/// Module name for `symbol`, which also names the one-time witness
+fn currency_module_name(symbol: &str) -> Result<String> {
+    let valid = symbol
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic())
+        && symbol.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if !valid {
+        return Err(ServiceError::Service(format!(
+            "Currency symbol {:?} must be ASCII letters and digits, starting with a letter",
+            symbol
+        )));
+    }
+
+    Ok(symbol.to_ascii_lowercase())
+}
+
+fn currency_package_source(
+    module: &str,
+    name: &str,
+    symbol: &str,
+    decimals: u8,
+    description: &str,
+    icon_url: Option<&str>,
+) -> String {
+    let icon_url = match icon_url {
+        Some(icon_url) => format!(
+            "option::some(url::new_unsafe_from_bytes(b\"{}\"))",
+            move_byte_string(icon_url)
+        ),
+        None => "option::none()".to_string(),
+    };
+
+    CURRENCY_TEMPLATE
+        .replace("{module}", module)
+        .replace("{witness}", &module.to_ascii_uppercase())
+        .replace("{decimals}", &decimals.to_string())
+        .replace("{symbol}", &move_byte_string(symbol))
+        .replace("{name}", &move_byte_string(name))
+        .replace("{description}", &move_byte_string(description))
+        .replace("{icon_url}", &icon_url)
+}
+
+/// Picks the published package and created capabilities out of the object changes
+fn currency_result(response: &SuiTransactionBlockResponse) -> Result<RegulatedCurrencyResult> {
+    Ok(RegulatedCurrencyResult {
+        treasury_cap_id: created_object_id(response, SUI_FRAMEWORK_ADDRESS, "coin", "TreasuryCap")?,
+        policy_id: created_object_id(response, SUI_FRAMEWORK_ADDRESS, "token", "TokenPolicy")?,
+        deny_cap_id: created_object_id(response, SUI_FRAMEWORK_ADDRESS, "coin", "DenyCapV2")?,
+        package_id: published_package_id(response)?,
+        digest: response.digest.to_string(),
+    })
+}
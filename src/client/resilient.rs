@@ -0,0 +1,337 @@
+use std::{
+    future::Future,
+    ops::Deref,
+    sync::{
+        Arc, Once, Weak,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use sui_sdk::{SuiClient, SuiClientBuilder, error::SuiRpcResult};
+use tokio::{sync::OnceCell, task::JoinHandle};
+
+use crate::{
+    service::types::{Result, ServiceError},
+    utils::epoch::{epoch_to_estimated_timestamp, timestamp_to_estimated_epoch},
+};
+
+/// How often endpoints are probed by default
+pub const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a request or health probe may take before the endpoint counts as down
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fullnode endpoints in order of preference
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    pub primary: String,
+    pub fallbacks: Vec<String>,
+    pub health_check_interval: Duration,
+    /// Start of epoch 0 in Unix milliseconds, for the estimates in `utils::epoch`
+    pub genesis_timestamp_ms: Option<u64>,
+}
+
+impl NetworkConfig {
+    pub fn new(primary: String) -> Self {
+        Self {
+            primary,
+            fallbacks: Vec::new(),
+            health_check_interval: DEFAULT_HEALTH_CHECK_INTERVAL,
+            genesis_timestamp_ms: None,
+        }
+    }
+
+    /// Endpoints to fall over to, tried in order, when the primary is down
+    pub fn with_fallback_rpc(mut self, fallbacks: Vec<String>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    pub fn with_health_check_interval(mut self, health_check_interval: Duration) -> Self {
+        self.health_check_interval = health_check_interval;
+        self
+    }
+
+    /// Timestamp of the genesis checkpoint, e.g. from `get_checkpoint`
+    pub fn with_genesis_timestamp_ms(mut self, genesis_timestamp_ms: u64) -> Self {
+        self.genesis_timestamp_ms = Some(genesis_timestamp_ms);
+        self
+    }
+
+    /// Estimated start of `epoch` on this network, see `epoch_to_estimated_timestamp`
+    ///
+    /// `None` until a genesis timestamp is set.
+    pub fn estimated_epoch_start_ms(&self, epoch: u64, epoch_duration_ms: u64) -> Option<u64> {
+        self.genesis_timestamp_ms
+            .map(|genesis| epoch_to_estimated_timestamp(epoch, genesis, epoch_duration_ms))
+    }
+
+    /// Estimated epoch of this network in progress at `timestamp_ms`, see
+    /// `timestamp_to_estimated_epoch`
+    ///
+    /// `None` until a genesis timestamp is set.
+    pub fn estimated_epoch_at(&self, timestamp_ms: u64, epoch_duration_ms: u64) -> Option<u64> {
+        self.genesis_timestamp_ms
+            .map(|genesis| timestamp_to_estimated_epoch(timestamp_ms, genesis, epoch_duration_ms))
+    }
+}
+
+struct Endpoint {
+    /// `None` for a primary handed over already built
+    url: Option<String>,
+    /// Connected on first use, so fallbacks cost nothing until needed
+    client: OnceCell<SuiClient>,
+}
+
+struct Inner {
+    endpoints: Vec<Endpoint>,
+    /// Index of the endpoint requests currently go to; always connected
+    active: AtomicUsize,
+    timeout: Duration,
+    /// Interval of the health check started by the first `call`, if any
+    health_check_interval: Option<Duration>,
+    health_check_started: Once,
+}
+
+/// `SuiClient` that fails over between fullnodes
+///
+/// Dereferences to the client of the active endpoint, so it can be used
+/// wherever a `SuiClient` is. Only requests made through `call` fail over
+/// on errors and timeouts; plain calls through `Deref` go to the active
+/// endpoint and are not retried. `SquadConnect` sends its reads through
+/// `call`; transaction building, dev-inspection and execution, and the
+/// optional feature modules, use `get_node` and stay on the active
+/// endpoint. Use `spawn_health_check` (or `with_fallbacks_and_health_check`)
+/// to move back to the primary once it recovers.
+#[derive(Clone)]
+pub struct ResilientSuiClient {
+    inner: Arc<Inner>,
+}
+
+impl ResilientSuiClient {
+    /// Wraps a single client, with nothing to fall over to
+    pub fn new(primary: SuiClient) -> Self {
+        Self::with_fallbacks(primary, Vec::new())
+    }
+
+    /// Wraps `primary`, falling over to `fallbacks` in order
+    pub fn with_fallbacks(primary: SuiClient, fallbacks: Vec<String>) -> Self {
+        Self::from_primary(primary, fallbacks, None)
+    }
+
+    /// Wraps `primary`, falling over to `fallbacks` and running `check_health` every `interval`
+    ///
+    /// The health check is spawned by the first `call`, so the client can be
+    /// built outside a Tokio runtime.
+    pub fn with_fallbacks_and_health_check(
+        primary: SuiClient,
+        fallbacks: Vec<String>,
+        interval: Duration,
+    ) -> Self {
+        Self::from_primary(primary, fallbacks, Some(interval))
+    }
+
+    fn from_primary(
+        primary: SuiClient,
+        fallbacks: Vec<String>,
+        health_check_interval: Option<Duration>,
+    ) -> Self {
+        let endpoints = std::iter::once(Endpoint {
+            url: None,
+            client: OnceCell::new_with(Some(primary)),
+        })
+        .chain(fallbacks.into_iter().map(|url| Endpoint {
+            url: Some(url),
+            client: OnceCell::new(),
+        }))
+        .collect();
+
+        Self {
+            inner: Arc::new(Inner {
+                endpoints,
+                active: AtomicUsize::new(0),
+                timeout: DEFAULT_RPC_TIMEOUT,
+                health_check_interval,
+                health_check_started: Once::new(),
+            }),
+        }
+    }
+
+    /// Connects to the endpoints of `config`
+    ///
+    /// Starts on the first endpoint that can be reached and fails with
+    /// `ServiceError::Network` if none can. The first `call` starts a health
+    /// check every `config.health_check_interval`.
+    pub async fn connect(config: &NetworkConfig) -> Result<Self> {
+        let endpoints: Vec<Endpoint> = std::iter::once(&config.primary)
+            .chain(&config.fallbacks)
+            .map(|url| Endpoint {
+                url: Some(url.clone()),
+                client: OnceCell::new(),
+            })
+            .collect();
+
+        let client = Self {
+            inner: Arc::new(Inner {
+                endpoints,
+                active: AtomicUsize::new(0),
+                timeout: DEFAULT_RPC_TIMEOUT,
+                health_check_interval: Some(config.health_check_interval),
+                health_check_started: Once::new(),
+            }),
+        };
+
+        for index in 0..client.inner.endpoints.len() {
+            if client.endpoint_client(index).await.is_some() {
+                client.inner.active.store(index, Ordering::SeqCst);
+                return Ok(client);
+            }
+        }
+
+        Err(ServiceError::Network(format!(
+            "None of the {} RPC endpoints could be reached",
+            client.inner.endpoints.len()
+        )))
+    }
+
+    /// Index of the active endpoint; 0 is the primary
+    pub fn active_index(&self) -> usize {
+        self.inner.active.load(Ordering::SeqCst)
+    }
+
+    /// Runs `f` against the active endpoint, falling over on errors and timeouts
+    ///
+    /// Each endpoint is tried at most once, starting with the active one. The
+    /// first endpoint that answers becomes the active one.
+    pub async fn call<T, F, Fut>(&self, f: F) -> Result<T>
+    where
+        F: Fn(SuiClient) -> Fut,
+        Fut: Future<Output = SuiRpcResult<T>>,
+    {
+        if let Some(interval) = self.inner.health_check_interval {
+            self.inner.health_check_started.call_once(|| {
+                self.spawn_health_check(interval);
+            });
+        }
+
+        let count = self.inner.endpoints.len();
+        let start = self.active_index();
+        let mut last_error = String::new();
+
+        for offset in 0..count {
+            let index = (start + offset) % count;
+            let Some(client) = self.endpoint_client(index).await else {
+                last_error = format!("endpoint {} is unreachable", index);
+                continue;
+            };
+
+            match tokio::time::timeout(self.inner.timeout, f(client.clone())).await {
+                Ok(Ok(value)) => {
+                    self.inner.active.store(index, Ordering::SeqCst);
+                    return Ok(value);
+                }
+                Ok(Err(e)) => last_error = e.to_string(),
+                Err(_) => last_error = format!("endpoint {} timed out", index),
+            }
+
+            log::warn!("RPC endpoint {} failed: {}", index, last_error);
+        }
+
+        Err(ServiceError::Network(format!(
+            "All RPC endpoints failed, last error: {}",
+            last_error
+        )))
+    }
+
+    /// Probes the endpoints in order of preference and activates the first healthy one
+    ///
+    /// This is what moves requests back to the primary after it recovers.
+    pub async fn check_health(&self) {
+        for index in 0..self.inner.endpoints.len() {
+            if self.is_healthy(index).await {
+                self.inner.active.store(index, Ordering::SeqCst);
+                return;
+            }
+        }
+
+        log::error!("No RPC endpoint is healthy");
+    }
+
+    /// Runs `check_health` every `interval` in the background
+    ///
+    /// The task stops once every clone of this client has been dropped.
+    pub fn spawn_health_check(&self, interval: Duration) -> JoinHandle<()> {
+        let inner: Weak<Inner> = Arc::downgrade(&self.inner);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+
+                ResilientSuiClient { inner }.check_health().await;
+            }
+        })
+    }
+
+    async fn is_healthy(&self, index: usize) -> bool {
+        let Some(client) = self.endpoint_client(index).await else {
+            return false;
+        };
+
+        matches!(
+            tokio::time::timeout(
+                self.inner.timeout,
+                client.read_api().get_latest_checkpoint_sequence_number(),
+            )
+            .await,
+            Ok(Ok(_))
+        )
+    }
+
+    /// Client of endpoint `index`, connecting it first if needed
+    async fn endpoint_client(&self, index: usize) -> Option<&SuiClient> {
+        let endpoint = &self.inner.endpoints[index];
+
+        if let Some(client) = endpoint.client.get() {
+            return Some(client);
+        }
+
+        let url = endpoint.url.as_deref()?;
+
+        endpoint
+            .client
+            .get_or_try_init(|| async {
+                tokio::time::timeout(self.inner.timeout, SuiClientBuilder::default().build(url))
+                    .await
+                    .map_err(|_| format!("Connecting to {} timed out", url))?
+                    .map_err(|e| format!("Failed to connect to {}: {}", url, e))
+            })
+            .await
+            .inspect_err(|e| log::warn!("{}", e))
+            .ok()
+    }
+}
+
+impl From<SuiClient> for ResilientSuiClient {
+    fn from(primary: SuiClient) -> Self {
+        Self::new(primary)
+    }
+}
+
+impl Deref for ResilientSuiClient {
+    type Target = SuiClient;
+
+    fn deref(&self) -> &SuiClient {
+        self.inner.endpoints[self.active_index()]
+            .client
+            .get()
+            .expect("active RPC endpoint is connected")
+    }
+}
@@ -0,0 +1,134 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::service::types::{Result, ServiceError};
+
+use super::{dtos::ZkLoginSession, squad_connect::SquadConnect};
+
+type HmacSha256 = Hmac<Sha256>;
+
+impl ZkLoginSession {
+    /// `Set-Cookie` header value carrying this session as cookie `cookie_name`
+    ///
+    /// The value is the base64url-encoded JSON session followed by its
+    /// HMAC-SHA256 under `hmac_secret`. It is signed, not encrypted: the
+    /// JWT stays readable to anyone holding the cookie, so keep `secure`
+    /// and `http_only` on outside local development. The cookie is scoped
+    /// to `/` with `SameSite=Lax`.
+    pub fn export_to_cookie(
+        &self,
+        cookie_name: &str,
+        hmac_secret: &[u8],
+        secure: bool,
+        http_only: bool,
+        max_age_secs: u64,
+    ) -> Result<String> {
+        let json = serde_json::to_vec(self)
+            .map_err(|e| ServiceError::Service(format!("Failed to serialize session: {}", e)))?;
+        let payload = URL_SAFE_NO_PAD.encode(json);
+        let signature =
+            URL_SAFE_NO_PAD.encode(session_mac(hmac_secret, &payload)?.finalize().into_bytes());
+
+        let mut cookie = format!(
+            "{}={}.{}; Max-Age={}; Path=/; SameSite=Lax",
+            cookie_name, payload, signature, max_age_secs
+        );
+
+        if secure {
+            cookie.push_str("; Secure");
+        }
+
+        if http_only {
+            cookie.push_str("; HttpOnly");
+        }
+
+        Ok(cookie)
+    }
+
+    /// Reads the session stored by `export_to_cookie` from a `Cookie` request header
+    ///
+    /// Fails with `ServiceError::JwtExtraction` if `cookie_name` is missing
+    /// or not a session cookie, and with `ServiceError::InvalidProof` if
+    /// the signature does not match `hmac_secret`.
+    pub fn import_from_cookie(
+        cookie_header: &str,
+        cookie_name: &str,
+        hmac_secret: &[u8],
+    ) -> Result<ZkLoginSession> {
+        let value = cookie_header
+            .split(';')
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == cookie_name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                ServiceError::JwtExtraction(format!("Cookie {} not found", cookie_name))
+            })?;
+
+        let (payload, signature) = value.split_once('.').ok_or_else(|| {
+            ServiceError::JwtExtraction(format!("Cookie {} is not a session", cookie_name))
+        })?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|e| {
+            ServiceError::JwtExtraction(format!("Invalid session signature: {}", e))
+        })?;
+
+        session_mac(hmac_secret, payload)?
+            .verify_slice(&signature)
+            .map_err(|_| {
+                ServiceError::InvalidProof("Session cookie signature mismatch".to_string())
+            })?;
+
+        let json = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|e| ServiceError::JwtExtraction(format!("Invalid session cookie: {}", e)))?;
+
+        serde_json::from_slice(&json)
+            .map_err(|e| ServiceError::JwtExtraction(format!("Invalid session cookie: {}", e)))
+    }
+}
+
+impl SquadConnect {
+    /// `ZkLoginSession::export_to_cookie` for the current session
+    pub fn export_zk_session_to_cookie(
+        &self,
+        cookie_name: &str,
+        hmac_secret: &[u8],
+        secure: bool,
+        http_only: bool,
+        max_age_secs: u64,
+    ) -> Result<String> {
+        self.zk_login_session().export_to_cookie(
+            cookie_name,
+            hmac_secret,
+            secure,
+            http_only,
+            max_age_secs,
+        )
+    }
+
+    /// Resumes the session stored in cookie `name` of `cookie_header`
+    ///
+    /// See `ZkLoginSession::import_from_cookie` for the errors. The
+    /// ephemeral keystore must already be at the path later calls use.
+    pub fn restore_from_cookie(
+        &mut self,
+        cookie_header: &str,
+        name: &str,
+        secret: &[u8],
+    ) -> Result<()> {
+        let session = ZkLoginSession::import_from_cookie(cookie_header, name, secret)?;
+
+        self.set_zk_proof_params(session.randomness, session.public_key, session.max_epoch);
+        self.set_jwt(session.jwt);
+
+        Ok(())
+    }
+}
+
+fn session_mac(hmac_secret: &[u8], payload: &str) -> Result<HmacSha256> {
+    let mut mac = HmacSha256::new_from_slice(hmac_secret)
+        .map_err(|e| ServiceError::Service(format!("Invalid session secret: {}", e)))?;
+    mac.update(payload.as_bytes());
+
+    Ok(mac)
+}
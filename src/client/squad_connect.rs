@@ -1,51 +1,447 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use crate::service::{
-    dtos::AccountResponse,
-    services::Services,
+    dtos::{AccountResponse, SponsorCostEstimate, SponsorTransactionResponse},
+    services::{self, Services, extract_jwt_from_callback_url, extract_state_from_callback_url},
     types::{GoogleOauthProvider, Result, ServiceError},
+    zkp::compute_address_with_salt,
 };
+use fastcrypto::encoding::{Base64, Encoding};
 use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use futures::future::join_all;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode, decode_header, jwk::Jwk};
+use move_core_types::language_storage::StructTag;
 use serde::{Deserialize, Serialize};
 use shared_crypto::intent::Intent;
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::{
-    SuiClient,
+    SuiClient, SuiClientBuilder,
+    json::SuiJsonValue,
+    rpc_types::{
+        Coin, DelegatedStake, DevInspectArgs, EventFilter, ProtocolConfigResponse, StakeStatus,
+        SuiExecutionStatus, SuiMoveNormalizedModule, SuiObjectData, SuiObjectDataFilter,
+        SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery, SuiParsedData,
+        SuiPastObjectResponse, SuiRawData, SuiTransactionBlockDataAPI,
+        SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+        SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, SuiTypeTag,
+        TransactionFilter,
+    },
     types::{
-        base_types::SuiAddress,
+        Identifier, SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_FRAMEWORK_ADDRESS,
+        SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_ID, TypeTag,
+        base_types::{ObjectID, ObjectRef, ObjectType, SequenceNumber, SuiAddress},
+        crypto::{EncodeDecodeBase64, PublicKey},
+        digests::TransactionDigest,
+        dynamic_field::{DynamicFieldName, Field},
+        error::SuiObjectResponseError,
+        gas_coin::GAS,
+        governance::StakedSui,
+        multisig::{MultiSig, MultiSigPublicKey},
+        object::Owner,
+        parse_sui_struct_tag, parse_sui_type_tag,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        quorum_driver_types::ExecuteTransactionRequestType,
         signature::GenericSignature,
-        transaction::{Transaction, TransactionData},
+        sui_serde::BigInt,
+        sui_system_state::{
+            SuiSystemState, SuiSystemStateWrapper,
+            sui_system_state_inner_v1::SuiSystemStateInnerV1,
+            sui_system_state_inner_v2::SuiSystemStateInnerV2,
+            sui_system_state_summary::SuiSystemStateSummary,
+        },
+        transaction::{
+            Argument, CallArg, Command, InputObjectKind, ObjectArg, ProgrammableTransaction,
+            Transaction, TransactionData, TransactionDataAPI, TransactionKind,
+        },
         zk_login_authenticator::ZkLoginAuthenticator,
     },
 };
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    service::dtos::{
+        MAINNET_CHAIN_IDENTIFIER, Network, NonceParams, OauthProvider, TESTNET_CHAIN_IDENTIFIER,
+    },
+    utils::{
+        abi::{event_types, find_function, move_call_info, validate_call_args},
+        address::zk_login_address_matches,
+        coins::{
+            CoinSelectionResult, CoinSelectionStrategy, aggregate_coins, coin_type_to_move_type,
+            select_coins, select_gas_coin_strategy,
+        },
+        epoch::{needs_key_rotation, next_epoch_start_ms, proof_validity},
+        explorer,
+        protocol::{max_programmable_tx_commands, max_tx_size_bytes},
+        staking::{delegation_portfolio, estimate_validator_apy, pool_exchange_rate},
+    },
+};
+
+use super::{
+    builder::SquadConnectBuilder,
+    dtos::{
+        AccountState, AddressActivity, AuthResult, Checkpoint, CheckpointId, CheckpointTransaction,
+        CoinMetadata, DelegationPortfolio, EpochInfo, KeyRotationEvent, MoveCallArg, MoveCallInfo,
+        MoveCallSimulation, MoveCallSpec, MoveEventTypeInfo, PendingTransaction, Portfolio,
+        ProofValidity, PublisherCapInfo, SponsorRequest, StakePosition, StakingPoolInfo,
+        TransactionFunding, TransferPolicyRule, ValidatorSummary, ZkLoginSession,
+    },
+    readonly::{ReadOnlySquadConnect, SquadConnectRef},
+    resilient::ResilientSuiClient,
+};
+
+#[cfg(feature = "price-oracle")]
+use super::price_oracle::PriceCache;
+
+/// Gas budget for the preliminary transaction that merges coins
+const CONSOLIDATE_GAS_BUDGET: u64 = 10_000_000;
+
+const PUBLISHER_TYPE: &str = "0x2::package::Publisher";
+
+const VALIDATOR_EPOCH_INFO_EVENT: &str = "0x3::validator_set::ValidatorEpochInfoEventV2";
+
+/// Emitted by the system at every epoch change with the figures of the ending epoch
+const SYSTEM_EPOCH_INFO_EVENT: &str = "0x3::sui_system_state_inner::SystemEpochInfoEvent";
+
+const TRANSFER_POLICY_CREATED_EVENT: &str = "0x2::transfer_policy::TransferPolicyCreated";
+
+/// Emitted when a closed-loop `TokenPolicy<T>` is shared
+const TOKEN_POLICY_CREATED_EVENT: &str = "0x2::token::TokenPolicyCreated";
 
-use crate::service::dtos::Network;
+const TREASURY_CAP_TYPE: &str = "0x2::coin::TreasuryCap";
+
+/// Most transactions the node returns from one `multi_get_transactions_with_options` call
+const MULTI_GET_TRANSACTIONS_LIMIT: usize = 50;
+
+/// Recent transactions `get_pending_transactions` checks for finality
+pub const PENDING_TRANSACTIONS_LOOKBACK: usize = 50;
+
+const CHECKPOINT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const TRANSACTION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long `sponsor_and_wait` waits for a sponsored transaction to execute
+const SPONSORED_TX_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Digest of checkpoint 0 on testnet, whose first four bytes are the chain id
+const TESTNET_GENESIS_CHECKPOINT: &str = "69WiPg3DAQiwdxfncX6wYQ2siKwAe6L9BZthQea3JNMD";
+
+/// Digest of checkpoint 0 on mainnet, whose first four bytes are the chain id
+const MAINNET_GENESIS_CHECKPOINT: &str = "4btiuiMPvEENsttpZC7CZ53DruC3MAgfznDbASZ7DR6S";
+
+/// Epochs left before `max_epoch` at which `rotate_ephemeral_key` replaces the key by default
+pub const DEFAULT_REAUTH_THRESHOLD_EPOCHS: u64 = 1;
+
+/// Shortest wait between two checks of the key rotation monitor
+const MIN_KEY_ROTATION_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long `get_portfolio` serves cached balances by default
+pub const DEFAULT_PORTFOLIO_TTL: Duration = Duration::from_secs(30);
+
+/// How many sponsor requests `batch_sponsor_transactions` sends at once by default
+pub const DEFAULT_MAX_CONCURRENT_SPONSORS: usize = 5;
+
+/// How long `get_address_activity` serves a computed activity from cache
+pub const ADDRESS_ACTIVITY_TTL: Duration = Duration::from_secs(60);
+
+/// Most activities `get_address_activity` keeps cached at once
+pub const ADDRESS_ACTIVITY_CACHE_LIMIT: usize = 256;
+
+/// Transactions requested per page when walking an address's history
+const TRANSACTION_HISTORY_PAGE_SIZE: usize = 50;
+
+/// Activities by `(address, from_ms, to_ms)`, with when each was computed
+type ActivityCache = HashMap<(SuiAddress, u64, u64), (AddressActivity, Instant)>;
 
 #[derive(Clone)]
 pub struct SquadConnect {
     services: Services,
-    jwt: String,
+    portfolio_cache: Arc<RwLock<HashMap<SuiAddress, Portfolio>>>,
+    portfolio_ttl: Duration,
+    validate_move_calls: bool,
+    reauth_threshold_epochs: u64,
+    max_concurrent_sponsors: usize,
+    /// `None` to follow the network's default explorer
+    explorer_base_url: Option<String>,
+    memo_package_id: Option<ObjectID>,
+    /// Salts by `(iss, sub)`; Enoki never changes them for a user of this app
+    salt_cache: Arc<RwLock<HashMap<(String, String), String>>>,
+    activity_cache: Arc<RwLock<ActivityCache>>,
+    /// Address Enoki last reported, with the JWT it was reported for
+    known_address: Arc<RwLock<Option<(String, SuiAddress)>>>,
+    account_state_cache: Arc<RwLock<HashMap<SuiAddress, AccountState>>>,
+    #[cfg(feature = "price-oracle")]
+    price_cache: Arc<RwLock<PriceCache>>,
 }
 
 impl SquadConnect {
     pub fn new(node: SuiClient, client_id: String, network: Network, api_key: String) -> Self {
-        let services = Services::new(node, network, api_key, client_id);
+        SquadConnectBuilder::new(node, client_id, network, api_key).build()
+    }
+
+    pub(crate) fn from_services(services: Services) -> Self {
         Self {
             services,
-            jwt: String::new(),
+            portfolio_cache: Arc::default(),
+            portfolio_ttl: DEFAULT_PORTFOLIO_TTL,
+            validate_move_calls: false,
+            reauth_threshold_epochs: DEFAULT_REAUTH_THRESHOLD_EPOCHS,
+            max_concurrent_sponsors: DEFAULT_MAX_CONCURRENT_SPONSORS,
+            explorer_base_url: None,
+            memo_package_id: None,
+            salt_cache: Arc::default(),
+            activity_cache: Arc::default(),
+            known_address: Arc::default(),
+            account_state_cache: Arc::default(),
+            #[cfg(feature = "price-oracle")]
+            price_cache: Arc::default(),
         }
     }
 
+    /// Sets how long `get_portfolio` serves cached balances
+    pub fn set_portfolio_ttl(&mut self, ttl: Duration) {
+        self.portfolio_ttl = ttl;
+    }
+
+    /// Checks `call_move_function` arguments against the function signature
+    ///
+    /// Costs an extra ABI request per call, so it is meant for debugging.
+    pub fn set_validate_move_calls(&mut self, validate_move_calls: bool) {
+        self.validate_move_calls = validate_move_calls;
+    }
+
+    /// Sets how many epochs before `max_epoch` `rotate_ephemeral_key` replaces the key
+    pub fn set_reauth_threshold_epochs(&mut self, reauth_threshold_epochs: u64) {
+        self.reauth_threshold_epochs = reauth_threshold_epochs;
+    }
+
+    /// Sets how many sponsor requests `batch_sponsor_transactions` sends at once
+    ///
+    /// Values below 1 are treated as 1.
+    pub fn set_max_concurrent_sponsors(&mut self, max_concurrent_sponsors: usize) {
+        self.max_concurrent_sponsors = max_concurrent_sponsors.max(1);
+    }
+
+    /// Points explorer links at `explorer_base_url` instead of the network's default
+    pub fn set_explorer_base_url(&mut self, explorer_base_url: String) {
+        self.explorer_base_url = Some(explorer_base_url);
+    }
+
+    /// Package `send_with_memo` emits memos through, once published
+    pub fn memo_package_id(&self) -> Option<ObjectID> {
+        self.memo_package_id
+    }
+
+    /// Reuses a memo package published earlier, e.g. by another instance
+    pub fn set_memo_package_id(&mut self, memo_package_id: Option<ObjectID>) {
+        self.memo_package_id = memo_package_id;
+    }
+
+    /// Pyth prices `get_sui_price_usd` read on this instance's network
+    #[cfg(feature = "price-oracle")]
+    pub(crate) fn price_cache(&self) -> &RwLock<PriceCache> {
+        &self.price_cache
+    }
+
+    /// Connects to the public Sui testnet fullnode
+    pub async fn connect_to_testnet(client_id: String, api_key: String) -> Result<Self> {
+        let node = SuiClientBuilder::default()
+            .build_testnet()
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to connect to testnet: {}", e)))?;
+
+        Ok(SquadConnectBuilder::new(node, client_id, Network::Testnet, api_key).build())
+    }
+
+    /// Connects to the public Sui mainnet fullnode
+    pub async fn connect_to_mainnet(client_id: String, api_key: String) -> Result<Self> {
+        let node = SuiClientBuilder::default()
+            .build_mainnet()
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to connect to mainnet: {}", e)))?;
+
+        Ok(SquadConnectBuilder::new(node, client_id, Network::Mainnet, api_key).build())
+    }
+
+    /// Connects to the public Sui devnet fullnode
+    pub async fn connect_to_devnet(client_id: String, api_key: String) -> Result<Self> {
+        let node = SuiClientBuilder::default()
+            .build_devnet()
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to connect to devnet: {}", e)))?;
+
+        Ok(SquadConnectBuilder::new(node, client_id, Network::Devnet, api_key).build())
+    }
+
+    /// Connects to a custom fullnode
+    ///
+    /// The Enoki network is inferred from the node's chain identifier. Only
+    /// mainnet and testnet nodes are recognized; connect to devnet with
+    /// `connect_to_devnet` or `SquadConnectBuilder` instead.
+    pub async fn connect_to_url(rpc_url: &str, client_id: String, api_key: String) -> Result<Self> {
+        let node = SuiClientBuilder::default()
+            .build(rpc_url)
+            .await
+            .map_err(|e| {
+                ServiceError::Network(format!("Failed to connect to {}: {}", rpc_url, e))
+            })?;
+
+        let chain_identifier =
+            node.read_api().get_chain_identifier().await.map_err(|e| {
+                ServiceError::Network(format!("Failed to get chain identifier: {}", e))
+            })?;
+
+        let network = Network::from_chain_identifier(&chain_identifier)?;
+
+        Ok(SquadConnectBuilder::new(node, client_id, network, api_key).build())
+    }
+
+    /// Returns a borrowed handle exposing only the non-mutating methods
+    pub fn readonly(&self) -> SquadConnectRef<'_> {
+        SquadConnectRef::new(self)
+    }
+
+    /// Returns an owned, cloneable read-only copy of this instance
+    ///
+    /// Calling a mutating method on the copy panics.
+    pub fn clone_readonly(&self) -> ReadOnlySquadConnect {
+        ReadOnlySquadConnect::new(self.clone())
+    }
+
     pub fn get_node(&self) -> &SuiClient {
         &self.services.get_node()
     }
 
+    /// Switches the target network without rebuilding the instance
+    ///
+    /// This invalidates all existing session state: the nonce, the ZK proof
+    /// parameters and the JWT are cleared, and the zkLogin flow has to be
+    /// restarted with `create_zkp_payload`. The memo package, published on
+    /// the old network, is forgotten too. The new node is queried once and
+    /// rejected with `ServiceError::Network` if it cannot be reached.
+    ///
+    /// Fallback endpoints of the old network are dropped. To fall over on the
+    /// new network, pass a `ResilientSuiClient` connected with its
+    /// `NetworkConfig` instead of a plain `SuiClient`.
+    pub async fn with_network(
+        &mut self,
+        network: Network,
+        new_node: impl Into<ResilientSuiClient>,
+    ) -> Result<()> {
+        let new_node = new_node.into();
+
+        new_node
+            .read_api()
+            .get_chain_identifier()
+            .await
+            .map_err(|e| {
+                ServiceError::Network(format!(
+                    "Node (api version {}) is unreachable: {}",
+                    new_node.api_version(),
+                    e
+                ))
+            })?;
+
+        self.services.set_network(network, new_node);
+        self.services.clear_jwt();
+        self.portfolio_cache = Arc::default();
+        self.activity_cache = Arc::default();
+        self.account_state_cache = Arc::default();
+        #[cfg(feature = "price-oracle")]
+        self.price_cache = Arc::default();
+        // Packages are published per network
+        self.memo_package_id = None;
+
+        Ok(())
+    }
+
+    pub fn get_network(&self) -> &Network {
+        self.services.get_network()
+    }
+
+    /// Explorer page of `address` on the configured network
+    pub fn get_address_link(&self, address: SuiAddress) -> String {
+        explorer::address_url(self.explorer_base_url(), self.get_network(), address)
+    }
+
+    /// Explorer page of the transaction `digest` on the configured network
+    pub fn get_transaction_link(&self, digest: &str) -> String {
+        explorer::transaction_url(self.explorer_base_url(), self.get_network(), digest)
+    }
+
+    /// Explorer page of the object `id` on the configured network
+    pub fn get_object_link(&self, id: ObjectID) -> String {
+        explorer::object_url(self.explorer_base_url(), self.get_network(), id)
+    }
+
+    fn explorer_base_url(&self) -> &str {
+        self.explorer_base_url
+            .as_deref()
+            .unwrap_or_else(|| self.get_network().default_explorer_url())
+    }
+
     pub fn get_zk_proof_params(&self) -> (String, String, u64) {
         self.services.get_zk_proof_params()
     }
 
+    /// The nonce from `create_zkp_payload`, empty before it has run
+    pub fn get_nonce(&self) -> &str {
+        self.services.get_nonce()
+    }
+
+    /// The nonce and its parameters, see `NonceParams::is_valid`
+    pub fn get_nonce_params(&self) -> NonceParams {
+        self.services.get_nonce_params()
+    }
+
+    /// When the current nonce is expected to expire
+    pub fn nonce_expires_at(&self) -> Option<SystemTime> {
+        self.services.nonce_expires_at()
+    }
+
+    /// Time left before the current nonce expires
+    pub fn nonce_expires_in(&self) -> Option<Duration> {
+        self.services.nonce_expires_in()
+    }
+
+    /// Tells which step of the zkLogin flow comes next
+    pub fn get_zklogin_flow_state(&self) -> ZkLoginFlowState {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        if max_epoch == 0 {
+            ZkLoginFlowState::NeedsPayloadInit
+        } else if self.services.get_jwt().is_empty() {
+            ZkLoginFlowState::NeedsOauthCallback
+        } else if self.services.cached_zk_proof().is_none() {
+            ZkLoginFlowState::NeedsZkProof
+        } else {
+            ZkLoginFlowState::ReadyToSign
+        }
+    }
+
+    /// Logs out: zeroizes the JWT and all zkLogin session state
+    pub fn reset_session(&mut self) {
+        self.services.reset_zkp_session();
+        self.services.clear_jwt();
+    }
+
+    /// Whether Enoki has not rejected the API key; see `Services::is_api_key_valid`
+    pub fn is_api_key_valid(&self) -> bool {
+        self.services.is_api_key_valid()
+    }
+
+    /// Lets Enoki calls through again, e.g. after the API key was rotated
+    pub fn reset_api_key_status(&mut self) {
+        self.services.reset_api_key_status();
+    }
+
     pub fn set_jwt(&mut self, jwt: String) {
-        self.jwt = jwt;
+        self.services.set_jwt(jwt);
     }
 
     pub fn set_zk_proof_params(&mut self, randomness: String, public_key: String, max_epoch: u64) {
@@ -53,12 +449,121 @@ impl SquadConnect {
             .set_zk_proof_params(randomness, public_key, max_epoch);
     }
 
+    /// The current session, for storing outside this instance
+    pub fn zk_login_session(&self) -> ZkLoginSession {
+        let (randomness, public_key, max_epoch) = self.get_zk_proof_params();
+
+        ZkLoginSession {
+            randomness,
+            public_key,
+            max_epoch,
+            jwt: self.services.get_jwt().to_string(),
+        }
+    }
+
     pub async fn create_zkp_payload(&mut self, path: PathBuf) -> Result<()> {
         self.services.create_zkp_payload(path).await?;
 
         Ok(())
     }
 
+    /// Replaces the ephemeral key once `max_epoch` is close
+    ///
+    /// When `reauth_threshold_epochs` or fewer epochs are left, a new key
+    /// pair is stored at `path` and a new nonce requested through
+    /// `create_zkp_payload`. The JWT and proof of the old nonce no longer
+    /// match and are cleared, so the user has to complete the OAuth flow
+    /// again. Returns whether the key was rotated.
+    pub async fn rotate_ephemeral_key(&mut self, path: PathBuf) -> Result<bool> {
+        let current_epoch = self.get_system_state().await?.epoch;
+
+        self.rotate_ephemeral_key_at_epoch(path, current_epoch)
+            .await
+    }
+
+    /// `rotate_ephemeral_key` with the current epoch already known
+    async fn rotate_ephemeral_key_at_epoch(
+        &mut self,
+        path: PathBuf,
+        current_epoch: u64,
+    ) -> Result<bool> {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        if !needs_key_rotation(max_epoch, current_epoch, self.reauth_threshold_epochs) {
+            return Ok(false);
+        }
+
+        self.create_zkp_payload(path).await?;
+        self.services.clear_jwt();
+
+        Ok(true)
+    }
+
+    /// Calls `rotate_ephemeral_key` four times per epoch in the background
+    ///
+    /// Every rotation is reported on `tx`. The task stops once the receiver
+    /// is dropped; failed checks are logged and retried on the next tick.
+    pub fn start_key_rotation_monitor(
+        squad_connect: Arc<tokio::sync::Mutex<SquadConnect>>,
+        path: PathBuf,
+        tx: tokio::sync::mpsc::Sender<KeyRotationEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                // The epoch is read without holding the lock, so other
+                // callers only wait while a key is actually rotated
+                let node = squad_connect
+                    .lock()
+                    .await
+                    .services
+                    .get_resilient_node()
+                    .clone();
+
+                let state = match node
+                    .call(|node| async move {
+                        node.governance_api().get_latest_sui_system_state().await
+                    })
+                    .await
+                {
+                    Ok(state) => state,
+                    Err(e) => {
+                        log::error!("Failed to get system state: {}", e);
+                        tokio::time::sleep(MIN_KEY_ROTATION_POLL_INTERVAL).await;
+                        continue;
+                    }
+                };
+
+                let mut squad_connect = squad_connect.lock().await;
+
+                match squad_connect
+                    .rotate_ephemeral_key_at_epoch(path.clone(), state.epoch)
+                    .await
+                {
+                    Ok(true) => {
+                        let (_, new_public_key, new_max_epoch) =
+                            squad_connect.get_zk_proof_params();
+                        let event = KeyRotationEvent {
+                            new_max_epoch,
+                            new_public_key,
+                            rotated_at: Instant::now(),
+                        };
+
+                        if tx.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => log::error!("Failed to rotate ephemeral key: {}", e),
+                }
+
+                drop(squad_connect);
+
+                let interval = Duration::from_millis(state.epoch_duration_ms / 4);
+                tokio::time::sleep(interval.max(MIN_KEY_ROTATION_POLL_INTERVAL)).await;
+            }
+        })
+    }
+
     pub async fn get_url<T: Send + Serialize>(
         &mut self,
         redirect_url: String,
@@ -70,11 +575,182 @@ impl SquadConnect {
     }
 
     pub async fn recover_seed_address(&self) -> Result<ZkLoginInputs> {
-        let zkresponse = self.services.zk_proof(&self.jwt).await?;
+        let zkresponse = self.services.zk_proof_stored().await?;
 
         Ok(zkresponse)
     }
 
+    /// Requests zkLogin proofs for several JWTs concurrently
+    ///
+    /// Results are in the same order as `jwts`, and one failing request
+    /// does not affect the others. Each request fails with
+    /// `ServiceError::Timeout` once it takes longer than the configured
+    /// request timeout. The stored JWT's proof is served from the cache
+    /// when available. All proofs use the current ephemeral key and nonce,
+    /// so every JWT must have been issued for that nonce.
+    pub async fn get_multiple_zk_proofs(&self, jwts: Vec<&str>) -> Vec<Result<ZkLoginInputs>> {
+        let cached = self
+            .services
+            .cached_zk_proof()
+            .map(|zk_inputs| (self.services.get_jwt(), zk_inputs));
+
+        let mut results: Vec<Option<Result<ZkLoginInputs>>> = jwts.iter().map(|_| None).collect();
+        let mut tasks = JoinSet::new();
+
+        for (index, jwt) in jwts.into_iter().enumerate() {
+            if let Some((_, zk_inputs)) =
+                cached.as_ref().filter(|(cached_jwt, _)| *cached_jwt == jwt)
+            {
+                results[index] = Some(Ok(zk_inputs.clone()));
+                continue;
+            }
+
+            let services = self.services.clone();
+            let jwt = jwt.to_string();
+
+            tasks.spawn(async move {
+                let timeout = services.request_timeout();
+
+                let result = tokio::time::timeout(timeout, services.zk_proof(&jwt))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(ServiceError::Timeout(format!(
+                            "zkLogin proof request timed out after {:?}",
+                            timeout
+                        )))
+                    });
+
+                (index, result)
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => log::error!("Proof task failed: {}", e),
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result
+                    .unwrap_or_else(|| Err(ServiceError::Service("Proof task failed".to_string())))
+            })
+            .collect()
+    }
+
+    /// Fetches the zkLogin accounts of several JWTs concurrently
+    ///
+    /// Results are in the same order as `jwts`. Each request fails with
+    /// `ServiceError::Timeout` once it takes longer than the configured
+    /// request timeout.
+    pub async fn get_multiple_accounts(&self, jwts: Vec<&str>) -> Vec<Result<AccountResponse>> {
+        let mut results: Vec<Option<Result<AccountResponse>>> = jwts.iter().map(|_| None).collect();
+        let mut tasks = JoinSet::new();
+
+        for (index, jwt) in jwts.into_iter().enumerate() {
+            let services = self.services.clone();
+            let jwt = jwt.to_string();
+
+            tasks.spawn(async move {
+                let timeout = services.request_timeout();
+
+                let result = tokio::time::timeout(timeout, services.get_account(&jwt))
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(ServiceError::Timeout(format!(
+                            "Account request timed out after {:?}",
+                            timeout
+                        )))
+                    });
+
+                (index, result)
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => log::error!("Account task failed: {}", e),
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(ServiceError::Service("Account task failed".to_string()))
+                })
+            })
+            .collect()
+    }
+
+    /// Fetches the zkLogin proof and the account for the stored JWT concurrently
+    ///
+    /// This is what an app needs right after the OAuth callback.
+    pub async fn get_zklogin_proof_and_account(&self) -> Result<(ZkLoginInputs, AccountResponse)> {
+        tokio::try_join!(self.recover_seed_address(), self.get_address())
+    }
+
+    /// Stores `jwt` and fetches its zkLogin proof and account concurrently
+    ///
+    /// Convert the result into a `ZkLoginResult` to also get the parsed
+    /// address.
+    pub async fn get_zklogin_inputs_from_jwt(
+        &mut self,
+        jwt: String,
+    ) -> Result<(ZkLoginInputs, AccountResponse)> {
+        self.set_jwt(jwt);
+
+        self.get_zklogin_proof_and_account().await
+    }
+
+    /// Signs in with the OAuth callback URL the provider redirected to
+    ///
+    /// This is the recommended integration point for web callback handlers.
+    /// The `id_token` is read from the query string or the fragment and
+    /// stored, then the proof and account are fetched concurrently as in
+    /// `get_zklogin_proof_and_account`. The address Enoki reports must be
+    /// the one derived from the proof; otherwise the JWT is cleared and
+    /// `ServiceError::InvalidProof` returned.
+    pub async fn handle_oauth_redirect(&mut self, callback_url: &str) -> Result<AuthResult> {
+        let jwt = extract_jwt_from_callback_url(callback_url)?;
+        self.set_jwt(jwt);
+
+        let (zk_inputs, account) = self.get_zklogin_proof_and_account().await?;
+        let address = account_address(&account)?;
+
+        if !zk_login_address_matches(&zk_inputs, address) {
+            self.services.clear_jwt();
+
+            return Err(ServiceError::InvalidProof(format!(
+                "Proof does not match the account address {}",
+                address
+            )));
+        }
+
+        Ok(AuthResult {
+            zk_inputs,
+            account,
+            address,
+        })
+    }
+
+    /// `handle_oauth_redirect` that also returns the `state` passed to `get_url`
+    ///
+    /// The state is parsed first, so a malformed one fails before any
+    /// request is sent.
+    pub async fn handle_oauth_redirect_with_state<S: for<'de> Deserialize<'de>>(
+        &mut self,
+        callback_url: &str,
+    ) -> Result<(AuthResult, Option<S>)> {
+        let state = extract_state_from_callback_url(callback_url)?;
+        let auth = self.handle_oauth_redirect(callback_url).await?;
+
+        Ok((auth, state))
+    }
+
     pub fn extract_state_from_callback<T: for<'de> Deserialize<'de>>(
         &self,
         callback_url: &str,
@@ -82,12 +758,193 @@ impl SquadConnect {
         self.services.extract_state_from_callback(callback_url)
     }
 
+    /// Fetches Google's public signing keys, cached for one hour
+    pub async fn get_google_jwks() -> Result<Vec<Jwk>> {
+        services::get_google_jwks().await
+    }
+
+    /// Verifies the JWT signature against Google's published keys
+    ///
+    /// Only the RS256/ES256 signature is checked; expiry and audience are left
+    /// to the caller so an expired token still reports a valid signature.
+    pub async fn verify_jwt_signature(&self, jwt: &str) -> Result<()> {
+        let header = decode_header(jwt)
+            .map_err(|e| ServiceError::JwtFormat(format!("Failed to decode JWT header: {}", e)))?;
+
+        if !matches!(header.alg, Algorithm::RS256 | Algorithm::ES256) {
+            return Err(ServiceError::JwtFormat(format!(
+                "Unsupported JWT algorithm: {:?}",
+                header.alg
+            )));
+        }
+
+        let kid = header
+            .kid
+            .ok_or_else(|| ServiceError::JwtFormat("JWT header has no kid".to_string()))?;
+
+        let jwks = Self::get_google_jwks().await?;
+
+        let jwk = jwks
+            .iter()
+            .find(|jwk| jwk.common.key_id.as_deref() == Some(kid.as_str()))
+            .ok_or_else(|| {
+                ServiceError::InvalidProof("JWT signature verification failed".to_string())
+            })?;
+
+        let decoding_key = DecodingKey::from_jwk(jwk).map_err(|_| {
+            ServiceError::InvalidProof("JWT signature verification failed".to_string())
+        })?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.validate_exp = false;
+        validation.validate_aud = false;
+        validation.required_spec_claims.clear();
+
+        decode::<serde_json::Value>(jwt, &decoding_key, &validation).map_err(|_| {
+            ServiceError::InvalidProof("JWT signature verification failed".to_string())
+        })?;
+
+        Ok(())
+    }
+
+    /// Lists the OAuth providers Enoki supports for this app, cached for one hour
+    pub async fn get_supported_oauth_providers(&self) -> Result<Vec<String>> {
+        self.services.get_supported_oauth_providers().await
+    }
+
     pub async fn get_address(&self) -> Result<AccountResponse> {
-        let account = self.services.get_account(&self.jwt).await?;
+        let account = self.services.get_account(self.services.get_jwt()).await?;
+
+        Ok(account)
+    }
+
+    /// Salt of the zkLogin account for the stored JWT
+    ///
+    /// The salt is stable for a given `(user, app)` pair, so it is fetched
+    /// from Enoki once per issuer and subject and served from cache after
+    /// that, across JWT refreshes.
+    pub async fn get_zklogin_salt(&self) -> Result<String> {
+        let claims = services::decode_jwt_claims(self.services.get_jwt())?;
+        let key = (
+            jwt_claim(&claims, "iss")?.to_string(),
+            jwt_claim(&claims, "sub")?.to_string(),
+        );
+
+        if let Some(salt) = self
+            .salt_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned())
+        {
+            return Ok(salt);
+        }
+
+        let account = self.get_address().await?;
+
+        if let Ok(mut cache) = self.salt_cache.write() {
+            cache.insert(key, account.salt.clone());
+        }
+
+        Ok(account.salt)
+    }
+
+    /// Derives the zkLogin address of the stored JWT locally
+    ///
+    /// Uses the salt from `get_zklogin_salt` and the `iss`, `sub` and `aud`
+    /// claims of the JWT, so once the salt is cached the address can be
+    /// checked without trusting Enoki's answer. For a JWT with several
+    /// audiences the first one is used.
+    pub async fn recompute_address(&self) -> Result<SuiAddress> {
+        let salt = self.get_zklogin_salt().await?;
+        let claims = services::decode_jwt_claims(self.services.get_jwt())?;
+
+        let aud = match &claims["aud"] {
+            serde_json::Value::Array(audiences) => audiences.first().and_then(|aud| aud.as_str()),
+            aud => aud.as_str(),
+        }
+        .ok_or_else(|| ServiceError::JwtExtraction("JWT has no aud claim".to_string()))?;
+
+        compute_address_with_salt(
+            jwt_claim(&claims, "sub")?,
+            &salt,
+            jwt_claim(&claims, "iss")?,
+            aud,
+        )
+    }
+
+    /// Gets the zkLogin account for a JWT issued by `provider`
+    ///
+    /// The JWT's `iss` claim must belong to `provider`; a token from
+    /// another provider would otherwise silently resolve to a different
+    /// address. On success the JWT becomes the session JWT.
+    pub async fn get_zklogin_address_for_provider(
+        &mut self,
+        provider: OauthProvider,
+        jwt: &str,
+    ) -> Result<AccountResponse> {
+        let claims = services::decode_jwt_claims(jwt)?;
+        let issuer = claims["iss"]
+            .as_str()
+            .ok_or_else(|| ServiceError::JwtExtraction("JWT has no iss claim".to_string()))?;
+
+        if !provider.is_issuer(issuer) {
+            return Err(ServiceError::JwtExtraction(format!(
+                "JWT issued by {} is not a {} token",
+                issuer, provider
+            )));
+        }
+
+        let account = self.services.get_account(jwt).await?;
+        self.set_jwt(jwt.to_string());
 
         Ok(account)
     }
 
+    /// Checks that `zk_inputs` prove ownership of `expected_address`
+    ///
+    /// Guards against a tampered or mixed-up Enoki response: the address is
+    /// derived locally from the proof's issuer and address seed. Returns
+    /// `false` if it doesn't match, and fails with
+    /// `ServiceError::InvalidProof` if `max_epoch` has already passed.
+    pub async fn verify_zk_address(
+        &self,
+        zk_inputs: &ZkLoginInputs,
+        expected_address: SuiAddress,
+        max_epoch: u64,
+    ) -> Result<bool> {
+        if !zk_login_address_matches(zk_inputs, expected_address) {
+            return Ok(false);
+        }
+
+        let current_epoch = self.get_system_state().await?.epoch;
+
+        if max_epoch < current_epoch {
+            return Err(ServiceError::InvalidProof(format!(
+                "Max epoch {} is before the current epoch {}",
+                max_epoch, current_epoch
+            )));
+        }
+
+        Ok(true)
+    }
+
+    /// `verify_zk_address` against the account Enoki reports for the stored JWT
+    pub async fn verify_my_zk_address(&self, zk_inputs: &ZkLoginInputs) -> Result<bool> {
+        let account = self.get_address().await?;
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        self.verify_zk_address(zk_inputs, account_address(&account)?, max_epoch)
+            .await
+    }
+
+    /// Looks up the zkLogin address of a Google user by email
+    ///
+    /// Requires the `email` scope in the OAuth request; see
+    /// `Services::get_zklogin_address_by_email` for how the lookup works.
+    pub async fn get_zklogin_address_by_email(&self, email: &str) -> Result<Option<SuiAddress>> {
+        self.services.get_zklogin_address_by_email(email).await
+    }
+
     pub async fn sign_transaction(
         &self,
         tx: TransactionData,
@@ -96,6 +953,8 @@ impl SquadConnect {
         max_epoch: u64,
         path: PathBuf,
     ) -> Result<Transaction> {
+        self.validate_object_refs(&tx).await?;
+
         let key_store = FileBasedKeystore::new(&path).map_err(|e| {
             ServiceError::InvalidResponse(format!("Failed to create key store: {}", e))
         })?;
@@ -116,23 +975,4001 @@ impl SquadConnect {
         Ok(transaction)
     }
 
-    pub async fn sponsor_transaction(
-        &mut self,
-        tx: Transaction,
-        sender: SuiAddress,
-        allowed_addresses: Vec<String>,
-        allowed_move_call_targets: Vec<String>,
-    ) -> Result<String> {
-        let sponsor_transaction = self
-            .services
-            .create_sponsor_transaction(tx, sender, allowed_addresses, allowed_move_call_targets)
-            .await?;
+    /// Signs `tx` with the ephemeral Ed25519 key alone, without zkLogin
+    ///
+    /// Meant for testing the signing path in isolation: the signature
+    /// authenticates the ephemeral address, so the network only accepts the
+    /// transaction if its sender is `signer` rather than the zkLogin account.
+    pub fn sign_transaction_with_ephemeral_key_only(
+        &self,
+        tx: &TransactionData,
+        path: &Path,
+        signer: SuiAddress,
+    ) -> Result<Transaction> {
+        let key_store = FileBasedKeystore::new(&path.to_path_buf()).map_err(|e| {
+            ServiceError::InvalidResponse(format!("Failed to create key store: {}", e))
+        })?;
 
-        let result = self
-            .services
-            .submit_sponsor_transaction(sponsor_transaction.digest, sponsor_transaction.bytes)
-            .await?;
+        let signature = key_store
+            .sign_secure(&signer, tx, Intent::sui_transaction())
+            .map_err(|e| {
+                ServiceError::InvalidResponse(format!("Failed to sign transaction: {}", e))
+            })?;
 
-        Ok(result.digest)
+        Ok(Transaction::from_generic_sig_data(
+            tx.clone(),
+            vec![GenericSignature::Signature(signature)],
+        ))
     }
+
+    /// Signs `tx` as the multi-sig account of `multi_sig_public_key`
+    ///
+    /// The committee comes from `utils::multisig::multi_sig_public_key`, and
+    /// the transaction sender must be its address. Each signer is an address
+    /// and the keystore holding its key. The signer whose key is the
+    /// ephemeral key signs for the zkLogin member of the committee, so
+    /// `zk_inputs` are needed when it takes part; the others sign with their
+    /// own key. The signers' weights must reach the committee's threshold.
+    pub async fn create_multi_sig_transaction(
+        &self,
+        tx: TransactionData,
+        multi_sig_public_key: MultiSigPublicKey,
+        signers: Vec<(SuiAddress, PathBuf)>,
+        zk_inputs: Option<ZkLoginInputs>,
+        max_epoch: u64,
+    ) -> Result<Transaction> {
+        let multi_sig_address = SuiAddress::from(&multi_sig_public_key);
+
+        if tx.sender() != multi_sig_address {
+            return Err(ServiceError::Service(format!(
+                "Transaction sender {} is not the multi-sig address {}",
+                tx.sender(),
+                multi_sig_address
+            )));
+        }
+
+        self.validate_object_refs(&tx).await?;
+
+        let ephemeral_address = self.ephemeral_address().ok();
+        let mut signatures = Vec::with_capacity(signers.len());
+
+        for (signer, path) in signers {
+            let key_store = FileBasedKeystore::new(&path).map_err(|e| {
+                ServiceError::InvalidResponse(format!("Failed to create key store: {}", e))
+            })?;
+
+            let signature = key_store
+                .sign_secure(&signer, &tx, Intent::sui_transaction())
+                .map_err(|e| {
+                    ServiceError::InvalidResponse(format!("Failed to sign transaction: {}", e))
+                })?;
+
+            let signature = match &zk_inputs {
+                Some(zk_inputs) if Some(signer) == ephemeral_address => {
+                    GenericSignature::ZkLoginAuthenticator(ZkLoginAuthenticator::new(
+                        zk_inputs.clone(),
+                        max_epoch,
+                        signature,
+                    ))
+                }
+                _ => GenericSignature::Signature(signature),
+            };
+
+            signatures.push(signature);
+        }
+
+        let multi_sig = MultiSig::combine(signatures, multi_sig_public_key).map_err(|e| {
+            ServiceError::Service(format!("Failed to combine multi-sig signatures: {}", e))
+        })?;
+
+        Ok(Transaction::from_generic_sig_data(
+            tx,
+            vec![GenericSignature::MultiSig(multi_sig)],
+        ))
+    }
+
+    pub async fn sponsor_transaction(
+        &mut self,
+        tx: Transaction,
+        sender: SuiAddress,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        let sponsor_transaction = self
+            .services
+            .create_sponsor_transaction(tx, sender, allowed_addresses, allowed_move_call_targets)
+            .await?;
+
+        let result = self
+            .services
+            .submit_sponsor_transaction(sponsor_transaction.digest, sponsor_transaction.bytes)
+            .await?;
+
+        Ok(result.digest)
+    }
+
+    /// Sponsors several transactions concurrently, then signs and submits each
+    ///
+    /// Every request is sponsored like `sponsor_transaction`; the sponsored
+    /// bytes are then signed with the ephemeral key like in
+    /// `sponsor_and_wait`, and each result is the digest once the
+    /// transaction is visible on the node.
+    ///
+    /// At most `max_concurrent_sponsors` requests are in flight at once.
+    /// Results are in the same order as `requests`, and one failing request
+    /// does not affect the others. The transaction digest serves as the
+    /// idempotency key: a transaction appearing more than once in the batch
+    /// is sponsored only the first time, and its repeats fail with
+    /// `ServiceError::Service` instead of being charged again.
+    pub async fn batch_sponsor_transactions(
+        &mut self,
+        requests: Vec<SponsorRequest>,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Vec<Result<String>> {
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrent_sponsors));
+        let mut results: Vec<Option<Result<String>>> = requests.iter().map(|_| None).collect();
+        let mut idempotency_keys = HashSet::new();
+        let mut tasks = JoinSet::new();
+
+        for (index, request) in requests.into_iter().enumerate() {
+            let idempotency_key = *request.tx.digest();
+
+            if !idempotency_keys.insert(idempotency_key) {
+                results[index] = Some(Err(ServiceError::Service(format!(
+                    "Transaction {} is already sponsored in this batch",
+                    idempotency_key
+                ))));
+                continue;
+            }
+
+            let mut squad_connect = self.clone();
+            let zk_inputs = zk_inputs.clone();
+            let path = path.clone();
+            let semaphore = semaphore.clone();
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+
+                let result = async {
+                    let sponsor_transaction = squad_connect
+                        .services
+                        .create_sponsor_transaction(
+                            request.tx,
+                            request.sender,
+                            request.allowed_addresses,
+                            request.allowed_move_call_targets,
+                        )
+                        .await?;
+
+                    squad_connect
+                        .submit_sponsored(sponsor_transaction, zk_inputs, max_epoch, path)
+                        .await
+                }
+                .await;
+
+                (index, result)
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(e) => log::error!("Sponsor task failed: {}", e),
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(ServiceError::Service("Sponsor task failed".to_string()))
+                })
+            })
+            .collect()
+    }
+
+    /// Sponsors `TransactionData` directly, without building a `Transaction`
+    ///
+    /// The BCS-encoded transaction kind is sent to Enoki with
+    /// `account.address` as the sender. The sponsored bytes are then signed
+    /// with the ephemeral key and submitted, and the call returns once the
+    /// transaction is visible on the node. Same as `sponsor_and_wait`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sponsor_transaction_data(
+        &mut self,
+        tx_data: TransactionData,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        self.sponsor_and_wait(
+            tx_data,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
+    }
+
+    /// Sponsors `tx_data` through Enoki, signs it and waits until it executes
+    ///
+    /// Only the transaction kind is sent: Enoki fills in its own gas, so the
+    /// gas budget and payment of `tx_data` are ignored. The sender then signs
+    /// the sponsored bytes with the ephemeral key, and the call returns once
+    /// the transaction is visible on the node.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sponsor_and_wait(
+        &mut self,
+        tx_data: TransactionData,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        let sponsor_transaction = self
+            .request_sponsorship(
+                &tx_data,
+                &account,
+                allowed_addresses,
+                allowed_move_call_targets,
+            )
+            .await?;
+
+        self.submit_sponsored(sponsor_transaction, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    /// Sponsors `tx` through Enoki, or pays the gas from `account` if Enoki declines
+    ///
+    /// Falls back only when the sponsorship request itself fails with
+    /// `ServiceError::Network`, `ServiceError::Unauthorized` or
+    /// `ServiceError::Forbidden`: Enoki rejecting the request (address or
+    /// Move call target not allowed, quota exhausted, any non-2xx status)
+    /// or Enoki being unreachable. Once Enoki has sponsored the transaction,
+    /// later failures are returned as they are, since the sponsored
+    /// transaction may already have been submitted.
+    ///
+    /// When paying itself, gas comes from SUI coins of `account` that `tx`
+    /// does not use as inputs. A zero gas budget, as in transactions built
+    /// for sponsorship only, is replaced with a dev-inspect estimate.
+    /// Returns the digest and how the gas was paid.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sponsor_with_fallback(
+        &mut self,
+        tx: TransactionData,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<(String, TransactionFunding)> {
+        match self
+            .request_sponsorship(&tx, &account, allowed_addresses, allowed_move_call_targets)
+            .await
+        {
+            Ok(sponsor_transaction) => {
+                let digest = self
+                    .submit_sponsored(sponsor_transaction, zk_inputs, max_epoch, path)
+                    .await?;
+
+                Ok((digest, TransactionFunding::Sponsored))
+            }
+            Err(
+                ServiceError::Network(e)
+                | ServiceError::Unauthorized(e)
+                | ServiceError::Forbidden(e),
+            ) => {
+                log::warn!("Sponsorship failed, paying gas from the wallet: {}", e);
+
+                let tx_data = self.self_paid_transaction(&tx, &account).await?;
+                let response = self
+                    .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+                    .await?;
+
+                let gas_used_mist = response
+                    .effects
+                    .as_ref()
+                    .map(|effects| effects.gas_cost_summary().gas_used())
+                    .unwrap_or_default();
+
+                Ok((
+                    response.digest.to_string(),
+                    TransactionFunding::SelfPaid { gas_used_mist },
+                ))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Asks Enoki to sponsor the kind of `tx_data`
+    async fn request_sponsorship(
+        &mut self,
+        tx_data: &TransactionData,
+        account: &AccountResponse,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<SponsorTransactionResponse> {
+        let sender = account_address(account)?;
+
+        let kind_bytes = bcs::to_bytes(tx_data.kind()).map_err(|e| {
+            ServiceError::Service(format!("Failed to serialize transaction kind: {}", e))
+        })?;
+
+        self.services
+            .create_sponsor_transaction_kind(
+                Base64::from_bytes(&kind_bytes),
+                sender,
+                allowed_addresses,
+                allowed_move_call_targets,
+            )
+            .await
+    }
+
+    /// Signs a transaction sponsored by Enoki, submits it and waits until it executes
+    async fn submit_sponsored(
+        &mut self,
+        sponsor_transaction: SponsorTransactionResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sponsored_bytes = Base64::decode(&sponsor_transaction.bytes).map_err(|e| {
+            ServiceError::InvalidResponse(format!("Invalid sponsored transaction bytes: {}", e))
+        })?;
+        let sponsored_data: TransactionData = bcs::from_bytes(&sponsored_bytes).map_err(|e| {
+            ServiceError::InvalidResponse(format!("Invalid sponsored transaction: {}", e))
+        })?;
+
+        let signer = self.ephemeral_address()?;
+        let transaction = self
+            .sign_transaction(sponsored_data, signer, zk_inputs, max_epoch, path)
+            .await?;
+
+        let (_, signatures) = transaction.to_tx_bytes_and_signatures();
+        let signature = signatures.first().ok_or_else(|| {
+            ServiceError::InvalidProof("Sponsored transaction was not signed".to_string())
+        })?;
+
+        let result = self
+            .services
+            .submit_sponsor_transaction(sponsor_transaction.digest, signature.encoded())
+            .await?;
+
+        self.wait_for_transaction(&result.digest, SPONSORED_TX_TIMEOUT)
+            .await?;
+
+        Ok(result.digest)
+    }
+
+    /// `tx` with gas paid by `account` from coins it does not otherwise use
+    async fn self_paid_transaction(
+        &self,
+        tx: &TransactionData,
+        account: &AccountResponse,
+    ) -> Result<TransactionData> {
+        let sender = account_address(account)?;
+        let gas_price = self.reference_gas_price().await?;
+
+        let gas_budget = match tx.gas_budget() {
+            0 => {
+                let simulation = self
+                    .simulate_transaction(sender, tx.kind().clone(), None)
+                    .await?;
+
+                if !is_simulation_successful(&simulation) {
+                    return Err(ServiceError::DryRunFailed(
+                        simulation.error.unwrap_or(simulation.effects_status),
+                    ));
+                }
+
+                // Leave headroom, as gas costs vary between dev-inspect and execution
+                (simulation.gas_used_mist + simulation.gas_used_mist / 5)
+                    .max(gas_price.saturating_mul(services::MIN_GAS_UNITS))
+            }
+            gas_budget => gas_budget,
+        };
+
+        let inputs: Vec<ObjectID> = tx
+            .input_objects()
+            .map_err(|e| ServiceError::Service(format!("Invalid transaction inputs: {}", e)))?
+            .iter()
+            .map(|input| input.object_id())
+            .collect();
+
+        let coins: Vec<Coin> = self
+            .get_coins(sender, None)
+            .await?
+            .into_iter()
+            .filter(|coin| !inputs.contains(&coin.coin_object_id))
+            .collect();
+
+        let gas_coins = select_coins(&coins, gas_budget, CoinSelectionStrategy::Largest)
+            .ok_or_else(|| ServiceError::InsufficientGas {
+                available: coins.iter().map(|coin| coin.balance).sum(),
+                required: gas_budget,
+            })?;
+
+        Ok(TransactionData::new_with_gas_coins(
+            tx.kind().clone(),
+            sender,
+            gas_coins.iter().map(|coin| coin.object_ref()).collect(),
+            gas_budget,
+            gas_price,
+        ))
+    }
+
+    /// Sends every SUI coin of `account` to `recipient`
+    ///
+    /// Built with `TransactionData::new_pay_all_sui`: all coins are merged
+    /// into the gas coin, gas is deducted and the remainder, including what
+    /// is left of the gas budget, goes to `recipient`, leaving the sender
+    /// with no SUI. `TransactionData::new_transfer_sui` instead only moves
+    /// one coin (or an amount split from it) and keeps the gas coin with the
+    /// sender. Returns the transaction digest.
+    pub async fn pay_all_sui(
+        &mut self,
+        recipient: SuiAddress,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let coins = self.get_coins(sender, None).await?;
+        let (coin_refs, total) = aggregate_coins(&coins);
+
+        if total < gas_budget {
+            return Err(ServiceError::InsufficientGas {
+                available: total,
+                required: gas_budget,
+            });
+        }
+
+        let gas_price = self.reference_gas_price().await?;
+        let tx_data =
+            TransactionData::new_pay_all_sui(sender, coin_refs, recipient, gas_budget, gas_price);
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Sends every SUI coin of `account` to `recipient` with gas paid by Enoki
+    ///
+    /// `new_pay_all_sui` merges into the gas coin, which belongs to the
+    /// sponsor here, so the coins are merged and transferred explicitly.
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn pay_all_sui_sponsored(
+        &mut self,
+        recipient: SuiAddress,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let coins = self.get_coins(sender, None).await?;
+        let (coin_refs, _) = aggregate_coins(&coins);
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        let mut coin_args = coin_refs
+            .into_iter()
+            .map(|coin_ref| {
+                builder
+                    .obj(ObjectArg::ImmOrOwnedObject(coin_ref))
+                    .map_err(ptb_error)
+            })
+            .collect::<Result<Vec<Argument>>>()?;
+
+        if coin_args.is_empty() {
+            return Err(ServiceError::Service(format!("{} owns no SUI", sender)));
+        }
+
+        let primary = coin_args.remove(0);
+        if !coin_args.is_empty() {
+            builder.command(Command::MergeCoins(primary, coin_args));
+        }
+        builder.transfer_arg(recipient, primary);
+
+        let tx_data = TransactionData::new_programmable(sender, vec![], builder.finish(), 0, 0);
+
+        self.sponsor_and_wait(
+            tx_data,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
+    }
+
+    /// Pays `amounts[i]` MIST to `recipients[i]` from the coins of `account`
+    ///
+    /// Built with `TransactionData::new_pay_sui`: every SUI coin is merged
+    /// into the gas coin and the payments are split from it, so the change
+    /// stays with the sender as one coin. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn pay_sui(
+        &mut self,
+        recipients: Vec<SuiAddress>,
+        amounts: Vec<u64>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        if recipients.len() != amounts.len() {
+            return Err(ServiceError::Service(format!(
+                "{} recipients but {} amounts",
+                recipients.len(),
+                amounts.len()
+            )));
+        }
+
+        let sender = account_address(&account)?;
+        let coins = self.get_coins(sender, None).await?;
+        let (mut coin_refs, total) = aggregate_coins(&coins);
+        let required = amounts.iter().fold(gas_budget, |required, amount| {
+            required.saturating_add(*amount)
+        });
+
+        if total < required || coin_refs.is_empty() {
+            return Err(ServiceError::InsufficientGas {
+                available: total,
+                required,
+            });
+        }
+
+        let gas_payment = coin_refs.remove(0);
+        let gas_price = self.reference_gas_price().await?;
+
+        let tx_data = TransactionData::new_pay_sui(
+            sender,
+            coin_refs,
+            recipients,
+            amounts,
+            gas_payment,
+            gas_budget,
+            gas_price,
+        )
+        .map_err(ptb_error)?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Sends `amount_mist` SUI to `recipient` with gas paid by Enoki
+    ///
+    /// The transaction is built with a zero gas budget and only works
+    /// sponsored. `TransactionData::new_transfer_sui` splits the amount from
+    /// the gas coin, which here belongs to the sponsor, so the SUI is paid
+    /// from the sender's own coins instead. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_sui_with_sponsor(
+        &mut self,
+        recipient: SuiAddress,
+        amount_mist: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let coins = self.get_coins(sender, None).await?;
+
+        let selected = select_coins(&coins, amount_mist, CoinSelectionStrategy::Largest)
+            .ok_or_else(|| ServiceError::InsufficientGas {
+                available: coins.iter().map(|coin| coin.balance).sum(),
+                required: amount_mist,
+            })?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .pay(
+                selected.iter().map(|coin| coin.object_ref()).collect(),
+                vec![recipient],
+                vec![amount_mist],
+            )
+            .map_err(ptb_error)?;
+
+        let tx_data = TransactionData::new_programmable(sender, vec![], builder.finish(), 0, 0);
+
+        self.sponsor_and_wait(
+            tx_data,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
+    }
+
+    /// Transfers an owned object to `recipient` with gas paid by Enoki
+    ///
+    /// Like `transfer_sui_with_sponsor`, this only works sponsored.
+    /// Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn transfer_object_with_sponsor(
+        &mut self,
+        object_id: ObjectID,
+        recipient: SuiAddress,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<String> {
+        let sender = account_address(&account)?;
+        let object_ref = self.get_object_ref(object_id).await?;
+
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder
+            .transfer_object(recipient, object_ref)
+            .map_err(ptb_error)?;
+
+        let tx_data = TransactionData::new_programmable(sender, vec![], builder.finish(), 0, 0);
+
+        self.sponsor_and_wait(
+            tx_data,
+            account,
+            zk_inputs,
+            max_epoch,
+            path,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
+    }
+
+    /// Fetches the normalized Move modules of a deployed package
+    ///
+    /// # Example
+    /// ```rust
+    /// use squad_connect::utils::abi::{build_type_args_from_abi, find_function};
+    ///
+    /// let modules = squad_connect.get_package_abi(package_id).await?;
+    /// let function = find_function(&modules, "pool", "swap").ok_or("swap not found")?;
+    /// let type_args = build_type_args_from_abi(function, &["0x2::sui::SUI"])?;
+    ///
+    /// let tx_data = squad_connect
+    ///     .build_programmable_batch(sender, gas_budget, |builder| {
+    ///         builder.programmable_move_call(
+    ///             package_id,
+    ///             Identifier::new("pool").unwrap(),
+    ///             Identifier::new("swap").unwrap(),
+    ///             type_args,
+    ///             vec![],
+    ///         );
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn get_package_abi(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<SuiMoveNormalizedModule>> {
+        let modules = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .get_normalized_move_modules_by_package(package_id)
+                    .await
+            })
+            .await?;
+
+        Ok(modules.into_values().collect())
+    }
+
+    /// Event types `package_id` declares, with their fields
+    ///
+    /// Read from the package ABI, which does not tell events apart from
+    /// other structs: every struct with `copy` and `drop` but no `key` is
+    /// reported, see `utils::abi::event_types`. Subscribe to them with a
+    /// filter from `utils::abi::build_event_filter_for_package`.
+    pub async fn get_move_event_types(
+        &self,
+        package_id: ObjectID,
+    ) -> Result<Vec<MoveEventTypeInfo>> {
+        let modules = self.get_package_abi(package_id).await?;
+
+        Ok(event_types(&modules))
+    }
+
+    /// Signature of `module::function` in `package`
+    ///
+    /// Fails with `ServiceError::Service` if the module or function does not
+    /// exist or is not visible in the package ABI.
+    pub async fn get_move_call_target_info(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+    ) -> Result<MoveCallInfo> {
+        let modules = self.get_package_abi(package).await?;
+
+        find_function(&modules, module, function)
+            .map(move_call_info)
+            .ok_or_else(|| {
+                ServiceError::Service(format!(
+                    "Function {}::{}::{} not found",
+                    package, module, function
+                ))
+            })
+    }
+
+    /// Returns `(epoch, commission_rate_bps)` pairs for a validator, newest first
+    ///
+    /// Read from the `ValidatorEpochInfoEventV2` events the system emits for
+    /// every active validator at each epoch change, so epochs in which the
+    /// validator was not active are simply absent.
+    pub async fn get_validator_commission_history(
+        &self,
+        validator: SuiAddress,
+        epochs: u32,
+    ) -> Result<Vec<(u64, u64)>> {
+        let event_type = parse_sui_struct_tag(VALIDATOR_EPOCH_INFO_EVENT)
+            .map_err(|e| ServiceError::Service(format!("Invalid event type: {}", e)))?;
+
+        let mut history = Vec::new();
+        let mut cursor = None;
+        let mut latest_epoch = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let event_type = event_type.clone();
+                    async move {
+                        node.event_api()
+                            .query_events(
+                                EventFilter::MoveEventType(event_type),
+                                cursor,
+                                None,
+                                true,
+                            )
+                            .await
+                    }
+                })
+                .await?;
+
+            for event in &page.data {
+                let json = &event.parsed_json;
+                let epoch = json_u64(&json["epoch"]).ok_or_else(|| {
+                    ServiceError::InvalidResponse("Validator event has no epoch".to_string())
+                })?;
+                let latest = *latest_epoch.get_or_insert(epoch);
+
+                if epoch + u64::from(epochs) <= latest {
+                    return Ok(history);
+                }
+
+                if json["validator_address"].as_str() == Some(validator.to_string().as_str()) {
+                    let commission_rate = json_u64(&json["commission_rate"]).ok_or_else(|| {
+                        ServiceError::InvalidResponse(
+                            "Validator event has no commission rate".to_string(),
+                        )
+                    })?;
+                    history.push((epoch, commission_rate));
+                }
+            }
+
+            if !page.has_next_page {
+                return Ok(history);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Summaries of epochs `from_epoch` to `to_epoch`, inclusive and oldest first
+    ///
+    /// Past epochs are read from the `SystemEpochInfoEvent` the system emits
+    /// when an epoch ends, whose timestamp is also the start of the next
+    /// one; the current epoch comes from the live system state. `to_epoch`
+    /// is capped at the current epoch. Fails with
+    /// `ServiceError::InvalidResponse` if the node has pruned the events of
+    /// an epoch in the range.
+    pub async fn get_epochs_range(&self, from_epoch: u64, to_epoch: u64) -> Result<Vec<EpochInfo>> {
+        if from_epoch > to_epoch {
+            return Err(ServiceError::Service(format!(
+                "Invalid epoch range {}..={}",
+                from_epoch, to_epoch
+            )));
+        }
+
+        let state = self.get_system_state().await?;
+        let to_epoch = to_epoch.min(state.epoch);
+
+        let event_type = parse_sui_struct_tag(SYSTEM_EPOCH_INFO_EVENT)
+            .map_err(|e| ServiceError::Service(format!("Invalid event type: {}", e)))?;
+
+        // Epoch ends keyed by epoch, including the one before the range,
+        // whose end is the range's first start
+        let mut ends: HashMap<u64, (u64, serde_json::Value)> = HashMap::new();
+        let mut cursor = None;
+
+        'pages: loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let event_type = event_type.clone();
+                    async move {
+                        node.event_api()
+                            .query_events(
+                                EventFilter::MoveEventType(event_type),
+                                cursor,
+                                None,
+                                true,
+                            )
+                            .await
+                    }
+                })
+                .await?;
+
+            for event in page.data {
+                let epoch = json_u64(&event.parsed_json["epoch"]).ok_or_else(|| {
+                    ServiceError::InvalidResponse("Epoch event has no epoch".to_string())
+                })?;
+
+                if epoch + 1 < from_epoch {
+                    break 'pages;
+                }
+
+                if let Some(timestamp_ms) = event.timestamp_ms {
+                    ends.insert(epoch, (timestamp_ms, event.parsed_json));
+                }
+            }
+
+            if !page.has_next_page {
+                break;
+            }
+
+            cursor = page.next_cursor;
+        }
+
+        let missing = |epoch: u64| {
+            ServiceError::InvalidResponse(format!("No epoch info for epoch {}", epoch))
+        };
+
+        let mut epochs = Vec::new();
+
+        for epoch in from_epoch..=to_epoch {
+            let start_timestamp_ms = if epoch == state.epoch {
+                state.epoch_start_timestamp_ms
+            } else if epoch == 0 {
+                self.get_checkpoint(CheckpointId::SequenceNumber(0))
+                    .await?
+                    .timestamp_ms
+            } else {
+                ends.get(&(epoch - 1)).ok_or_else(|| missing(epoch - 1))?.0
+            };
+
+            if epoch == state.epoch {
+                epochs.push(EpochInfo {
+                    epoch,
+                    start_timestamp_ms,
+                    end_timestamp_ms: None,
+                    total_stake: state.total_stake,
+                    storage_fund: state.storage_fund_total_object_storage_rebates
+                        + state.storage_fund_non_refundable_balance,
+                    reference_gas_price: state.reference_gas_price,
+                });
+                continue;
+            }
+
+            let (end_timestamp_ms, json) = ends.get(&epoch).ok_or_else(|| missing(epoch))?;
+            let field = |name: &str| {
+                json_u64(&json[name]).ok_or_else(|| {
+                    ServiceError::InvalidResponse(format!("Epoch event has no {}", name))
+                })
+            };
+
+            epochs.push(EpochInfo {
+                epoch,
+                start_timestamp_ms,
+                end_timestamp_ms: Some(*end_timestamp_ms),
+                total_stake: field("total_stake")?,
+                storage_fund: field("storage_fund_balance")?,
+                reference_gas_price: field("reference_gas_price")?,
+            });
+        }
+
+        Ok(epochs)
+    }
+
+    /// Move type of `object_id`, or `None` if it is a package
+    pub async fn get_object_type_tag(&self, object_id: ObjectID) -> Result<Option<StructTag>> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_type())
+            .await?;
+
+        let object_type = response
+            .data
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", object_id))
+            })?
+            .object_type()
+            .map_err(|e| ServiceError::InvalidResponse(format!("Object has no type: {}", e)))?;
+
+        match object_type {
+            ObjectType::Package => Ok(None),
+            ObjectType::Struct(_) => StructTag::from_str(&object_type.to_string())
+                .map(Some)
+                .map_err(|e| ServiceError::InvalidResponse(format!("Invalid object type: {}", e))),
+        }
+    }
+
+    /// Digest of the transaction that last mutated `object_id`
+    ///
+    /// Following `previous_transaction` from transaction to transaction
+    /// walks back through the object's mutation history.
+    pub async fn get_object_last_transaction(&self, object_id: ObjectID) -> Result<String> {
+        let response = self
+            .get_object_with_options(
+                object_id,
+                SuiObjectDataOptions::new().with_previous_transaction(),
+            )
+            .await?;
+
+        let object = response.data.ok_or_else(|| {
+            ServiceError::InvalidResponse(format!("Object {} not found", object_id))
+        })?;
+
+        object
+            .previous_transaction
+            .map(|digest| digest.to_string())
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!(
+                    "Object {} has no previous transaction",
+                    object_id
+                ))
+            })
+    }
+
+    /// Signs `tx` with the ephemeral key and executes it
+    ///
+    /// The signer is the address of the ephemeral key created by
+    /// `create_zkp_payload`; the transaction sender must be the zkLogin
+    /// account. Fails with `ServiceError::Service` if execution aborts.
+    pub async fn sign_and_execute_transaction(
+        &self,
+        tx: TransactionData,
+        zk_login_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let signer = self.ephemeral_address()?;
+
+        let transaction = self
+            .sign_transaction(tx, signer, zk_login_inputs, max_epoch, path)
+            .await?;
+
+        self.execute_transaction(transaction).await
+    }
+
+    /// Dev-inspects a Move call as `sender` without executing it
+    ///
+    /// `args` are resolved against the function signature the way the `sui
+    /// client call` CLI does, so objects can be given by ID. No gas is
+    /// charged; `gas_budget` only caps the simulated execution. Aborts are
+    /// reported in the result rather than as an error, see
+    /// `is_simulation_successful`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn dry_run_move_call(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<SuiJsonValue>,
+        sender: SuiAddress,
+        gas_budget: Option<u64>,
+    ) -> Result<MoveCallSimulation> {
+        let tx_kind = self
+            .get_node()
+            .transaction_builder()
+            .move_call_tx_kind(
+                package,
+                module,
+                function,
+                type_args.into_iter().map(SuiTypeTag::from).collect(),
+                args,
+            )
+            .await
+            .map_err(|e| ServiceError::Service(format!("Failed to build Move call: {}", e)))?;
+
+        self.simulate_transaction(sender, tx_kind, gas_budget).await
+    }
+
+    /// Calls a single Move function from `account` and executes it
+    ///
+    /// `args` are passed as-is, so objects must already be resolved to
+    /// `CallArg::Object` and pure values BCS-encoded. When move call
+    /// validation is enabled, `args` are also checked with
+    /// `validate_call_args` first.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn call_move_function(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let tx_data = self
+            .move_call_transaction(
+                package, module, function, type_args, args, gas_budget, &account,
+            )
+            .await?;
+
+        self.sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    /// Like `call_move_function`, but dev-inspects the call first
+    ///
+    /// Nothing is signed if the call would fail; the failure is returned as
+    /// `ServiceError::DryRunFailed`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn dry_run_and_call_move_function(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let tx_data = self
+            .move_call_transaction(
+                package, module, function, type_args, args, gas_budget, &account,
+            )
+            .await?;
+
+        let simulation = self
+            .simulate_transaction(tx_data.sender(), tx_data.kind().clone(), Some(gas_budget))
+            .await?;
+
+        if !is_simulation_successful(&simulation) {
+            return Err(ServiceError::DryRunFailed(format!(
+                "{}::{}::{}: {}",
+                package,
+                module,
+                function,
+                simulation.error.unwrap_or(simulation.effects_status)
+            )));
+        }
+
+        self.sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    /// Transaction of a single Move call from `account`, validated if enabled
+    #[allow(clippy::too_many_arguments)]
+    async fn move_call_transaction(
+        &self,
+        package: ObjectID,
+        module: &str,
+        function: &str,
+        type_args: Vec<TypeTag>,
+        args: Vec<CallArg>,
+        gas_budget: u64,
+        account: &AccountResponse,
+    ) -> Result<TransactionData> {
+        let sender = account_address(account)?;
+
+        if self.validate_move_calls {
+            let info = self
+                .get_move_call_target_info(package, module, function)
+                .await?;
+            validate_call_args(&info, &args)?;
+        }
+
+        self.build_programmable_batch(sender, gas_budget, |builder| {
+            builder
+                .move_call(
+                    package,
+                    Identifier::new(module).map_err(ptb_error)?,
+                    Identifier::new(function).map_err(ptb_error)?,
+                    type_args,
+                    args,
+                )
+                .map_err(ptb_error)
+        })
+        .await
+    }
+
+    /// Dev-inspects `tx_kind` and collects what the last command returned
+    async fn simulate_transaction(
+        &self,
+        sender: SuiAddress,
+        tx_kind: TransactionKind,
+        gas_budget: Option<u64>,
+    ) -> Result<MoveCallSimulation> {
+        let args = DevInspectArgs {
+            gas_budget: gas_budget.map(BigInt::from),
+            ..Default::default()
+        };
+
+        let results = self
+            .get_node()
+            .read_api()
+            .dev_inspect_transaction_block(sender, tx_kind, None, None, Some(args))
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to inspect transaction: {}", e)))?;
+
+        let return_values = results
+            .results
+            .unwrap_or_default()
+            .pop()
+            .map(|result| result.return_values)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(bytes, type_tag)| {
+                serde_json::json!({ "type": type_tag, "bcs": Base64::encode(bytes) })
+            })
+            .collect();
+
+        let (effects_status, failure) = match results.effects.status() {
+            SuiExecutionStatus::Success => ("success".to_string(), None),
+            SuiExecutionStatus::Failure { error } => {
+                (format!("failure: {}", error), Some(error.clone()))
+            }
+        };
+
+        Ok(MoveCallSimulation {
+            return_values,
+            gas_used_mist: results.effects.gas_cost_summary().gas_used(),
+            effects_status,
+            events: results.events.data,
+            error: results.error.or(failure),
+        })
+    }
+
+    /// Submits a signed transaction and waits for local execution
+    pub async fn execute_transaction(
+        &self,
+        transaction: Transaction,
+    ) -> Result<SuiTransactionBlockResponse> {
+        let response = self
+            .get_node()
+            .quorum_driver_api()
+            .execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::full_content(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to execute transaction: {}", e)))?;
+
+        if let Some(effects) = &response.effects
+            && let SuiExecutionStatus::Failure { error } = effects.status()
+        {
+            return Err(ServiceError::Service(format!(
+                "Transaction {} failed: {}",
+                response.digest, error
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Lists every coin of `coin_type` owned by `owner`, following pagination
+    ///
+    /// `None` lists SUI coins.
+    pub async fn get_coins(&self, owner: SuiAddress, coin_type: Option<&str>) -> Result<Vec<Coin>> {
+        let mut coins = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let coin_type = coin_type.map(str::to_string);
+                    let cursor = cursor.clone();
+                    async move {
+                        node.coin_read_api()
+                            .get_coins(owner, coin_type, cursor, None)
+                            .await
+                    }
+                })
+                .await?;
+
+            coins.extend(page.data);
+
+            if !page.has_next_page {
+                return Ok(coins);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Balances of `owner` for each of `coin_types`, fetched concurrently
+    ///
+    /// Keys are the coin types as passed in.
+    pub async fn batch_get_balances(
+        &self,
+        owner: SuiAddress,
+        coin_types: Vec<&str>,
+    ) -> Result<HashMap<String, u64>> {
+        let node = self.services.get_resilient_node();
+
+        let balances = join_all(coin_types.iter().map(|coin_type| {
+            node.call(move |node| async move {
+                node.coin_read_api()
+                    .get_balance(owner, Some(coin_type.to_string()))
+                    .await
+            })
+        }))
+        .await;
+
+        coin_types
+            .into_iter()
+            .zip(balances)
+            .map(|(coin_type, balance)| {
+                let balance = balance.map_err(|e| {
+                    ServiceError::Network(format!("Failed to get {} balance: {}", coin_type, e))
+                })?;
+
+                Ok((
+                    coin_type.to_string(),
+                    u64::try_from(balance.total_balance).unwrap_or(u64::MAX),
+                ))
+            })
+            .collect()
+    }
+
+    /// Every coin balance of `owner`, served from cache while fresh
+    ///
+    /// Cached portfolios are kept for the TTL set with `set_portfolio_ttl`,
+    /// `DEFAULT_PORTFOLIO_TTL` unless changed.
+    pub async fn get_portfolio(&self, owner: SuiAddress) -> Result<Portfolio> {
+        if let Some(portfolio) = self
+            .portfolio_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&owner).cloned())
+            && !portfolio.is_stale(self.portfolio_ttl)
+        {
+            return Ok(portfolio);
+        }
+
+        let balances = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move { node.coin_read_api().get_all_balances(owner).await })
+            .await?;
+
+        let portfolio = Portfolio {
+            balances: balances
+                .into_iter()
+                .map(|balance| {
+                    (
+                        balance.coin_type,
+                        u64::try_from(balance.total_balance).unwrap_or(u64::MAX),
+                    )
+                })
+                .collect(),
+            owner,
+            fetched_at: Instant::now(),
+        };
+
+        if let Ok(mut cache) = self.portfolio_cache.write() {
+            cache.insert(owner, portfolio.clone());
+        }
+
+        Ok(portfolio)
+    }
+
+    /// Balances, NFTs and stakes of the account of the stored JWT
+    ///
+    /// Once the address is known from Enoki, balances, owned NFTs and stake
+    /// positions are fetched concurrently: this fires several Sui node
+    /// requests at once, and NFTs are paged through, so the first call can
+    /// be slow for accounts owning many objects. Snapshots are then served
+    /// from cache for the TTL set with `set_portfolio_ttl`, and the address
+    /// is remembered for the JWT, so a cached snapshot costs no request.
+    pub async fn get_full_account_state(&self) -> Result<AccountState> {
+        let address = self.jwt_address().await?;
+
+        if let Some(state) = self
+            .account_state_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&address).cloned())
+            && !state.is_stale(self.portfolio_ttl)
+        {
+            return Ok(state);
+        }
+
+        let (portfolio, nfts, stake_positions) = tokio::try_join!(
+            self.get_portfolio(address),
+            self.get_owned_nfts(address),
+            self.get_user_stake_positions(address),
+        )?;
+
+        let sui_balance = portfolio
+            .balances
+            .iter()
+            .filter(|(coin_type, _)| {
+                parse_sui_struct_tag(coin_type).is_ok_and(|tag| tag == GAS::type_())
+            })
+            .fold(0u64, |total, (_, balance)| total.saturating_add(*balance));
+        let pending_rewards_mist = stake_positions.iter().fold(0u64, |total, position| {
+            total.saturating_add(position.estimated_reward)
+        });
+
+        let state = AccountState {
+            address,
+            sui_balance,
+            all_balances: portfolio.balances,
+            nfts,
+            stake_positions,
+            pending_rewards_mist,
+            fetched_at: Instant::now(),
+        };
+
+        if let Ok(mut cache) = self.account_state_cache.write() {
+            cache.insert(address, state.clone());
+        }
+
+        Ok(state)
+    }
+
+    /// Address of the stored JWT, asking Enoki only the first time
+    async fn jwt_address(&self) -> Result<SuiAddress> {
+        let jwt = self.services.get_jwt();
+
+        if let Some(address) = self.known_address.read().ok().and_then(|known| {
+            known
+                .as_ref()
+                .filter(|(known_jwt, _)| known_jwt == jwt)
+                .map(|(_, address)| *address)
+        }) {
+            return Ok(address);
+        }
+
+        let address = account_address(&self.get_address().await?)?;
+
+        if let Ok(mut known) = self.known_address.write() {
+            *known = Some((jwt.to_string(), address));
+        }
+
+        Ok(address)
+    }
+
+    /// Total minted supply of `coin_type`, in base units
+    pub async fn get_coin_total_supply(&self, coin_type: &str) -> Result<u64> {
+        let supply = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move { node.coin_read_api().get_total_supply(coin_type).await })
+            .await?;
+
+        Ok(supply.value)
+    }
+
+    /// The `TreasuryCap<coin_type>` owned by `publisher`, if it still holds it
+    ///
+    /// `None` if the cap was transferred, frozen or wrapped in another object.
+    pub async fn find_treasury_cap(
+        &self,
+        publisher: SuiAddress,
+        coin_type: &str,
+    ) -> Result<Option<ObjectID>> {
+        let cap_type = parse_sui_struct_tag(&format!(
+            "{}<{}>",
+            TREASURY_CAP_TYPE,
+            coin_type_to_move_type(coin_type)
+        ))
+        .map_err(|e| ServiceError::Service(format!("Invalid coin type {}: {}", coin_type, e)))?;
+
+        let objects = self
+            .get_owned_objects(
+                publisher,
+                Some(SuiObjectDataFilter::StructType(cap_type)),
+                SuiObjectDataOptions::new(),
+            )
+            .await?;
+
+        Ok(objects.first().map(|object| object.object_id))
+    }
+
+    /// The shared `TokenPolicy<policy_type>` of a closed-loop token, if one was created
+    ///
+    /// Found through the `TokenPolicyCreated` event emitted when the policy
+    /// is shared, so policies created but never shared are not returned.
+    pub async fn get_regulated_coin_policy(&self, policy_type: &str) -> Result<Option<ObjectID>> {
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}<{}>",
+            TOKEN_POLICY_CREATED_EVENT,
+            coin_type_to_move_type(policy_type)
+        ))
+        .map_err(|e| ServiceError::Service(format!("Invalid event type: {}", e)))?;
+
+        let page = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let event_type = event_type.clone();
+                async move {
+                    node.event_api()
+                        .query_events(EventFilter::MoveEventType(event_type), None, Some(1), false)
+                        .await
+                }
+            })
+            .await?;
+
+        page.data
+            .first()
+            .map(|event| {
+                event.parsed_json["id"]
+                    .as_str()
+                    .map(parse_object_id)
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse("Token policy event has no id".to_string())
+                    })?
+            })
+            .transpose()
+    }
+
+    /// Name, symbol and decimals of `coin_type`
+    ///
+    /// Fails with `ServiceError::Service` if the coin type has no
+    /// `CoinMetadata` object.
+    pub async fn get_token_metadata(&self, coin_type: &str) -> Result<CoinMetadata> {
+        self.find_token_metadata(coin_type)
+            .await?
+            .ok_or_else(|| ServiceError::Service(format!("No coin metadata for {}", coin_type)))
+    }
+
+    /// Metadata of every coin type `owner` holds, keyed by coin type
+    ///
+    /// Coin types are taken from `get_portfolio`. Types without a
+    /// `CoinMetadata` object are left out.
+    pub async fn get_all_coin_metadata(
+        &self,
+        owner: SuiAddress,
+    ) -> Result<HashMap<String, CoinMetadata>> {
+        let portfolio = self.get_portfolio(owner).await?;
+
+        let metadata = join_all(
+            portfolio
+                .balances
+                .keys()
+                .map(|coin_type| self.find_token_metadata(coin_type)),
+        )
+        .await;
+
+        portfolio
+            .balances
+            .into_keys()
+            .zip(metadata)
+            .filter_map(|(coin_type, metadata)| match metadata {
+                Ok(Some(metadata)) => Some(Ok((coin_type, metadata))),
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    async fn find_token_metadata(&self, coin_type: &str) -> Result<Option<CoinMetadata>> {
+        let metadata = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.coin_read_api()
+                    .get_coin_metadata(coin_type.to_string())
+                    .await
+            })
+            .await?;
+
+        Ok(metadata.map(|metadata| CoinMetadata {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            decimals: metadata.decimals,
+            description: metadata.description,
+            icon_url: metadata.icon_url,
+        }))
+    }
+
+    /// Value of the portfolio of `owner` in MIST
+    ///
+    /// Only SUI is counted for now; other coins would need a price source.
+    pub async fn get_total_portfolio_value_mist(&self, owner: SuiAddress) -> Result<u64> {
+        let portfolio = self.get_portfolio(owner).await?;
+
+        Ok(portfolio
+            .balances
+            .iter()
+            .filter(|(coin_type, _)| {
+                parse_sui_struct_tag(coin_type).is_ok_and(|tag| tag == GAS::type_())
+            })
+            .fold(0u64, |total, (_, balance)| total.saturating_add(*balance)))
+    }
+
+    /// Largest gas budget `address` can set without being left with no SUI
+    ///
+    /// The SUI balance minus a margin of `MIN_GAS_UNITS` at the reference gas
+    /// price, which keeps enough behind to pay for one more transaction.
+    pub async fn get_available_gas_budget(&self, address: SuiAddress) -> Result<u64> {
+        let balance = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move { node.coin_read_api().get_balance(address, None).await })
+            .await?;
+
+        let gas_price = self.reference_gas_price().await?;
+        let margin = gas_price.saturating_mul(services::MIN_GAS_UNITS);
+        let total = u64::try_from(balance.total_balance).unwrap_or(u64::MAX);
+
+        Ok(total.saturating_sub(margin))
+    }
+
+    /// Merges `coins` into the first one and returns its updated reference
+    ///
+    /// SUI coins are merged by using all of them as gas payment, which the
+    /// protocol smashes into the first coin, so the gas used is deducted
+    /// from the merged balance. Other coin types are merged with a
+    /// `MergeCoins` command paid for from the sender's SUI.
+    pub async fn consolidate_coins(
+        &mut self,
+        coins: Vec<Coin>,
+        account: &AccountResponse,
+        zk_login_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<ObjectRef> {
+        let primary = coins
+            .first()
+            .ok_or_else(|| ServiceError::Service("No coins to consolidate".to_string()))?;
+
+        if coins.len() == 1 {
+            return Ok(primary.object_ref());
+        }
+
+        let sender = account_address(account)?;
+        let primary_id = primary.coin_object_id;
+        let is_sui = parse_sui_struct_tag(&primary.coin_type).is_ok_and(|tag| tag == GAS::type_());
+
+        let tx_data = if is_sui {
+            let gas_price = self.reference_gas_price().await?;
+
+            TransactionData::new_programmable(
+                sender,
+                coins.iter().map(Coin::object_ref).collect(),
+                ProgrammableTransactionBuilder::new().finish(),
+                CONSOLIDATE_GAS_BUDGET,
+                gas_price,
+            )
+        } else {
+            let mut builder = ProgrammableTransactionBuilder::new();
+
+            let mut arguments = coins
+                .iter()
+                .map(|coin| builder.obj(ObjectArg::ImmOrOwnedObject(coin.object_ref())))
+                .collect::<std::result::Result<Vec<Argument>, _>>()
+                .map_err(|e| ServiceError::Service(format!("Failed to add coin input: {}", e)))?;
+
+            let primary_arg = arguments.remove(0);
+            builder.command(Command::MergeCoins(primary_arg, arguments));
+
+            self.programmable_transaction_data(sender, builder.finish(), CONSOLIDATE_GAS_BUDGET)
+                .await?
+        };
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_login_inputs, max_epoch, path)
+            .await?;
+
+        let effects = response.effects.ok_or_else(|| {
+            ServiceError::InvalidResponse("Transaction response has no effects".to_string())
+        })?;
+
+        effects
+            .mutated()
+            .iter()
+            .find(|object| object.reference.object_id == primary_id)
+            .map(|object| object.reference.to_object_ref())
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Merged coin not found in effects".to_string())
+            })
+    }
+
+    /// Returns a coin holding at least `budget_mist`, merging coins if needed
+    ///
+    /// Coins are picked largest first. When no single coin covers the budget,
+    /// the selected coins are merged in a preliminary transaction signed with
+    /// the zkLogin credentials, and the merged coin is returned. Merging SUI
+    /// coins pays its gas out of the merged coin, so they must then cover
+    /// `budget_mist` plus that transaction's budget.
+    pub async fn get_coins_for_gas(
+        &mut self,
+        budget_mist: u64,
+        coin_type: Option<&str>,
+        account: &AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<ObjectRef> {
+        let owner = account_address(account)?;
+        let coins = self.get_coins(owner, coin_type).await?;
+        let available = aggregate_coins(&coins).1;
+        let is_sui = coin_type.is_none_or(|coin_type| {
+            parse_sui_struct_tag(coin_type).is_ok_and(|tag| tag == GAS::type_())
+        });
+
+        let mut selected = select_coins(&coins, budget_mist, CoinSelectionStrategy::Largest)
+            .ok_or(ServiceError::InsufficientGas {
+                available,
+                required: budget_mist,
+            })?;
+
+        if is_sui && selected.len() > 1 {
+            let required = budget_mist.saturating_add(CONSOLIDATE_GAS_BUDGET);
+
+            selected = select_coins(&coins, required, CoinSelectionStrategy::Largest).ok_or(
+                ServiceError::InsufficientGas {
+                    available,
+                    required,
+                },
+            )?;
+        }
+
+        self.consolidate_coins(selected, account, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    /// Returns a SUI coin holding at least `min_balance_mist` to pay gas with
+    ///
+    /// The largest coin is returned if it is enough. Otherwise the coins are
+    /// merged first through `consolidate_coins`, whose own gas comes out of
+    /// the merged coin, so they must cover `min_balance_mist` plus the
+    /// merge's gas budget. Fails with `ServiceError::InsufficientGas` if the
+    /// account does not hold enough SUI.
+    pub async fn get_gas_coin(
+        &mut self,
+        min_balance_mist: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<ObjectRef> {
+        let owner = account_address(&account)?;
+        let coins = self.get_coins(owner, None).await?;
+
+        match select_gas_coin_strategy(&coins, min_balance_mist) {
+            CoinSelectionResult::Single(coin) => Ok(coin.object_ref()),
+            CoinSelectionResult::Merge(_) => {
+                let required = min_balance_mist.saturating_add(CONSOLIDATE_GAS_BUDGET);
+                let selected = select_coins(&coins, required, CoinSelectionStrategy::Merge).ok_or(
+                    ServiceError::InsufficientGas {
+                        available: aggregate_coins(&coins).1,
+                        required,
+                    },
+                )?;
+
+                self.consolidate_coins(selected, &account, zk_inputs, max_epoch, path)
+                    .await
+            }
+            CoinSelectionResult::Insufficient { available } => Err(ServiceError::InsufficientGas {
+                available,
+                required: min_balance_mist,
+            }),
+        }
+    }
+
+    /// Lists the `0x2::package::Publisher` objects owned by `address`
+    ///
+    /// A publisher proves authority over a package and is required to
+    /// create `Display` templates and transfer policies for its types.
+    pub async fn get_owned_publisher_caps(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Vec<PublisherCapInfo>> {
+        let publisher_type = parse_sui_struct_tag(PUBLISHER_TYPE)
+            .map_err(|e| ServiceError::Service(format!("Invalid publisher type: {}", e)))?;
+
+        let objects = self
+            .get_owned_objects(
+                address,
+                Some(SuiObjectDataFilter::StructType(publisher_type)),
+                SuiObjectDataOptions::new().with_content(),
+            )
+            .await?;
+
+        objects
+            .iter()
+            .map(|object| {
+                let package = move_object_fields(object)
+                    .and_then(|fields| fields["package"].as_str().map(parse_object_id))
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse("Publisher has no package field".to_string())
+                    })??;
+
+                Ok(PublisherCapInfo {
+                    cap_id: object.object_id,
+                    package,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds a transaction creating a `0x2::display::Display<T>` for `type_str`
+    ///
+    /// The display is created from `fields` with `display::new_with_fields`,
+    /// its version is bumped so indexers pick it up, and it is transferred to
+    /// `owner`. Later edits go through the returned `Display` object.
+    pub async fn build_set_display_transaction(
+        &self,
+        owner: SuiAddress,
+        publisher_cap_id: ObjectID,
+        type_str: &str,
+        fields: HashMap<String, String>,
+        gas_budget: u64,
+    ) -> Result<TransactionData> {
+        let type_tag = parse_type_tag(type_str)?;
+        let publisher_ref = self.get_object_ref(publisher_cap_id).await?;
+
+        let mut fields: Vec<(String, String)> = fields.into_iter().collect();
+        fields.sort();
+        let (keys, values): (Vec<String>, Vec<String>) = fields.into_iter().unzip();
+
+        self.build_programmable_batch(owner, gas_budget, |builder| {
+            let publisher = builder
+                .obj(ObjectArg::ImmOrOwnedObject(publisher_ref))
+                .map_err(ptb_error)?;
+            let keys = builder.pure(keys).map_err(ptb_error)?;
+            let values = builder.pure(values).map_err(ptb_error)?;
+
+            let display = builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("display").map_err(ptb_error)?,
+                Identifier::new("new_with_fields").map_err(ptb_error)?,
+                vec![type_tag.clone()],
+                vec![publisher, keys, values],
+            );
+
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("display").map_err(ptb_error)?,
+                Identifier::new("update_version").map_err(ptb_error)?,
+                vec![type_tag],
+                vec![display],
+            );
+
+            builder.transfer_arg(owner, display);
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Builds a transaction creating a `TransferPolicy<T>` with a royalty rule
+    ///
+    /// The policy is shared so marketplaces can confirm purchases against it,
+    /// and the `TransferPolicyCap<T>` is sent to `royalty_recipient`, who can
+    /// withdraw the collected royalties. Uses Mysten's Kiosk `royalty_rule`,
+    /// which is only deployed on mainnet and testnet.
+    pub async fn build_create_transfer_policy_transaction(
+        &self,
+        owner: SuiAddress,
+        publisher_cap_id: ObjectID,
+        type_str: &str,
+        royalty_bps: u64,
+        royalty_recipient: SuiAddress,
+        gas_budget: u64,
+    ) -> Result<TransactionData> {
+        let royalty_bps = u16::try_from(royalty_bps)
+            .ok()
+            .filter(|bps| *bps <= 10_000)
+            .ok_or_else(|| {
+                ServiceError::Service(format!("Royalty of {} bps exceeds 100%", royalty_bps))
+            })?;
+
+        let rules_package = self.kiosk_rules_package()?;
+        let type_tag = parse_type_tag(type_str)?;
+        let publisher_ref = self.get_object_ref(publisher_cap_id).await?;
+
+        self.build_programmable_batch(owner, gas_budget, |builder| {
+            let publisher = builder
+                .obj(ObjectArg::ImmOrOwnedObject(publisher_ref))
+                .map_err(ptb_error)?;
+
+            let created = builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("transfer_policy").map_err(ptb_error)?,
+                Identifier::new("new").map_err(ptb_error)?,
+                vec![type_tag.clone()],
+                vec![publisher],
+            );
+            let policy = nested_result(created, 0)?;
+            let policy_cap = nested_result(created, 1)?;
+
+            let amount_bp = builder.pure(royalty_bps).map_err(ptb_error)?;
+            let min_amount = builder.pure(0u64).map_err(ptb_error)?;
+
+            builder.programmable_move_call(
+                rules_package,
+                Identifier::new("royalty_rule").map_err(ptb_error)?,
+                Identifier::new("add").map_err(ptb_error)?,
+                vec![type_tag.clone()],
+                vec![policy, policy_cap, amount_bp, min_amount],
+            );
+
+            builder.programmable_move_call(
+                SUI_FRAMEWORK_PACKAGE_ID,
+                Identifier::new("transfer").map_err(ptb_error)?,
+                Identifier::new("public_share_object").map_err(ptb_error)?,
+                vec![transfer_policy_type(&type_tag)?],
+                vec![policy],
+            );
+
+            builder.transfer_arg(royalty_recipient, policy_cap);
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Builds a transaction withdrawing a `StakedSui` back to `sender`
+    ///
+    /// Fails before building if `staked_sui_id` is not a
+    /// `0x3::staking_pool::StakedSui`. Rewards are paid out together with
+    /// the principal.
+    pub async fn build_request_withdraw_stake_transaction(
+        &self,
+        sender: SuiAddress,
+        staked_sui_id: ObjectID,
+        gas_budget: u64,
+    ) -> Result<TransactionData> {
+        let response = self
+            .get_object_with_options(staked_sui_id, SuiObjectDataOptions::new().with_type())
+            .await?;
+
+        let object_type = response
+            .data
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", staked_sui_id))
+            })?
+            .object_type()
+            .map_err(|e| ServiceError::InvalidResponse(format!("Object has no type: {}", e)))?;
+
+        if !parse_sui_struct_tag(&object_type.to_string())
+            .is_ok_and(|tag| tag == StakedSui::type_())
+        {
+            return Err(ServiceError::Service(format!(
+                "{} is a {}, not a StakedSui",
+                staked_sui_id, object_type
+            )));
+        }
+
+        self.get_node()
+            .transaction_builder()
+            .request_withdraw_stake(sender, staked_sui_id, None, gas_budget)
+            .await
+            .map_err(|e| {
+                ServiceError::Service(format!("Failed to build withdraw stake transaction: {}", e))
+            })
+    }
+
+    /// Withdraws `staked_sui` and returns the digest with the SUI received
+    ///
+    /// Sui pays staking rewards together with the principal when the stake
+    /// is withdrawn, so this ends the position rather than skimming its
+    /// rewards. The amount, in MIST, is read from a dry run of
+    /// `sui_system::request_withdraw_stake_non_entry` made just before the
+    /// withdrawal is executed; it may differ slightly if the epoch changes
+    /// in between.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn claim_staking_rewards(
+        &mut self,
+        staked_sui: ObjectRef,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<(String, u64)> {
+        let sender = account_address(&account)?;
+        let received = self.estimate_withdrawn_stake(sender, staked_sui).await?;
+
+        let tx_data = self
+            .build_request_withdraw_stake_transaction(sender, staked_sui.0, gas_budget)
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok((response.digest.to_string(), received))
+    }
+
+    /// `claim_staking_rewards` for every stake position of `staker`
+    ///
+    /// Positions are withdrawn one transaction at a time, in the order
+    /// `get_user_stake_positions` lists them; the first failure stops the
+    /// run, leaving the remaining positions staked.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn claim_all_staking_rewards(
+        &mut self,
+        staker: SuiAddress,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<Vec<(String, u64)>> {
+        let positions = self.get_user_stake_positions(staker).await?;
+        let mut claims = Vec::with_capacity(positions.len());
+
+        for position in positions {
+            let staked_sui = self.get_object_ref(position.staked_sui_id).await?;
+
+            claims.push(
+                self.claim_staking_rewards(
+                    staked_sui,
+                    gas_budget,
+                    account.clone(),
+                    zk_inputs.clone(),
+                    max_epoch,
+                    path.clone(),
+                )
+                .await?,
+            );
+        }
+
+        Ok(claims)
+    }
+
+    /// MIST that withdrawing `staked_sui` would pay out, principal included
+    async fn estimate_withdrawn_stake(
+        &self,
+        sender: SuiAddress,
+        staked_sui: ObjectRef,
+    ) -> Result<u64> {
+        let mut builder = ProgrammableTransactionBuilder::new();
+
+        let system_state = builder.obj(ObjectArg::SUI_SYSTEM_MUT).map_err(ptb_error)?;
+        let staked_sui = builder
+            .obj(ObjectArg::ImmOrOwnedObject(staked_sui))
+            .map_err(ptb_error)?;
+
+        builder.programmable_move_call(
+            SUI_SYSTEM_PACKAGE_ID,
+            Identifier::new("sui_system").map_err(ptb_error)?,
+            Identifier::new("request_withdraw_stake_non_entry").map_err(ptb_error)?,
+            vec![],
+            vec![system_state, staked_sui],
+        );
+
+        let results = self
+            .get_node()
+            .read_api()
+            .dev_inspect_transaction_block(
+                sender,
+                TransactionKind::ProgrammableTransaction(builder.finish()),
+                None,
+                None,
+                None,
+            )
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to inspect transaction: {}", e)))?;
+
+        if let Some(error) = results.error {
+            return Err(ServiceError::DryRunFailed(format!(
+                "Stake withdrawal failed: {}",
+                error
+            )));
+        }
+
+        let (bytes, _) = results
+            .results
+            .and_then(|mut results| results.pop())
+            .and_then(|mut result| result.return_values.pop())
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Stake withdrawal returned no balance".to_string())
+            })?;
+
+        // `Balance<SUI>` is a struct holding a single `u64`
+        bcs::from_bytes(&bytes)
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid withdrawn balance: {}", e)))
+    }
+
+    /// Deletes `wrapped_objects` to get their storage deposits back
+    ///
+    /// Sui charges a storage deposit when an object is written and refunds
+    /// it, less a small non-refundable fee, when a transaction deletes the
+    /// object. There is no generic delete: only the module defining a type
+    /// can destroy its objects. Zero-balance `Coin<T>`s are destroyed with
+    /// `coin::destroy_zero`; any other object fails the call with
+    /// `ServiceError::Service` before anything is sent. The rebate is
+    /// added to the gas coin after execution. Returns the digest and the
+    /// transaction's storage rebate in MIST, which includes the gas coin's
+    /// own small rebate.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn reclaim_storage(
+        &mut self,
+        wrapped_objects: Vec<ObjectRef>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<(String, u64)> {
+        let sender = account_address(&account)?;
+        let responses = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let object_ids = wrapped_objects.iter().map(|object| object.0).collect();
+                async move {
+                    node.read_api()
+                        .multi_get_object_with_options(
+                            object_ids,
+                            SuiObjectDataOptions::new().with_type().with_content(),
+                        )
+                        .await
+                }
+            })
+            .await?;
+
+        let mut coin_types = Vec::with_capacity(wrapped_objects.len());
+
+        for (object_ref, response) in wrapped_objects.iter().zip(responses) {
+            let object = response.data.ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", object_ref.0))
+            })?;
+            let coin_type = match &object.type_ {
+                Some(ObjectType::Struct(type_)) => type_.coin_type_maybe(),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                ServiceError::Service(format!(
+                    "Object {} has no generic destructor; only zero-balance coins can be reclaimed",
+                    object_ref.0
+                ))
+            })?;
+
+            let balance = move_object_fields(&object)
+                .and_then(|fields| fields["balance"].as_str().map(str::to_string))
+                .unwrap_or_default();
+            if balance != "0" {
+                return Err(ServiceError::Service(format!(
+                    "Coin {} still holds {} and can't be destroyed",
+                    object_ref.0, balance
+                )));
+            }
+
+            coin_types.push(coin_type);
+        }
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                for (object_ref, coin_type) in wrapped_objects.into_iter().zip(coin_types) {
+                    let coin = builder
+                        .obj(ObjectArg::ImmOrOwnedObject(object_ref))
+                        .map_err(ptb_error)?;
+
+                    builder.programmable_move_call(
+                        SUI_FRAMEWORK_PACKAGE_ID,
+                        Identifier::new("coin").map_err(ptb_error)?,
+                        Identifier::new("destroy_zero").map_err(ptb_error)?,
+                        vec![coin_type],
+                        vec![coin],
+                    );
+                }
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+        let rebate = response
+            .effects
+            .as_ref()
+            .map(|effects| effects.gas_cost_summary().storage_rebate)
+            .unwrap_or_default();
+
+        Ok((response.digest.to_string(), rebate))
+    }
+
+    /// Storage deposits held by `objects`, in MIST
+    ///
+    /// Sums the `storage_rebate` recorded on each object. Deleting them
+    /// refunds slightly less, as the protocol keeps a non-refundable share.
+    pub async fn estimate_storage_rebate(&self, objects: &[ObjectID]) -> Result<u64> {
+        let mut options = SuiObjectDataOptions::new();
+        options.show_storage_rebate = true;
+
+        let responses = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let options = options.clone();
+                async move {
+                    node.read_api()
+                        .multi_get_object_with_options(objects.to_vec(), options)
+                        .await
+                }
+            })
+            .await?;
+
+        objects
+            .iter()
+            .zip(responses)
+            .try_fold(0u64, |total, (object_id, response)| {
+                let rebate = response
+                    .data
+                    .and_then(|object| object.storage_rebate)
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse(format!(
+                            "Object {} has no storage rebate",
+                            object_id
+                        ))
+                    })?;
+
+                Ok(total.saturating_add(rebate))
+            })
+    }
+
+    /// Adds `objects` to `builder` and packs them into a `vector<T>`
+    ///
+    /// Owned and immutable objects are passed by reference, shared ones
+    /// mutably. The returned argument is the result of the `MakeMoveVec`
+    /// command and can be passed to any Move function taking a
+    /// `vector<T>` of objects. All objects must be of the same type.
+    pub async fn build_object_vector_ptb_arg(
+        &self,
+        builder: &mut ProgrammableTransactionBuilder,
+        objects: Vec<ObjectID>,
+    ) -> Result<Argument> {
+        let responses = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let objects = objects.clone();
+                async move {
+                    node.read_api()
+                        .multi_get_object_with_options(
+                            objects,
+                            SuiObjectDataOptions::new().with_owner(),
+                        )
+                        .await
+                }
+            })
+            .await?;
+
+        let elements = objects
+            .into_iter()
+            .zip(responses)
+            .map(|(object_id, response)| {
+                let object = response.data.ok_or_else(|| {
+                    ServiceError::InvalidResponse(format!("Object {} not found", object_id))
+                })?;
+
+                let object_arg = match object.owner {
+                    Some(Owner::Shared {
+                        initial_shared_version,
+                    }) => ObjectArg::SharedObject {
+                        id: object_id,
+                        initial_shared_version,
+                        mutable: true,
+                    },
+                    _ => ObjectArg::ImmOrOwnedObject(object.object_ref()),
+                };
+
+                builder.obj(object_arg).map_err(ptb_error)
+            })
+            .collect::<Result<Vec<Argument>>>()?;
+
+        Ok(builder.command(Command::MakeMoveVec(None, elements)))
+    }
+
+    /// Builds one PTB running `calls` in order, possibly across packages
+    ///
+    /// Calls are numbered by their position in `calls`, so a later call can
+    /// consume an earlier one's output through `MoveCallArg::Result` or
+    /// `MoveCallArg::NestedResult`.
+    pub async fn build_multi_package_ptb(
+        &self,
+        sender: SuiAddress,
+        calls: Vec<MoveCallSpec>,
+        gas_budget: u64,
+    ) -> Result<TransactionData> {
+        self.build_programmable_batch(sender, gas_budget, |builder| {
+            for (index, call) in calls.into_iter().enumerate() {
+                let type_args = call
+                    .type_args
+                    .into_iter()
+                    .map(|type_arg| type_arg.try_into().map_err(ptb_error))
+                    .collect::<Result<Vec<TypeTag>>>()?;
+
+                let args = call
+                    .args
+                    .into_iter()
+                    .map(|arg| match arg {
+                        MoveCallArg::Input(input) => builder.input(input).map_err(ptb_error),
+                        MoveCallArg::Result(command) if usize::from(command) < index => {
+                            Ok(Argument::Result(command))
+                        }
+                        MoveCallArg::NestedResult(command, value)
+                            if usize::from(command) < index =>
+                        {
+                            Ok(Argument::NestedResult(command, value))
+                        }
+                        _ => Err(ServiceError::Service(format!(
+                            "Call {} can only use results of earlier calls",
+                            index
+                        ))),
+                    })
+                    .collect::<Result<Vec<Argument>>>()?;
+
+                builder.programmable_move_call(
+                    call.package,
+                    Identifier::new(call.module).map_err(ptb_error)?,
+                    Identifier::new(call.function).map_err(ptb_error)?,
+                    type_args,
+                    args,
+                );
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    /// Lists the rules of the `TransferPolicy<T>` for `type_str`
+    ///
+    /// The policy is found through the `TransferPolicyCreated<T>` event
+    /// emitted when it was created; if several policies exist for the type
+    /// the first one is used. Each rule's config is read from the
+    /// `RuleKey<Rule>` dynamic field the rule stores on the policy.
+    pub async fn get_transfer_policy_rules(
+        &self,
+        type_str: &str,
+    ) -> Result<Vec<TransferPolicyRule>> {
+        let policy_id = self.find_transfer_policy(type_str).await?;
+
+        let mut field_ids = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| async move {
+                    node.read_api()
+                        .get_dynamic_fields(policy_id, cursor, None)
+                        .await
+                })
+                .await?;
+
+            for field in page.data {
+                if let TypeTag::Struct(key) = &field.name.type_
+                    && key.address == SUI_FRAMEWORK_ADDRESS
+                    && key.module.as_str() == "transfer_policy"
+                    && key.name.as_str() == "RuleKey"
+                    && let Some(rule_type) = key.type_params.first()
+                {
+                    field_ids.push((rule_type.to_canonical_string(true), field.object_id));
+                }
+            }
+
+            if !page.has_next_page {
+                break;
+            }
+
+            cursor = page.next_cursor;
+        }
+
+        let objects = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let field_ids = field_ids.iter().map(|(_, id)| *id).collect();
+                async move {
+                    node.read_api()
+                        .multi_get_object_with_options(
+                            field_ids,
+                            SuiObjectDataOptions::new().with_content(),
+                        )
+                        .await
+                }
+            })
+            .await?;
+
+        field_ids
+            .into_iter()
+            .zip(objects)
+            .map(|((rule_type, _), response)| {
+                let config = response
+                    .data
+                    .as_ref()
+                    .and_then(move_object_fields)
+                    .map(|fields| fields["value"].clone())
+                    .ok_or_else(|| {
+                        ServiceError::InvalidResponse(format!("Rule {} has no config", rule_type))
+                    })?;
+
+                Ok(TransferPolicyRule { rule_type, config })
+            })
+            .collect()
+    }
+
+    /// Dry-runs `tx` to estimate what sponsoring it will cost
+    ///
+    /// Fails with `ServiceError::DryRunFailed` if the transaction would abort.
+    pub async fn estimate_sponsor_cost(
+        &self,
+        tx: &Transaction,
+        sender: SuiAddress,
+    ) -> Result<SponsorCostEstimate> {
+        self.services.estimate_sponsor_cost(tx, sender).await
+    }
+
+    /// Builds a programmable transaction by handing a fresh builder to `f`
+    ///
+    /// The closure adds the commands it needs; gas payment and the reference
+    /// gas price are resolved from the node once the closure returns.
+    pub async fn build_programmable_batch<F>(
+        &self,
+        sender: SuiAddress,
+        gas_budget: u64,
+        f: F,
+    ) -> Result<TransactionData>
+    where
+        F: FnOnce(&mut ProgrammableTransactionBuilder) -> Result<()>,
+    {
+        let mut builder = ProgrammableTransactionBuilder::new();
+
+        f(&mut builder)?;
+
+        self.programmable_transaction_data(sender, builder.finish(), gas_budget)
+            .await
+    }
+
+    /// Protocol parameters of the network, for `version` or the current one
+    ///
+    /// The protocol version can only change on epoch boundaries, so the
+    /// config can be cached until `time_until_epoch_end` elapses.
+    pub async fn get_protocol_config(
+        &self,
+        version: Option<u64>,
+    ) -> Result<ProtocolConfigResponse> {
+        self.services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .get_protocol_config(version.map(Into::into))
+                    .await
+            })
+            .await
+    }
+
+    /// Checks `tx_data` against the current `max_tx_size_bytes`
+    ///
+    /// Only the BCS transaction data is measured; signatures add a few
+    /// hundred bytes more, so stay clear of the limit.
+    pub async fn validate_transaction_size(&self, tx_data: &TransactionData) -> Result<()> {
+        let tx_bytes = bcs::to_bytes(tx_data).map_err(|e| {
+            ServiceError::Service(format!("Failed to serialize transaction: {}", e))
+        })?;
+
+        let config = self.get_protocol_config(None).await?;
+
+        if tx_bytes.len() as u64 > max_tx_size_bytes(&config) {
+            return Err(ServiceError::Service(
+                "Transaction exceeds max size".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Sends SUI to many recipients in one transaction
+    ///
+    /// The amounts are split off the gas coin, so gas coins are selected to
+    /// cover their sum plus `gas_budget`. Fails with `ServiceError::Service`
+    /// if that total overflows a `u64` or the PTB would exceed the
+    /// protocol's `max_programmable_tx_commands`. Returns the transaction
+    /// digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn bulk_transfer_sui(
+        &mut self,
+        transfers: Vec<(SuiAddress, u64)>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let required = transfers
+            .iter()
+            .try_fold(gas_budget, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or_else(|| {
+                ServiceError::Service("Transfer amounts plus gas overflow".to_string())
+            })?;
+
+        // One SplitCoins plus a TransferObjects per recipient
+        self.check_command_count(transfers.len() + 1).await?;
+
+        let sender = account_address(&account)?;
+        let pt = bulk_sui_transfer(&transfers)?;
+        let gas_payment = self.gas_payment(sender, required).await?;
+        let gas_price = self.reference_gas_price().await?;
+        let tx_data =
+            TransactionData::new_programmable(sender, gas_payment, pt, gas_budget, gas_price);
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Sends objects owned by `account` to their recipients in one transaction
+    ///
+    /// See `bulk_object_transfer` for how commands are grouped. Fails with
+    /// `ServiceError::Service` if the PTB would exceed the protocol's
+    /// `max_programmable_tx_commands`. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn bulk_transfer_objects(
+        &mut self,
+        transfers: Vec<(SuiAddress, ObjectID)>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let recipients: HashSet<SuiAddress> =
+            transfers.iter().map(|(recipient, _)| *recipient).collect();
+        self.check_command_count(recipients.len()).await?;
+
+        let sender = account_address(&account)?;
+        let mut objects = Vec::with_capacity(transfers.len());
+
+        for (recipient, object_id) in transfers {
+            objects.push((recipient, self.get_object_ref(object_id).await?));
+        }
+
+        let tx_data = self
+            .programmable_transaction_data(sender, bulk_object_transfer(objects)?, gas_budget)
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+
+    /// Fails if a PTB of `commands` commands exceeds the protocol limit
+    async fn check_command_count(&self, commands: usize) -> Result<()> {
+        let config = self.get_protocol_config(None).await?;
+
+        if commands as u64 > max_programmable_tx_commands(&config) {
+            return Err(ServiceError::Service("Too many transfers".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Sequence number of the latest checkpoint known to the node
+    pub async fn get_latest_checkpoint_sequence_number(&self) -> Result<u64> {
+        self.services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .get_latest_checkpoint_sequence_number()
+                    .await
+            })
+            .await
+    }
+
+    pub async fn get_checkpoint(&self, id: CheckpointId) -> Result<Checkpoint> {
+        self.services
+            .get_resilient_node()
+            .call(|node| async move { node.read_api().get_checkpoint(id).await })
+            .await
+    }
+
+    /// Known genesis checkpoint digest of devnet
+    ///
+    /// Devnet is wiped and regenerated regularly, each time with a new
+    /// genesis, so there is no fixed digest to compare against.
+    pub fn devnet_genesis_checkpoint() -> Option<&'static str> {
+        None
+    }
+
+    /// Known genesis checkpoint digest of testnet
+    pub fn testnet_genesis_checkpoint() -> &'static str {
+        TESTNET_GENESIS_CHECKPOINT
+    }
+
+    /// Known genesis checkpoint digest of mainnet
+    pub fn mainnet_genesis_checkpoint() -> &'static str {
+        MAINNET_GENESIS_CHECKPOINT
+    }
+
+    /// Checks that the node is on the chain `self` is configured for
+    ///
+    /// Compares the node's chain identifier, the first four bytes of its
+    /// genesis checkpoint digest, with the known identifier of the
+    /// configured network, which catches an RPC URL pointing at the wrong
+    /// network. Devnet has no stable identifier, so on devnet only a
+    /// mainnet or testnet node is rejected.
+    pub async fn assert_chain_matches_network(&self) -> Result<()> {
+        let network = self.services.get_network();
+        let actual = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move { node.read_api().get_chain_identifier().await })
+            .await?;
+
+        let matches = match network {
+            Network::Devnet => {
+                actual != MAINNET_CHAIN_IDENTIFIER && actual != TESTNET_CHAIN_IDENTIFIER
+            }
+            Network::Testnet => actual == TESTNET_CHAIN_IDENTIFIER,
+            Network::Mainnet => actual == MAINNET_CHAIN_IDENTIFIER,
+        };
+
+        if !matches {
+            return Err(ServiceError::Service(format!(
+                "Node chain identifier {} does not match {:?}",
+                actual, network
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Waits until the transaction `digest` is included in a checkpoint
+    ///
+    /// `execute_transaction` uses `WaitForLocalExecution`, which only means
+    /// the fullnode we talk to has applied the effects. A checkpoint is
+    /// certified by a quorum of validators, so once a transaction is in one
+    /// it is final network-wide and visible to every node and indexer.
+    ///
+    /// The node is polled for the transaction until it reports the
+    /// checkpoint that includes it, so this works however long ago the
+    /// transaction was executed. Fails with `ServiceError::Timeout` if it is
+    /// not checkpointed within `timeout`.
+    pub async fn wait_for_checkpoint_containing_tx(
+        &self,
+        digest: &str,
+        timeout: Duration,
+    ) -> Result<Checkpoint> {
+        let digest = TransactionDigest::from_str(digest)
+            .map_err(|e| ServiceError::Service(format!("Invalid transaction digest: {}", e)))?;
+
+        let wait = async {
+            loop {
+                // Unknown until the node has executed or synced the transaction
+                let checkpoint = self
+                    .services
+                    .get_resilient_node()
+                    .call(|node| async move {
+                        node.read_api()
+                            .get_transaction_with_options(
+                                digest,
+                                SuiTransactionBlockResponseOptions::new(),
+                            )
+                            .await
+                    })
+                    .await
+                    .ok()
+                    .and_then(|response| response.checkpoint);
+
+                if let Some(sequence_number) = checkpoint {
+                    return self
+                        .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+                        .await;
+                }
+
+                tokio::time::sleep(CHECKPOINT_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            ServiceError::Timeout(format!(
+                "Transaction {} was not checkpointed within {:?}",
+                digest, timeout
+            ))
+        })?
+    }
+
+    /// Transaction `digest` as included in checkpoint `expected_checkpoint`
+    ///
+    /// Confirms the transaction is final: unlike a response to
+    /// `execute_transaction`, which only shows that one fullnode applied the
+    /// effects, a checkpoint is certified by a quorum of validators and can't
+    /// be reverted. Fails with `ServiceError::InvalidResponse` if the
+    /// checkpoint does not include the transaction.
+    pub async fn get_tx_from_checkpoint(
+        &self,
+        digest: &str,
+        expected_checkpoint: u64,
+    ) -> Result<CheckpointTransaction> {
+        let digest = TransactionDigest::from_str(digest)
+            .map_err(|e| ServiceError::Service(format!("Invalid transaction digest: {}", e)))?;
+
+        let checkpoint = self
+            .checkpoint_including(expected_checkpoint, &digest)
+            .await?
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Transaction not in checkpoint".to_string())
+            })?;
+
+        self.checkpoint_transactions(&checkpoint, vec![digest])
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Transaction not in checkpoint".to_string())
+            })
+    }
+
+    /// The first `limit` transactions of checkpoint `checkpoint_seq`, in execution order
+    pub async fn get_checkpoint_transactions(
+        &self,
+        checkpoint_seq: u64,
+        limit: usize,
+    ) -> Result<Vec<CheckpointTransaction>> {
+        let checkpoint = self
+            .get_checkpoint(CheckpointId::SequenceNumber(checkpoint_seq))
+            .await?;
+        let digests = checkpoint
+            .transactions
+            .iter()
+            .take(limit)
+            .copied()
+            .collect();
+
+        self.checkpoint_transactions(&checkpoint, digests).await
+    }
+
+    /// Transactions sent by `address` that are not final yet
+    ///
+    /// Pending here means executed but not yet in a checkpoint. This is not
+    /// a mempool view: Sui has no mempool API, and a fullnode only reports
+    /// transactions after executing them. What can be seen is the short
+    /// window between execution and inclusion in a certified checkpoint, so
+    /// transactions still waiting for validators are never returned.
+    /// `submitted_at` is usually `None`, as the node timestamps transactions
+    /// by checkpoint. Only the `PENDING_TRANSACTIONS_LOOKBACK` most recent
+    /// transactions are checked.
+    pub async fn get_pending_transactions(
+        &self,
+        address: SuiAddress,
+    ) -> Result<Vec<PendingTransaction>> {
+        let page = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .query_transaction_blocks(
+                        SuiTransactionBlockResponseQuery::new(
+                            Some(TransactionFilter::FromAddress(address)),
+                            Some(SuiTransactionBlockResponseOptions::new().with_input()),
+                        ),
+                        None,
+                        Some(PENDING_TRANSACTIONS_LOOKBACK),
+                        true,
+                    )
+                    .await
+            })
+            .await?;
+
+        Ok(page
+            .data
+            .into_iter()
+            .filter(|response| response.checkpoint.is_none())
+            .filter_map(|response| {
+                let data = &response.transaction.as_ref()?.data;
+
+                Some(PendingTransaction {
+                    digest: response.digest.to_string(),
+                    sender: *data.sender(),
+                    gas_price: data.gas_data().price,
+                    gas_budget: data.gas_data().budget,
+                    submitted_at: response.timestamp_ms,
+                })
+            })
+            .collect())
+    }
+
+    /// Number of transactions `address` sent or received, over its whole history
+    ///
+    /// Pages through every transaction of the address like
+    /// `get_address_activity`, but only counts each page instead of keeping
+    /// it. Transactions sent by `address` to itself are counted once, and
+    /// ones not yet in a checkpoint are included.
+    pub async fn get_transaction_count(&self, address: SuiAddress) -> Result<u64> {
+        let sent = self
+            .count_transactions(
+                TransactionFilter::FromAddress(address),
+                SuiTransactionBlockResponseOptions::new(),
+                |_| true,
+            )
+            .await?;
+        let received = self
+            .count_transactions(
+                TransactionFilter::ToAddress(address),
+                SuiTransactionBlockResponseOptions::new().with_input(),
+                |response| {
+                    response
+                        .transaction
+                        .as_ref()
+                        .is_none_or(|transaction| *transaction.data.sender() != address)
+                },
+            )
+            .await?;
+
+        Ok(sent + received)
+    }
+
+    /// Number of transactions matching `filter` for which `counted` holds
+    async fn count_transactions(
+        &self,
+        filter: TransactionFilter,
+        options: SuiTransactionBlockResponseOptions,
+        counted: impl Fn(&SuiTransactionBlockResponse) -> bool,
+    ) -> Result<u64> {
+        let query = SuiTransactionBlockResponseQuery::new(Some(filter), Some(options));
+        let mut count = 0;
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let query = query.clone();
+                    async move {
+                        node.read_api()
+                            .query_transaction_blocks(
+                                query,
+                                cursor,
+                                Some(TRANSACTION_HISTORY_PAGE_SIZE),
+                                true,
+                            )
+                            .await
+                    }
+                })
+                .await?;
+
+            count += page
+                .data
+                .iter()
+                .filter(|response| counted(response))
+                .count() as u64;
+
+            if !page.has_next_page {
+                return Ok(count);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Transactions of `address` timestamped in `from_ms..=to_ms`
+    ///
+    /// Sent transactions are those signed by `address`, received ones those
+    /// sent by others that gave it objects. Fullnodes keep no per-address
+    /// counters, so this pages through both histories newest first, one
+    /// request per `TRANSACTION_HISTORY_PAGE_SIZE` transactions, stopping at
+    /// `from_ms`. Busy addresses take many requests: results are cached for
+    /// `ADDRESS_ACTIVITY_TTL`, up to `ADDRESS_ACTIVITY_CACHE_LIMIT` ranges at
+    /// once, and dashboards should prefer short, recent
+    /// ranges or persist the counts on their side. Transactions not yet in
+    /// a checkpoint have no timestamp and are left out.
+    pub async fn get_address_activity(
+        &self,
+        address: SuiAddress,
+        from_ms: u64,
+        to_ms: u64,
+    ) -> Result<AddressActivity> {
+        let key = (address, from_ms, to_ms);
+
+        if let Some((activity, computed_at)) = self
+            .activity_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.get(&key).cloned())
+            && computed_at.elapsed() < ADDRESS_ACTIVITY_TTL
+        {
+            return Ok(activity);
+        }
+
+        let sent = self
+            .transaction_history(
+                TransactionFilter::FromAddress(address),
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                from_ms,
+                to_ms,
+            )
+            .await?;
+        let received = self
+            .transaction_history(
+                TransactionFilter::ToAddress(address),
+                SuiTransactionBlockResponseOptions::new(),
+                from_ms,
+                to_ms,
+            )
+            .await?;
+
+        let sent_digests: HashSet<TransactionDigest> =
+            sent.iter().map(|response| response.digest).collect();
+        let received_tx = received
+            .iter()
+            .filter(|response| !sent_digests.contains(&response.digest))
+            .count() as u64;
+        let timestamps = || {
+            sent.iter()
+                .chain(&received)
+                .filter_map(|response| response.timestamp_ms)
+        };
+
+        let activity = AddressActivity {
+            total_tx: sent.len() as u64 + received_tx,
+            sent_tx: sent.len() as u64,
+            received_tx,
+            total_gas_spent_mist: sent
+                .iter()
+                .filter_map(|response| response.effects.as_ref())
+                .fold(0u64, |total, effects| {
+                    let net = effects.gas_cost_summary().net_gas_usage();
+                    total.saturating_add(u64::try_from(net).unwrap_or_default())
+                }),
+            first_tx_ms: timestamps().min(),
+            last_tx_ms: timestamps().max(),
+        };
+
+        if let Ok(mut cache) = self.activity_cache.write() {
+            cache.retain(|_, (_, computed_at)| computed_at.elapsed() < ADDRESS_ACTIVITY_TTL);
+
+            if cache.len() >= ADDRESS_ACTIVITY_CACHE_LIMIT
+                && let Some(oldest) = cache
+                    .iter()
+                    .min_by_key(|(_, (_, computed_at))| *computed_at)
+                    .map(|(key, _)| *key)
+            {
+                cache.remove(&oldest);
+            }
+
+            cache.insert(key, (activity.clone(), Instant::now()));
+        }
+
+        Ok(activity)
+    }
+
+    /// Transactions matching `filter` timestamped in `from_ms..=to_ms`, newest first
+    async fn transaction_history(
+        &self,
+        filter: TransactionFilter,
+        options: SuiTransactionBlockResponseOptions,
+        from_ms: u64,
+        to_ms: u64,
+    ) -> Result<Vec<SuiTransactionBlockResponse>> {
+        let query = SuiTransactionBlockResponseQuery::new(Some(filter), Some(options));
+        let mut transactions = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let query = query.clone();
+                    async move {
+                        node.read_api()
+                            .query_transaction_blocks(
+                                query,
+                                cursor,
+                                Some(TRANSACTION_HISTORY_PAGE_SIZE),
+                                true,
+                            )
+                            .await
+                    }
+                })
+                .await?;
+
+            let mut reached_start = false;
+
+            for response in page.data {
+                match response.timestamp_ms {
+                    Some(timestamp) if timestamp < from_ms => reached_start = true,
+                    Some(timestamp) if timestamp <= to_ms => transactions.push(response),
+                    _ => {}
+                }
+            }
+
+            if reached_start || !page.has_next_page {
+                return Ok(transactions);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Cancels a transaction before it is final
+    ///
+    /// Sui has no way to withdraw a submitted transaction, so this always
+    /// fails with `ServiceError::Service`. It exists so callers can be
+    /// written against the API if the protocol adds cancellation.
+    pub async fn cancel_pending_transaction(&self, _digest: &str) -> Result<()> {
+        Err(ServiceError::Service("Not yet supported".to_string()))
+    }
+
+    /// Checkpoint `sequence_number` if it includes transaction `digest`
+    async fn checkpoint_including(
+        &self,
+        sequence_number: u64,
+        digest: &TransactionDigest,
+    ) -> Result<Option<Checkpoint>> {
+        let checkpoint = self
+            .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+            .await?;
+
+        Ok(checkpoint
+            .transactions
+            .contains(digest)
+            .then_some(checkpoint))
+    }
+
+    /// Effects of `digests`, all included in `checkpoint`
+    async fn checkpoint_transactions(
+        &self,
+        checkpoint: &Checkpoint,
+        digests: Vec<TransactionDigest>,
+    ) -> Result<Vec<CheckpointTransaction>> {
+        let mut transactions = Vec::with_capacity(digests.len());
+
+        for chunk in digests.chunks(MULTI_GET_TRANSACTIONS_LIMIT) {
+            let responses = self
+                .services
+                .get_resilient_node()
+                .call(|node| async move {
+                    node.read_api()
+                        .multi_get_transactions_with_options(
+                            chunk.to_vec(),
+                            SuiTransactionBlockResponseOptions::new().with_effects(),
+                        )
+                        .await
+                })
+                .await?;
+
+            for response in responses {
+                let effects = response.effects.ok_or_else(|| {
+                    ServiceError::InvalidResponse(format!(
+                        "Transaction {} has no effects",
+                        response.digest
+                    ))
+                })?;
+
+                transactions.push(CheckpointTransaction {
+                    digest: response.digest.to_string(),
+                    checkpoint: checkpoint.sequence_number,
+                    timestamp_ms: checkpoint.timestamp_ms,
+                    effects,
+                });
+            }
+        }
+
+        Ok(transactions)
+    }
+
+    /// Fetches the latest Sui system state summary
+    ///
+    /// `state.epoch` is the current epoch. The `max_epoch` returned by Enoki
+    /// for a nonce is an absolute epoch number: the zkLogin session (and the
+    /// ephemeral key behind it) stays valid while `state.epoch <= max_epoch`.
+    pub async fn get_system_state(&self) -> Result<SuiSystemStateSummary> {
+        self.services
+            .get_resilient_node()
+            .call(|node| async move { node.governance_api().get_latest_sui_system_state().await })
+            .await
+    }
+
+    /// Active validators with an estimated staking APY
+    ///
+    /// The APY assumes the current stake subsidy is paid every epoch and
+    /// ignores gas fees; see `utils::staking::estimate_validator_apy`.
+    pub async fn get_validators(&self) -> Result<Vec<ValidatorSummary>> {
+        let state = self.get_system_state().await?;
+
+        Ok(state
+            .active_validators
+            .iter()
+            .map(|validator| {
+                let apy = estimate_validator_apy(
+                    validator,
+                    state.epoch_duration_ms,
+                    state.stake_subsidy_current_distribution_amount,
+                );
+
+                ValidatorSummary {
+                    name: validator.name.clone(),
+                    address: validator.sui_address,
+                    stake: validator.staking_pool_sui_balance,
+                    commission_rate: u16::try_from(validator.commission_rate).unwrap_or(u16::MAX),
+                    apy_bps: (apy * 10_000.0) as u64,
+                    voting_power: validator.voting_power,
+                }
+            })
+            .collect())
+    }
+
+    /// Staking pool of active validator `validator` in the current epoch
+    ///
+    /// Fails with `ServiceError::Service` if `validator` is not in the
+    /// active set.
+    pub async fn get_validator_staking_pool(
+        &self,
+        validator: SuiAddress,
+    ) -> Result<StakingPoolInfo> {
+        let state = self.get_system_state().await?;
+
+        let summary = state
+            .active_validators
+            .iter()
+            .find(|summary| summary.sui_address == validator)
+            .ok_or_else(|| {
+                ServiceError::Service(format!("Validator {} is not active", validator))
+            })?;
+
+        Ok(StakingPoolInfo {
+            pool_id: summary.staking_pool_id,
+            active_stake: summary.staking_pool_sui_balance,
+            pending_stake: summary.pending_stake,
+            pending_withdrawal: summary.pending_total_sui_withdraw,
+            exchange_rate: pool_exchange_rate(summary),
+            epoch: state.epoch,
+        })
+    }
+
+    /// `StakedSui` objects owned by `staker`, across all validators
+    ///
+    /// Rewards are the node's estimate; stakes that are still pending or
+    /// already withdrawn report none.
+    pub async fn get_user_stake_positions(&self, staker: SuiAddress) -> Result<Vec<StakePosition>> {
+        let delegations = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move { node.governance_api().get_stakes(staker).await })
+            .await?;
+
+        Ok(stake_positions(&delegations))
+    }
+
+    /// All `StakedSui` objects owned by `staker`, with totals
+    ///
+    /// The objects are listed by type, so stakes wrapped in other objects
+    /// are not included; validators and reward estimates come from the
+    /// node. See `utils::staking` for ranking and rebalancing the result.
+    pub async fn get_all_delegations(&self, staker: SuiAddress) -> Result<DelegationPortfolio> {
+        let staked_sui_ids: Vec<ObjectID> = self
+            .get_owned_objects(
+                staker,
+                Some(SuiObjectDataFilter::StructType(StakedSui::type_())),
+                SuiObjectDataOptions::new(),
+            )
+            .await?
+            .iter()
+            .map(|object| object.object_id)
+            .collect();
+
+        if staked_sui_ids.is_empty() {
+            return Ok(delegation_portfolio(Vec::new()));
+        }
+
+        let delegations = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let staked_sui_ids = staked_sui_ids.clone();
+                async move {
+                    node.governance_api()
+                        .get_stakes_by_ids(staked_sui_ids)
+                        .await
+                }
+            })
+            .await?;
+
+        Ok(delegation_portfolio(stake_positions(&delegations)))
+    }
+
+    /// Fetches the full Sui system state in its on-chain layout
+    ///
+    /// `get_system_state` returns `SuiSystemStateSummary`, which flattens
+    /// the state into the fields common to every version. This reads the
+    /// versioned inner object stored under `0x5` instead, returning the
+    /// variant matching the current system state version, so fields that
+    /// are not part of the summary are available too.
+    pub async fn get_system_state_inner(&self) -> Result<SuiSystemState> {
+        let wrapper: SuiSystemStateWrapper =
+            bcs::from_bytes(&self.get_object_bcs(SUI_SYSTEM_STATE_OBJECT_ID).await?).map_err(
+                |e| ServiceError::InvalidResponse(format!("Invalid system state wrapper: {}", e)),
+            )?;
+
+        let field = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .get_dynamic_field_object(
+                        SUI_SYSTEM_STATE_OBJECT_ID,
+                        DynamicFieldName {
+                            type_: TypeTag::U64,
+                            value: serde_json::Value::String(wrapper.version.to_string()),
+                        },
+                    )
+                    .await
+            })
+            .await?
+            .data
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("System state inner object not found".to_string())
+            })?;
+
+        let inner_bytes = match field.bcs {
+            Some(SuiRawData::MoveObject(object)) => object.bcs_bytes,
+            _ => self.get_object_bcs(field.object_id).await?,
+        };
+
+        let invalid_state =
+            |e: bcs::Error| ServiceError::InvalidResponse(format!("Invalid system state: {}", e));
+
+        match wrapper.version {
+            1 => bcs::from_bytes::<Field<u64, SuiSystemStateInnerV1>>(&inner_bytes)
+                .map(|field| SuiSystemState::V1(field.value))
+                .map_err(invalid_state),
+            2 => bcs::from_bytes::<Field<u64, SuiSystemStateInnerV2>>(&inner_bytes)
+                .map(|field| SuiSystemState::V2(field.value))
+                .map_err(invalid_state),
+            version => Err(ServiceError::InvalidResponse(format!(
+                "Unsupported system state version {}",
+                version
+            ))),
+        }
+    }
+
+    /// Stake a validator will have at the start of the next epoch
+    ///
+    /// Includes stake added and withdrawn during the current epoch, so it
+    /// predicts next-epoch rewards better than the current `stake_value`.
+    pub async fn get_validator_next_epoch_stake(&self, validator: SuiAddress) -> Result<u64> {
+        let state = self.get_system_state().await?;
+
+        state
+            .active_validators
+            .iter()
+            .find(|summary| summary.sui_address == validator)
+            .map(|summary| summary.next_epoch_stake)
+            .ok_or_else(|| {
+                ServiceError::Service(format!("{} is not an active validator", validator))
+            })
+    }
+
+    /// Time left until the current epoch is expected to end
+    pub async fn time_until_epoch_end(&self) -> Result<Duration> {
+        let state = self.get_system_state().await?;
+
+        Ok(Duration::from_millis(
+            next_epoch_start_ms(&state).saturating_sub(unix_time_ms()),
+        ))
+    }
+
+    /// Estimates how long the current zkLogin session stays valid
+    ///
+    /// The session ends when the network leaves `max_epoch`; the estimate
+    /// assumes every remaining epoch lasts as long as the current one.
+    pub async fn get_zklogin_session_duration_estimate(&self) -> Result<Duration> {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        let state = self.get_system_state().await?;
+
+        if max_epoch < state.epoch {
+            return Ok(Duration::ZERO);
+        }
+
+        let expires_at_ms = state.epoch_start_timestamp_ms.saturating_add(
+            (max_epoch - state.epoch)
+                .saturating_add(1)
+                .saturating_mul(state.epoch_duration_ms),
+        );
+
+        Ok(Duration::from_millis(
+            expires_at_ms.saturating_sub(unix_time_ms()),
+        ))
+    }
+
+    /// How long the proof for the current session remains usable
+    ///
+    /// Validity is epoch-based: see `ProofValidity`. The wall-clock estimate
+    /// can be off by however much the remaining epochs deviate from the
+    /// configured epoch duration.
+    pub async fn get_zklogin_proof_validity(&self) -> Result<ProofValidity> {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        self.check_proof_still_valid(max_epoch).await
+    }
+
+    /// Whether the proof for the current session can still sign transactions
+    pub async fn is_proof_valid(&self) -> Result<bool> {
+        Ok(self.get_zklogin_proof_validity().await?.is_valid)
+    }
+
+    /// Whether a proof made for `max_epoch` can still sign a new transaction
+    ///
+    /// `ZkLoginInputs` do not carry their `max_epoch`: it is committed in
+    /// the nonce, so pass the one the proof was requested with, as for
+    /// `sign_transaction`.
+    pub async fn check_proof_still_valid(&self, max_epoch: u64) -> Result<ProofValidity> {
+        let state = self.get_system_state().await?;
+
+        Ok(proof_validity(
+            max_epoch,
+            state.epoch,
+            state.epoch_start_timestamp_ms,
+            state.epoch_duration_ms,
+            unix_time_ms(),
+        ))
+    }
+
+    /// `existing` if the session's proof is still valid, else a proof from Enoki
+    ///
+    /// Without `existing`, the proof for the stored JWT is fetched through
+    /// `recover_seed_address`. Once the session's `max_epoch` has passed no
+    /// proof can be made for its nonce: the JWT is cleared and
+    /// `ServiceError::InvalidProof` returned, so the user has to start over
+    /// with `create_zkp_payload` and the OAuth flow.
+    pub async fn use_proof_or_refresh(
+        &mut self,
+        existing: Option<ZkLoginInputs>,
+    ) -> Result<ZkLoginInputs> {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+        let validity = self.check_proof_still_valid(max_epoch).await?;
+
+        if !validity.is_valid {
+            self.services.clear_jwt();
+
+            return Err(ServiceError::InvalidProof(format!(
+                "No proof can be made for max epoch {} in epoch {}",
+                max_epoch, validity.current_epoch
+            )));
+        }
+
+        match existing {
+            Some(zk_inputs) => Ok(zk_inputs),
+            None => self.recover_seed_address().await,
+        }
+    }
+
+    /// Returns a human-readable hint about when to re-authenticate
+    ///
+    /// # Example
+    /// ```rust
+    /// // "Session expires in ~2 hours. Re-authenticate before epoch 150."
+    /// println!("{}", squad_connect.get_zklogin_nonce_expiry_advice().await?);
+    /// ```
+    pub async fn get_zklogin_nonce_expiry_advice(&self) -> Result<String> {
+        let (_, _, max_epoch) = self.get_zk_proof_params();
+
+        if max_epoch == 0 {
+            return Ok("No active session. Call create_zkp_payload to start.".to_string());
+        }
+
+        let jwt = self.services.get_jwt();
+
+        if !jwt.is_empty() {
+            let claims = services::decode_jwt_claims(jwt)?;
+            let jwt_expired = claims
+                .get("exp")
+                .and_then(|exp| exp.as_u64())
+                .is_some_and(|exp| exp.saturating_mul(1000) <= unix_time_ms());
+
+            if jwt_expired {
+                return Ok(
+                    "JWT expired. Complete the OAuth flow again to obtain a new one.".to_string(),
+                );
+            }
+        }
+
+        let remaining = self.get_zklogin_session_duration_estimate().await?;
+
+        if remaining.is_zero() {
+            return Ok("Session expired. Call create_zkp_payload to restart.".to_string());
+        }
+
+        let hours = remaining.as_secs() / 3600;
+        let approx = if hours > 0 {
+            format!("~{} hour{}", hours, if hours == 1 { "" } else { "s" })
+        } else {
+            format!("~{} minutes", remaining.as_secs().div_ceil(60))
+        };
+
+        Ok(format!(
+            "Session expires in {}. Re-authenticate before epoch {}.",
+            approx,
+            max_epoch.saturating_add(1)
+        ))
+    }
+
+    /// Wraps a finished PTB into `TransactionData` with gas from `sender`
+    pub async fn programmable_transaction_data(
+        &self,
+        sender: SuiAddress,
+        pt: ProgrammableTransaction,
+        gas_budget: u64,
+    ) -> Result<TransactionData> {
+        let gas_payment = self.gas_payment(sender, gas_budget).await?;
+        let gas_price = self.reference_gas_price().await?;
+
+        Ok(TransactionData::new_programmable(
+            sender,
+            gas_payment,
+            pt,
+            gas_budget,
+            gas_price,
+        ))
+    }
+
+    fn kiosk_rules_package(&self) -> Result<ObjectID> {
+        let package = self.get_network().kiosk_rules_package().ok_or_else(|| {
+            ServiceError::Service(format!(
+                "Kiosk rules are not deployed on {}",
+                self.get_network()
+            ))
+        })?;
+
+        parse_object_id(package)
+    }
+
+    /// Finds the `TransferPolicy<T>` created for `type_str`
+    async fn find_transfer_policy(&self, type_str: &str) -> Result<ObjectID> {
+        let type_tag = parse_type_tag(type_str)?;
+        let event_type = parse_sui_struct_tag(&format!(
+            "{}<{}>",
+            TRANSFER_POLICY_CREATED_EVENT,
+            type_tag.to_canonical_string(true)
+        ))
+        .map_err(|e| ServiceError::Service(format!("Invalid event type: {}", e)))?;
+
+        let page = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let event_type = event_type.clone();
+                async move {
+                    node.event_api()
+                        .query_events(EventFilter::MoveEventType(event_type), None, Some(1), false)
+                        .await
+                }
+            })
+            .await?;
+
+        let event = page.data.first().ok_or_else(|| {
+            ServiceError::Service(format!("No transfer policy found for {}", type_str))
+        })?;
+
+        event.parsed_json["id"]
+            .as_str()
+            .map(parse_object_id)
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse("Transfer policy event has no id".to_string())
+            })?
+    }
+
+    /// Polls the node until the transaction `digest` is known
+    async fn wait_for_transaction(&self, digest: &str, timeout: Duration) -> Result<()> {
+        let digest = TransactionDigest::from_str(digest)
+            .map_err(|e| ServiceError::InvalidResponse(format!("Invalid digest: {}", e)))?;
+
+        let wait = async {
+            while self
+                .services
+                .get_resilient_node()
+                .call(|node| async move {
+                    node.read_api()
+                        .get_transaction_with_options(
+                            digest,
+                            SuiTransactionBlockResponseOptions::new(),
+                        )
+                        .await
+                })
+                .await
+                .is_err()
+            {
+                tokio::time::sleep(TRANSACTION_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, wait).await.map_err(|_| {
+            ServiceError::Timeout(format!(
+                "Transaction {} was not executed within {:?}",
+                digest, timeout
+            ))
+        })
+    }
+
+    /// Raw BCS contents of a Move object
+    async fn get_object_bcs(&self, object_id: ObjectID) -> Result<Vec<u8>> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::bcs_lossless())
+            .await?;
+
+        match response.data.and_then(|object| object.bcs) {
+            Some(SuiRawData::MoveObject(object)) => Ok(object.bcs_bytes),
+            _ => Err(ServiceError::InvalidResponse(format!(
+                "Object {} has no Move contents",
+                object_id
+            ))),
+        }
+    }
+
+    /// Whether an object with `object_id` exists and has not been deleted
+    pub async fn object_exists(&self, object_id: ObjectID) -> Result<bool> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new())
+            .await?;
+
+        match (response.data, response.error) {
+            (Some(_), _) => Ok(true),
+            (
+                None,
+                Some(
+                    SuiObjectResponseError::NotExists { .. }
+                    | SuiObjectResponseError::Deleted { .. },
+                ),
+            ) => Ok(false),
+            (None, error) => Err(ServiceError::InvalidResponse(format!(
+                "Failed to get object {}: {:?}",
+                object_id, error
+            ))),
+        }
+    }
+
+    pub async fn get_object_owner(&self, object_id: ObjectID) -> Result<Owner> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?;
+
+        response
+            .data
+            .and_then(|object| object.owner)
+            .ok_or_else(|| ServiceError::InvalidResponse(format!("Object {} not found", object_id)))
+    }
+
+    /// Fails with `ServiceError::InvalidProof` unless `owner` owns `object_id`
+    pub async fn assert_owned_by(&self, object_id: ObjectID, owner: SuiAddress) -> Result<()> {
+        match self.get_object_owner(object_id).await? {
+            Owner::AddressOwner(address) if address == owner => Ok(()),
+            _ => Err(ServiceError::InvalidProof(
+                "Object not owned by expected address".to_string(),
+            )),
+        }
+    }
+
+    pub async fn get_object_version(&self, object_id: ObjectID) -> Result<SequenceNumber> {
+        Ok(self.get_object_ref(object_id).await?.1)
+    }
+
+    /// State of `object_ids` right before and right after transaction `digest`
+    ///
+    /// Versions are taken from the transaction effects and read with
+    /// `try_get_parsed_past_object`, so later transactions don't affect the
+    /// result. `before` is `None` for objects the transaction created or
+    /// didn't take as input, `after` for objects it deleted or wrapped or
+    /// didn't touch. Past versions are pruned by most fullnodes after a
+    /// while, in which case they are `None` as well.
+    ///
+    /// When a sponsored transaction fails, pass the digest from the failed
+    /// execution along with the objects it used: `before` shows the versions
+    /// and owners the transaction was built against, which is usually enough
+    /// to spot an object that was spent or moved by another transaction.
+    pub async fn object_snapshot_before_and_after(
+        &self,
+        digest: &str,
+        object_ids: Vec<ObjectID>,
+    ) -> Result<HashMap<ObjectID, (Option<SuiObjectData>, Option<SuiObjectData>)>> {
+        let digest = TransactionDigest::from_str(digest)
+            .map_err(|e| ServiceError::Service(format!("Invalid digest: {}", e)))?;
+
+        let response = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .get_transaction_with_options(
+                        digest,
+                        SuiTransactionBlockResponseOptions::new().with_effects(),
+                    )
+                    .await
+            })
+            .await?;
+
+        let effects = response.effects.ok_or_else(|| {
+            ServiceError::InvalidResponse(format!("Transaction {} has no effects", digest))
+        })?;
+
+        let input_versions: HashMap<ObjectID, SequenceNumber> =
+            effects.modified_at_versions().into_iter().collect();
+        let output_versions: HashMap<ObjectID, SequenceNumber> = effects
+            .all_changed_objects()
+            .into_iter()
+            .map(|(object, _)| (object.object_id(), object.version()))
+            .collect();
+
+        let mut snapshots = HashMap::with_capacity(object_ids.len());
+
+        for object_id in object_ids {
+            let before = match input_versions.get(&object_id) {
+                Some(version) => self.get_past_object(object_id, *version).await?,
+                None => None,
+            };
+            let after = match output_versions.get(&object_id) {
+                Some(version) => self.get_past_object(object_id, *version).await?,
+                None => None,
+            };
+
+            snapshots.insert(object_id, (before, after));
+        }
+
+        Ok(snapshots)
+    }
+
+    /// `object_id` at `version`, or `None` if that version is gone
+    async fn get_past_object(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> Result<Option<SuiObjectData>> {
+        let response = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.read_api()
+                    .try_get_parsed_past_object(
+                        object_id,
+                        version,
+                        SuiObjectDataOptions::full_content(),
+                    )
+                    .await
+            })
+            .await?;
+
+        Ok(match response {
+            SuiPastObjectResponse::VersionFound(object) => Some(object),
+            _ => None,
+        })
+    }
+
+    /// Checks the owned objects `tx` uses before it is signed
+    ///
+    /// Each one must exist at the referenced version and belong to the
+    /// sender or the gas owner, so a stale or foreign reference fails here
+    /// instead of at execution.
+    async fn validate_object_refs(&self, tx: &TransactionData) -> Result<()> {
+        let inputs = tx
+            .input_objects()
+            .map_err(|e| ServiceError::Service(format!("Invalid transaction inputs: {}", e)))?;
+
+        let refs: Vec<(ObjectID, SequenceNumber)> = inputs
+            .into_iter()
+            .filter_map(|input| match input {
+                InputObjectKind::ImmOrOwnedMoveObject((object_id, version, _)) => {
+                    Some((object_id, version))
+                }
+                _ => None,
+            })
+            .collect();
+
+        if refs.is_empty() {
+            return Ok(());
+        }
+
+        let object_ids: Vec<ObjectID> = refs.iter().map(|(object_id, _)| *object_id).collect();
+        let responses = self
+            .services
+            .get_resilient_node()
+            .call(|node| {
+                let object_ids = object_ids.clone();
+                async move {
+                    node.read_api()
+                        .multi_get_object_with_options(
+                            object_ids,
+                            SuiObjectDataOptions::new().with_owner(),
+                        )
+                        .await
+                }
+            })
+            .await?;
+
+        for ((object_id, version), response) in refs.into_iter().zip(responses) {
+            let Some(object) = response.data else {
+                return Err(ServiceError::Service(format!(
+                    "Object {} does not exist",
+                    object_id
+                )));
+            };
+
+            if object.version != version {
+                return Err(ServiceError::Service(format!(
+                    "Object {} is at version {}, not {}",
+                    object_id, object.version, version
+                )));
+            }
+
+            if let Some(Owner::AddressOwner(owner)) = object.owner
+                && owner != tx.sender()
+                && owner != tx.gas_owner()
+            {
+                return Err(ServiceError::InvalidProof(
+                    "Object not owned by expected address".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_shared_object_arg(
+        &self,
+        object_id: ObjectID,
+        mutable: bool,
+    ) -> Result<ObjectArg> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_owner())
+            .await?;
+
+        match response.data.and_then(|object| object.owner) {
+            Some(Owner::Shared {
+                initial_shared_version,
+            }) => Ok(ObjectArg::SharedObject {
+                id: object_id,
+                initial_shared_version,
+                mutable,
+            }),
+            Some(_) => Err(ServiceError::Service(format!(
+                "Object {} is not shared",
+                object_id
+            ))),
+            None => Err(ServiceError::InvalidResponse(format!(
+                "Object {} not found",
+                object_id
+            ))),
+        }
+    }
+
+    pub(crate) async fn get_object_ref(&self, object_id: ObjectID) -> Result<ObjectRef> {
+        let response = self
+            .get_object_with_options(object_id, SuiObjectDataOptions::new())
+            .await?;
+
+        response
+            .data
+            .map(|object| object.object_ref())
+            .ok_or_else(|| ServiceError::InvalidResponse(format!("Object {} not found", object_id)))
+    }
+
+    /// Reads `object_id`, falling over to the fallback RPC endpoints on errors
+    pub(crate) async fn get_object_with_options(
+        &self,
+        object_id: ObjectID,
+        options: SuiObjectDataOptions,
+    ) -> Result<SuiObjectResponse> {
+        self.services
+            .get_resilient_node()
+            .call(|node| {
+                let options = options.clone();
+                async move {
+                    node.read_api()
+                        .get_object_with_options(object_id, options)
+                        .await
+                }
+            })
+            .await
+    }
+
+    pub(crate) async fn get_owned_objects(
+        &self,
+        owner: SuiAddress,
+        filter: Option<SuiObjectDataFilter>,
+        options: SuiObjectDataOptions,
+    ) -> Result<Vec<SuiObjectData>> {
+        let query = SuiObjectResponseQuery::new(filter, Some(options));
+        let mut objects = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self
+                .services
+                .get_resilient_node()
+                .call(|node| {
+                    let query = query.clone();
+                    async move {
+                        node.read_api()
+                            .get_owned_objects(owner, Some(query), cursor, None)
+                            .await
+                    }
+                })
+                .await?;
+
+            objects.extend(page.data.into_iter().filter_map(|response| response.data));
+
+            if !page.has_next_page {
+                return Ok(objects);
+            }
+
+            cursor = page.next_cursor;
+        }
+    }
+
+    fn ephemeral_address(&self) -> Result<SuiAddress> {
+        let (_, public_key, _) = self.get_zk_proof_params();
+
+        let public_key = PublicKey::decode_base64(&public_key).map_err(|e| {
+            ServiceError::InvalidProof(format!("Invalid ephemeral public key: {}", e))
+        })?;
+
+        Ok(SuiAddress::from(&public_key))
+    }
+
+    async fn gas_payment(&self, sender: SuiAddress, gas_budget: u64) -> Result<Vec<ObjectRef>> {
+        let coins = self
+            .services
+            .get_resilient_node()
+            .call(|node| async move {
+                node.coin_read_api()
+                    .select_coins(sender, None, gas_budget as u128, vec![])
+                    .await
+            })
+            .await?;
+
+        Ok(coins.iter().map(|coin| coin.object_ref()).collect())
+    }
+
+    pub(crate) async fn reference_gas_price(&self) -> Result<u64> {
+        self.services
+            .get_resilient_node()
+            .call(|node| async move { node.read_api().get_reference_gas_price().await })
+            .await
+    }
+}
+
+/// String claim `name` of decoded JWT `claims`
+fn jwt_claim<'a>(claims: &'a serde_json::Value, name: &str) -> Result<&'a str> {
+    claims[name]
+        .as_str()
+        .ok_or_else(|| ServiceError::JwtExtraction(format!("JWT has no {} claim", name)))
+}
+
+pub(crate) fn account_address(account: &AccountResponse) -> Result<SuiAddress> {
+    SuiAddress::from_str(&account.address)
+        .map_err(|e| ServiceError::InvalidResponse(format!("Invalid account address: {}", e)))
+}
+
+/// Returns the fields of a Move object as JSON, if content was requested
+pub(crate) fn move_object_fields(object: &SuiObjectData) -> Option<serde_json::Value> {
+    match &object.content {
+        Some(SuiParsedData::MoveObject(move_object)) => {
+            Some(move_object.fields.clone().to_json_value())
+        }
+        _ => None,
+    }
+}
+
+/// Parses an object ID that Move may render with or without the `0x` prefix
+pub(crate) fn parse_object_id(id: &str) -> Result<ObjectID> {
+    let id = if id.starts_with("0x") {
+        id.to_string()
+    } else {
+        format!("0x{}", id)
+    };
+
+    ObjectID::from_hex_literal(&id)
+        .map_err(|e| ServiceError::InvalidResponse(format!("Invalid object ID {}: {}", id, e)))
+}
+
+fn parse_type_tag(type_str: &str) -> Result<TypeTag> {
+    parse_sui_type_tag(type_str)
+        .map_err(|e| ServiceError::Service(format!("Invalid type {}: {}", type_str, e)))
+}
+
+/// Flattens the stakes of `delegations` into positions
+fn stake_positions(delegations: &[DelegatedStake]) -> Vec<StakePosition> {
+    delegations
+        .iter()
+        .flat_map(|delegation| {
+            delegation.stakes.iter().map(|stake| StakePosition {
+                staked_sui_id: stake.staked_sui_id,
+                validator: delegation.validator_address,
+                principal: stake.principal,
+                estimated_reward: match stake.status {
+                    StakeStatus::Active { estimated_reward } => estimated_reward,
+                    _ => 0,
+                },
+                activation_epoch: stake.stake_active_epoch,
+            })
+        })
+        .collect()
+}
+
+/// Whether a simulated Move call ran to completion
+pub fn is_simulation_successful(sim: &MoveCallSimulation) -> bool {
+    sim.error.is_none() && sim.effects_status == "success"
+}
+
+/// Adds a `transfer_policy::confirm_request` call for `request` to a PTB
+///
+/// This is the last step of a Kiosk purchase: `request` is the
+/// `TransferRequest<T>` hot potato returned by `kiosk::purchase` earlier in
+/// the same PTB, and every rule of the policy must already have added its
+/// receipt to it. `policy` is the shared `TransferPolicy<T>` the request is
+/// checked against. Returns the `(item, paid, from)` tuple the call yields.
+pub fn add_policy_confirm_request(
+    builder: &mut ProgrammableTransactionBuilder,
+    policy: ObjectArg,
+    request: Argument,
+    item_type: TypeTag,
+) -> Result<Argument> {
+    let policy = builder.obj(policy).map_err(ptb_error)?;
+
+    Ok(builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("transfer_policy").map_err(ptb_error)?,
+        Identifier::new("confirm_request").map_err(ptb_error)?,
+        vec![item_type],
+        vec![policy, request],
+    ))
+}
+
+/// PTB paying every `(recipient, amount)` of `transfers` from the gas coin
+///
+/// A single `SplitCoins` creates all the payments, followed by one
+/// `TransferObjects` per recipient for its split output.
+pub fn bulk_sui_transfer(transfers: &[(SuiAddress, u64)]) -> Result<ProgrammableTransaction> {
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let amounts = transfers
+        .iter()
+        .map(|(_, amount)| builder.pure(*amount))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(ptb_error)?;
+    let coins = builder.command(Command::SplitCoins(Argument::GasCoin, amounts));
+
+    for (index, (recipient, _)) in transfers.iter().enumerate() {
+        let index = u16::try_from(index).map_err(ptb_error)?;
+        builder.transfer_arg(*recipient, nested_result(coins, index)?);
+    }
+
+    Ok(builder.finish())
+}
+
+/// PTB sending each object of `transfers` to its recipient
+///
+/// Objects going to the same recipient share one `TransferObjects`
+/// command, in the order recipients first appear.
+pub fn bulk_object_transfer(
+    transfers: Vec<(SuiAddress, ObjectRef)>,
+) -> Result<ProgrammableTransaction> {
+    let mut grouped: Vec<(SuiAddress, Vec<ObjectRef>)> = Vec::new();
+
+    for (recipient, object) in transfers {
+        match grouped
+            .iter_mut()
+            .find(|(address, _)| *address == recipient)
+        {
+            Some((_, objects)) => objects.push(object),
+            None => grouped.push((recipient, vec![object])),
+        }
+    }
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    for (recipient, objects) in grouped {
+        let objects = objects
+            .into_iter()
+            .map(|object| builder.obj(ObjectArg::ImmOrOwnedObject(object)))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(ptb_error)?;
+        let recipient = builder.pure(recipient).map_err(ptb_error)?;
+
+        builder.command(Command::TransferObjects(objects, recipient));
+    }
+
+    Ok(builder.finish())
+}
+
+/// The shared `0x6::clock::Clock`, passed by immutable reference
+pub(crate) fn clock_object() -> ObjectArg {
+    ObjectArg::SharedObject {
+        id: SUI_CLOCK_OBJECT_ID,
+        initial_shared_version: SUI_CLOCK_OBJECT_SHARED_VERSION,
+        mutable: false,
+    }
+}
+
+/// Refers to the `index`-th value returned by a command yielding a tuple
+pub(crate) fn nested_result(result: Argument, index: u16) -> Result<Argument> {
+    match result {
+        Argument::Result(command) => Ok(Argument::NestedResult(command, index)),
+        _ => Err(ServiceError::Service(
+            "Expected a command result argument".to_string(),
+        )),
+    }
+}
+
+/// `0x2::transfer_policy::TransferPolicy<T>` for the given item type
+fn transfer_policy_type(item_type: &TypeTag) -> Result<TypeTag> {
+    parse_type_tag(&format!(
+        "0x2::transfer_policy::TransferPolicy<{}>",
+        item_type.to_canonical_string(true)
+    ))
+}
+
+/// Maps errors raised while adding PTB inputs or identifiers
+pub(crate) fn ptb_error(e: impl std::fmt::Display) -> ServiceError {
+    ServiceError::Service(format!("Failed to build transaction: {}", e))
+}
+
+/// Reads a u64 that the JSON-RPC may encode either as a number or a string
+fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|value| value.parse().ok()))
+}
+
+pub(crate) fn unix_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
 }
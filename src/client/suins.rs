@@ -0,0 +1,194 @@
+use std::path::PathBuf;
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use sui_sdk::types::{
+    Identifier,
+    base_types::{ObjectID, ObjectRef},
+    transaction::{Argument, Command, ObjectArg, TransactionData},
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::squad_connect::{SquadConnect, account_address, clock_object, nested_result, ptb_error};
+
+/// Price multiplier SuiNS applies to 3-character names
+const THREE_CHAR_MULTIPLIER: u64 = 25;
+
+/// Price multiplier SuiNS applies to 4-character names
+const FOUR_CHAR_MULTIPLIER: u64 = 5;
+
+/// SuiNS deployment to register names against
+///
+/// SuiNS upgrades its packages independently of this crate, so the IDs are
+/// supplied by the caller from the SuiNS documentation for their network.
+#[derive(Debug, Clone)]
+pub struct SuinsConfig {
+    /// Package with the `register` module
+    pub registration_package_id: ObjectID,
+    /// Package with the `controller` module
+    pub controller_package_id: ObjectID,
+    /// The shared `SuiNS` object
+    pub suins_object_id: ObjectID,
+    /// Yearly price of a name of 5 characters or more
+    pub price_per_year_mist: u64,
+}
+
+/// Cost in MIST of registering `name` for `years`
+///
+/// `price_per_year_mist` is the price of a name of 5 characters or more;
+/// shorter names are charged SuiNS's higher 3- and 4-character tiers.
+pub fn estimate_suins_registration_cost(name: &str, years: u8, price_per_year_mist: u64) -> u64 {
+    let multiplier = match name.trim_end_matches(".sui").len() {
+        3 => THREE_CHAR_MULTIPLIER,
+        4 => FOUR_CHAR_MULTIPLIER,
+        _ => 1,
+    };
+
+    price_per_year_mist
+        .saturating_mul(multiplier)
+        .saturating_mul(u64::from(years))
+}
+
+impl SquadConnect {
+    /// Registers `name.sui` for `years` and sends the registration NFT to `account`
+    ///
+    /// The fee is split from the gas coin, so the account needs enough SUI
+    /// for both. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn register_suins_name(
+        &self,
+        config: &SuinsConfig,
+        name: &str,
+        years: u8,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let domain = suins_domain(name)?;
+        let sender = account_address(&account)?;
+        let cost = estimate_suins_registration_cost(name, years, config.price_per_year_mist);
+        let suins = self
+            .get_shared_object_arg(config.suins_object_id, true)
+            .await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let suins = builder.obj(suins).map_err(ptb_error)?;
+                let domain = builder.pure(domain).map_err(ptb_error)?;
+                let years = builder.pure(years).map_err(ptb_error)?;
+                let cost = builder.pure(cost).map_err(ptb_error)?;
+                let clock = builder.obj(clock_object()).map_err(ptb_error)?;
+
+                let split = builder.command(Command::SplitCoins(Argument::GasCoin, vec![cost]));
+                let payment = nested_result(split, 0)?;
+
+                let registration = builder.programmable_move_call(
+                    config.registration_package_id,
+                    Identifier::new("register").map_err(ptb_error)?,
+                    Identifier::new("register").map_err(ptb_error)?,
+                    vec![],
+                    vec![suins, domain, years, payment, clock],
+                );
+
+                builder.transfer_arg(sender, registration);
+
+                Ok(())
+            })
+            .await?;
+
+        self.execute_for_digest(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    /// Makes `name.sui` the default name of `account`
+    ///
+    /// Points the name at the account using its registration NFT
+    /// `suins_cap`, then sets the reverse record so the account resolves
+    /// back to the name. Returns the transaction digest.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn set_default_name(
+        &self,
+        config: &SuinsConfig,
+        suins_cap: ObjectRef,
+        name: &str,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let domain = suins_domain(name)?;
+        let sender = account_address(&account)?;
+        let suins = self
+            .get_shared_object_arg(config.suins_object_id, true)
+            .await?;
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let suins = builder.obj(suins).map_err(ptb_error)?;
+                let registration = builder
+                    .obj(ObjectArg::ImmOrOwnedObject(suins_cap))
+                    .map_err(ptb_error)?;
+                let target = builder.pure(Some(sender)).map_err(ptb_error)?;
+                let domain = builder.pure(domain).map_err(ptb_error)?;
+                let clock = builder.obj(clock_object()).map_err(ptb_error)?;
+
+                builder.programmable_move_call(
+                    config.controller_package_id,
+                    Identifier::new("controller").map_err(ptb_error)?,
+                    Identifier::new("set_target_address").map_err(ptb_error)?,
+                    vec![],
+                    vec![suins, registration, target, clock],
+                );
+
+                builder.programmable_move_call(
+                    config.controller_package_id,
+                    Identifier::new("controller").map_err(ptb_error)?,
+                    Identifier::new("set_reverse_lookup").map_err(ptb_error)?,
+                    vec![],
+                    vec![suins, domain],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        self.execute_for_digest(tx_data, zk_inputs, max_epoch, path)
+            .await
+    }
+
+    async fn execute_for_digest(
+        &self,
+        tx_data: TransactionData,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<String> {
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+            .await?;
+
+        Ok(response.digest.to_string())
+    }
+}
+
+/// Validates a SuiNS label and returns the full `.sui` domain
+fn suins_domain(name: &str) -> Result<String> {
+    let valid = (3..=63).contains(&name.len())
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-');
+
+    if !valid {
+        return Err(ServiceError::Service(
+            "Invalid SuiNS name format".to_string(),
+        ));
+    }
+
+    Ok(format!("{}.sui", name))
+}
@@ -0,0 +1,211 @@
+use std::path::PathBuf;
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use sui_sdk::{
+    rpc_types::{SuiObjectDataOptions, SuiTransactionBlockEffectsAPI},
+    types::{
+        Identifier, SUI_FRAMEWORK_PACKAGE_ID,
+        base_types::{ObjectID, ObjectRef},
+        move_package::MovePackage,
+        transaction::ObjectArg,
+    },
+};
+
+use crate::service::{
+    dtos::AccountResponse,
+    types::{Result, ServiceError},
+};
+
+use super::{
+    move_package::published_package_id,
+    squad_connect::{
+        SquadConnect, account_address, move_object_fields, parse_object_id, ptb_error,
+    },
+};
+
+/// How far later upgrades of a package may go, as stored in its `UpgradeCap`
+///
+/// Each policy is stricter than the one before it; a cap can only be
+/// restricted further, never relaxed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum UpgradePolicy {
+    /// Any change keeping public signatures and struct layouts
+    Compatible = 0,
+    /// Only new functions and types
+    Additive = 128,
+    /// Only dependency changes
+    Dep = 192,
+}
+
+impl From<UpgradePolicy> for u8 {
+    fn from(policy: UpgradePolicy) -> Self {
+        policy as u8
+    }
+}
+
+impl TryFrom<u8> for UpgradePolicy {
+    type Error = ServiceError;
+
+    fn try_from(policy: u8) -> Result<Self> {
+        match policy {
+            0 => Ok(UpgradePolicy::Compatible),
+            128 => Ok(UpgradePolicy::Additive),
+            192 => Ok(UpgradePolicy::Dep),
+            _ => Err(ServiceError::Service(format!(
+                "Invalid upgrade policy: {}",
+                policy
+            ))),
+        }
+    }
+}
+
+/// Outcome of `upgrade_package_with_migration`
+#[derive(Debug, Clone)]
+pub struct UpgradeResult {
+    /// ID of the new package version
+    pub package_id: ObjectID,
+    /// Digest of the upgrade transaction
+    pub digest: String,
+    /// Objects changed by the migration call, besides the gas coin
+    pub migrated_objects: usize,
+}
+
+impl SquadConnect {
+    /// Upgrades the package of `upgrade_cap` and optionally migrates its state
+    ///
+    /// The upgrade is authorized with `policy`, one of the `UpgradePolicy`
+    /// bytes, then committed back into the cap in the same transaction.
+    /// `migration_fn` is `(object, module, function)`: once the upgrade is
+    /// executed, `module::function(&mut object)` of the new package is
+    /// called on the shared `object`. Sui does not let a transaction call a
+    /// package it upgrades, so the migration is a second transaction; if it
+    /// fails, the upgrade stays in place and the error is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upgrade_package_with_migration(
+        &mut self,
+        compiled_modules: Vec<Vec<u8>>,
+        dependencies: Vec<ObjectID>,
+        upgrade_cap: ObjectRef,
+        policy: u8,
+        migration_fn: Option<(ObjectID, String, String)>,
+        gas_budget: u64,
+        account: AccountResponse,
+        zk_inputs: ZkLoginInputs,
+        max_epoch: u64,
+        path: PathBuf,
+    ) -> Result<UpgradeResult> {
+        let policy = u8::from(UpgradePolicy::try_from(policy)?);
+        let sender = account_address(&account)?;
+        let current_package = self.upgrade_cap_package(upgrade_cap.0).await?;
+        let digest = MovePackage::compute_digest_for_modules_and_deps(
+            &compiled_modules,
+            &dependencies,
+            true,
+        )
+        .to_vec();
+
+        let tx_data = self
+            .build_programmable_batch(sender, gas_budget, |builder| {
+                let cap = builder
+                    .obj(ObjectArg::ImmOrOwnedObject(upgrade_cap))
+                    .map_err(ptb_error)?;
+                let policy = builder.pure(policy).map_err(ptb_error)?;
+                let digest = builder.pure(digest).map_err(ptb_error)?;
+
+                let ticket = builder.programmable_move_call(
+                    SUI_FRAMEWORK_PACKAGE_ID,
+                    Identifier::new("package").map_err(ptb_error)?,
+                    Identifier::new("authorize_upgrade").map_err(ptb_error)?,
+                    vec![],
+                    vec![cap, policy, digest],
+                );
+                let receipt =
+                    builder.upgrade(current_package, ticket, dependencies, compiled_modules);
+
+                builder.programmable_move_call(
+                    SUI_FRAMEWORK_PACKAGE_ID,
+                    Identifier::new("package").map_err(ptb_error)?,
+                    Identifier::new("commit_upgrade").map_err(ptb_error)?,
+                    vec![],
+                    vec![cap, receipt],
+                );
+
+                Ok(())
+            })
+            .await?;
+
+        let response = self
+            .sign_and_execute_transaction(tx_data, zk_inputs.clone(), max_epoch, path.clone())
+            .await?;
+        // Sui reports the new version of an upgraded package as published
+        let package_id = published_package_id(&response)?;
+
+        let migrated_objects = match migration_fn {
+            Some((object_id, module, function)) => {
+                let object = self.get_shared_object_arg(object_id, true).await?;
+
+                let tx_data = self
+                    .build_programmable_batch(sender, gas_budget, |builder| {
+                        let object = builder.obj(object).map_err(ptb_error)?;
+
+                        builder.programmable_move_call(
+                            package_id,
+                            Identifier::new(module).map_err(ptb_error)?,
+                            Identifier::new(function).map_err(ptb_error)?,
+                            vec![],
+                            vec![object],
+                        );
+
+                        Ok(())
+                    })
+                    .await?;
+
+                let response = self
+                    .sign_and_execute_transaction(tx_data, zk_inputs, max_epoch, path)
+                    .await?;
+                let effects = response.effects.ok_or_else(|| {
+                    ServiceError::InvalidResponse("Transaction response has no effects".to_string())
+                })?;
+                let gas_object = effects.gas_object().reference.object_id;
+
+                effects
+                    .mutated()
+                    .iter()
+                    .filter(|object| object.reference.object_id != gas_object)
+                    .count()
+            }
+            None => 0,
+        };
+
+        Ok(UpgradeResult {
+            package_id,
+            digest: response.digest.to_string(),
+            migrated_objects,
+        })
+    }
+
+    /// Package an `UpgradeCap` currently upgrades
+    async fn upgrade_cap_package(&self, upgrade_cap_id: ObjectID) -> Result<ObjectID> {
+        let response = self
+            .get_node()
+            .read_api()
+            .get_object_with_options(upgrade_cap_id, SuiObjectDataOptions::new().with_content())
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to get object: {}", e)))?;
+
+        let fields = response
+            .data
+            .as_ref()
+            .and_then(move_object_fields)
+            .ok_or_else(|| {
+                ServiceError::InvalidResponse(format!("Object {} not found", upgrade_cap_id))
+            })?;
+
+        let package = fields["package"].as_str().ok_or_else(|| {
+            ServiceError::InvalidResponse("UpgradeCap has no package field".to_string())
+        })?;
+
+        parse_object_id(package)
+    }
+}
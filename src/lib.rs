@@ -3,3 +3,4 @@ extern crate serde;
 
 pub mod client;
 pub mod service;
+pub mod utils;
@@ -2,6 +2,8 @@ use fastcrypto::encoding::Base64;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use super::types::{Result, ServiceError};
+
 #[derive(Debug, Clone)]
 pub enum Network {
     Devnet,
@@ -9,6 +11,53 @@ pub enum Network {
     Mainnet,
 }
 
+/// OAuth providers Enoki can issue zkLogin accounts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OauthProvider {
+    Google,
+    Facebook,
+    Twitch,
+    Apple,
+}
+
+impl OauthProvider {
+    /// Provider identifier as used in Enoki's app configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OauthProvider::Google => "google",
+            OauthProvider::Facebook => "facebook",
+            OauthProvider::Twitch => "twitch",
+            OauthProvider::Apple => "apple",
+        }
+    }
+
+    /// Whether `iss` is a JWT issuer this provider signs tokens as
+    pub fn is_issuer(&self, iss: &str) -> bool {
+        match self {
+            OauthProvider::Google => {
+                iss == "https://accounts.google.com" || iss == "accounts.google.com"
+            }
+            OauthProvider::Facebook => iss == "https://www.facebook.com",
+            OauthProvider::Twitch => iss == "https://id.twitch.tv/oauth2",
+            OauthProvider::Apple => iss == "https://appleid.apple.com",
+        }
+    }
+}
+
+impl fmt::Display for OauthProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Production Enoki API base URL
+pub const ENOKI_BASE_URL: &str = "https://api.enoki.mystenlabs.com/v1";
+
+/// Chain identifier reported by Sui mainnet nodes
+pub const MAINNET_CHAIN_IDENTIFIER: &str = "35834a8a";
+/// Chain identifier reported by Sui testnet nodes
+pub const TESTNET_CHAIN_IDENTIFIER: &str = "4c78adac";
+
 #[derive(Debug)]
 pub enum EnokiEndpoints {
     Nonce,
@@ -16,6 +65,8 @@ pub enum EnokiEndpoints {
     ZkProof,
     CreateSponsorTransaction,
     SubmitSponsorTransaction(String),
+    App,
+    AccountSearch,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -33,6 +84,27 @@ pub struct NonceResponse {
     pub estimated_expiration: u64,
 }
 
+/// The nonce of the current session and the parameters it commits to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NonceParams {
+    pub nonce: String,
+    pub randomness: String,
+    /// Ephemeral public key, flagged and base64 encoded
+    pub public_key: String,
+    pub max_epoch: u64,
+}
+
+impl NonceParams {
+    /// Whether a session was started: every field is set
+    pub fn is_valid(&self) -> bool {
+        !self.nonce.is_empty()
+            && !self.randomness.is_empty()
+            && !self.public_key.is_empty()
+            && self.max_epoch > 0
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NoncePayload {
@@ -87,6 +159,40 @@ pub struct SubmitSponsorTransactionResponse {
     pub digest: String,
 }
 
+/// Salt and subject of a zkLogin account, as returned by an account search
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSearchResponse {
+    pub salt: String,
+    pub sub: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationProvider {
+    pub provider_type: String,
+    pub client_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppResponse {
+    pub authentication_providers: Vec<AuthenticationProvider>,
+}
+
+/// Result of dry-running a transaction before sponsoring it
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SponsorCostEstimate {
+    /// Computation plus storage cost reported by the dry run
+    pub gas_used_mist: u64,
+    /// Reference gas price of the current epoch
+    pub gas_price: u64,
+    /// Net cost after the storage rebate
+    pub total_cost_mist: u64,
+    /// Whether the transaction's gas budget covers both the dry-run cost and the network minimum
+    pub budget_sufficient: bool,
+}
+
 impl fmt::Display for Network {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -108,24 +214,78 @@ impl From<String> for Network {
     }
 }
 
-impl fmt::Display for EnokiEndpoints {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let base_url = String::from("https://api.enoki.mystenlabs.com/v1");
+impl Network {
+    /// Maps a node's chain identifier to the Enoki network it belongs to
+    ///
+    /// Devnet is wiped regularly and has no stable identifier, so it cannot
+    /// be recognized; any identifier other than mainnet's or testnet's fails
+    /// with `ServiceError::Service`.
+    pub fn from_chain_identifier(chain_identifier: &str) -> Result<Self> {
+        match chain_identifier {
+            MAINNET_CHAIN_IDENTIFIER => Ok(Network::Mainnet),
+            TESTNET_CHAIN_IDENTIFIER => Ok(Network::Testnet),
+            _ => Err(ServiceError::Service(format!(
+                "Unknown chain identifier {}; pass the network explicitly",
+                chain_identifier
+            ))),
+        }
+    }
 
+    /// Block explorer links point to unless another one is configured
+    ///
+    /// The explorer serves every network; the network is selected with a
+    /// `network` query parameter.
+    pub fn default_explorer_url(&self) -> &'static str {
         match self {
-            EnokiEndpoints::Nonce => write!(f, "{}/zklogin/nonce", base_url),
-            EnokiEndpoints::Address => write!(f, "{}/zklogin", base_url),
-            EnokiEndpoints::ZkProof => write!(f, "{}/zklogin/zkp", base_url),
+            Network::Devnet | Network::Testnet | Network::Mainnet => "https://suiexplorer.com",
+        }
+    }
+
+    /// Package ID of Mysten's Kiosk rules (royalty, lock, personal kiosk) on this network
+    ///
+    /// Devnet is reset regularly, so no stable deployment exists there.
+    pub fn kiosk_rules_package(&self) -> Option<&'static str> {
+        match self {
+            Network::Mainnet => {
+                Some("0x434b5bd8f6a7b05fede0ff46c6e511d71ea326ed38056e3bcd681d2d7c2a7879")
+            }
+            Network::Testnet => {
+                Some("0xbd8fc1947cf119350184107a3087e2dc27efefa0dd82e25a1f699069fe81a585")
+            }
+            Network::Devnet => None,
+        }
+    }
+}
+
+impl EnokiEndpoints {
+    /// Builds the endpoint URL against a custom Enoki deployment
+    ///
+    /// `base_url` should include the API version, e.g. `http://localhost:8080/v1`.
+    pub fn url(&self, base_url: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
+
+        match self {
+            EnokiEndpoints::Nonce => format!("{}/zklogin/nonce", base_url),
+            EnokiEndpoints::Address => format!("{}/zklogin", base_url),
+            EnokiEndpoints::ZkProof => format!("{}/zklogin/zkp", base_url),
             EnokiEndpoints::CreateSponsorTransaction => {
-                write!(f, "{}/transaction-blocks/sponsor", base_url)
+                format!("{}/transaction-blocks/sponsor", base_url)
             }
             EnokiEndpoints::SubmitSponsorTransaction(digest) => {
-                write!(f, "{}/transaction-blocks/sponsor/{}", base_url, digest)
+                format!("{}/transaction-blocks/sponsor/{}", base_url, digest)
             }
+            EnokiEndpoints::App => format!("{}/app", base_url),
+            EnokiEndpoints::AccountSearch => format!("{}/zklogin/search", base_url),
         }
     }
 }
 
+impl fmt::Display for EnokiEndpoints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.url(ENOKI_BASE_URL))
+    }
+}
+
 impl From<(String, String, u64)> for NoncePayload {
     fn from(nonce_payload: (String, String, u64)) -> Self {
         let (network, ephemeral_public_key, additional_epochs) = nonce_payload;
@@ -1,4 +1,5 @@
 pub mod types;
 pub mod dtos;
 pub mod services;
-
+pub mod tls;
+pub mod zkp;
@@ -1,30 +1,66 @@
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
 
 use super::{
     dtos::{
-        AccountResponse, EnokiEndpoints, Network, NoncePayload, NonceResponse, ResponseData,
+        AccountResponse, AccountSearchResponse, AppResponse, ENOKI_BASE_URL, EnokiEndpoints,
+        Network, NonceParams, NoncePayload, NonceResponse, ResponseData, SponsorCostEstimate,
         SponsorTransactionPayload, SponsorTransactionResponse, SubmitSponsorTransactionPayload,
         SubmitSponsorTransactionResponse, ZKPPayload,
     },
     types::{GoogleOauthProvider, Result, ServiceError},
+    zkp::compute_address_with_salt,
+};
+use crate::{
+    client::resilient::ResilientSuiClient,
+    utils::{
+        address::{validate_allowed_addresses, validate_move_call_target},
+        epoch::unix_ms_to_system_time,
+    },
 };
 use async_trait::async_trait;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use fastcrypto::encoding::Base64;
 use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
 use jwt_simple::reexports::rand::{Rng, SeedableRng, rngs::StdRng, thread_rng};
 use reqwest::{
-    Client,
-    header::{HeaderMap, HeaderValue},
+    Client, Response, StatusCode,
+    header::{CONTENT_TYPE, HeaderMap, HeaderValue},
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore};
 use sui_sdk::{
     SuiClient,
+    rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI},
     types::{
         base_types::SuiAddress,
         crypto::{AccountKeyPair, EncodeDecodeBase64, KeypairTraits, SuiKeyPair},
-        transaction::Transaction,
+        transaction::{Transaction, TransactionDataAPI},
     },
 };
+use tokio::sync::RwLock;
+use zeroize::Zeroize;
+
+const GOOGLE_JWKS_URL: &str = "https://www.googleapis.com/oauth2/v3/certs";
+const GOOGLE_ISSUER: &str = "https://accounts.google.com";
+const GOOGLE_JWKS_TTL: Duration = Duration::from_secs(60 * 60);
+const OAUTH_PROVIDERS_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Smallest gas budget the network accepts, in gas units
+pub const MIN_GAS_UNITS: u64 = 1_000;
+
+const DEFAULT_MAX_RESPONSE_SIZE_BYTES: u64 = 1024 * 1024;
+const DEFAULT_MAX_REQUEST_SIZE_BYTES: u64 = 512 * 1024;
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+static GOOGLE_JWKS: RwLock<Option<(Vec<Jwk>, Instant)>> = RwLock::const_new(None);
 
 /// Squad Connect Services
 ///
@@ -59,8 +95,8 @@ use sui_sdk::{
 
 #[derive(Clone)]
 pub struct Services {
-    /// Sui blockchain client for network operations
-    node: SuiClient,
+    /// Sui blockchain client for network operations, with any fallback endpoints
+    node: ResilientSuiClient,
     /// Target network (Devnet, Testnet, or Mainnet)
     network: Network,
     /// Enoki API key for zkLogin services
@@ -75,6 +111,28 @@ pub struct Services {
     max_epoch: u64,
     /// OAuth nonce for authentication
     nonce: String,
+    /// When Enoki expects the nonce to expire, in Unix milliseconds
+    estimated_expiration: Option<u64>,
+    /// JWT of the signed-in user
+    jwt: String,
+    /// Proof returned by `zk_proof_stored` for the current JWT
+    zk_proof_cache: Arc<std::sync::RwLock<Option<ZkLoginInputs>>>,
+    /// HTTP client used for Enoki requests
+    http_client: Client,
+    /// Largest Enoki response body that will be read
+    max_response_size_bytes: u64,
+    /// Largest serialized sponsor transaction payload that will be sent
+    max_request_size_bytes: u64,
+    /// How long each Enoki request of a batch call may take
+    request_timeout: Duration,
+    /// Base URL of the Enoki API, including the version segment
+    enoki_base_url: String,
+    /// Dry-run transactions before asking Enoki to sponsor them
+    dry_run: bool,
+    /// OAuth providers enabled for the Enoki app, cached for one hour
+    oauth_providers: Arc<RwLock<Option<(Vec<String>, Instant)>>>,
+    /// Cleared when Enoki rejects the API key, so later calls fail fast
+    api_key_valid: Arc<AtomicBool>,
 }
 
 impl Services {
@@ -97,7 +155,7 @@ impl Services {
     /// ```
     pub fn new(node: SuiClient, network: Network, api_key: String, client_id: String) -> Self {
         Self {
-            node,
+            node: ResilientSuiClient::new(node),
             api_key,
             network,
             client_id,
@@ -105,7 +163,304 @@ impl Services {
             public_key: String::from(""),
             max_epoch: 0,
             nonce: String::from(""),
+            estimated_expiration: None,
+            jwt: String::new(),
+            zk_proof_cache: Arc::default(),
+            http_client: Client::new(),
+            max_response_size_bytes: DEFAULT_MAX_RESPONSE_SIZE_BYTES,
+            max_request_size_bytes: DEFAULT_MAX_REQUEST_SIZE_BYTES,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            enoki_base_url: ENOKI_BASE_URL.to_string(),
+            dry_run: false,
+            oauth_providers: Arc::new(RwLock::new(None)),
+            api_key_valid: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Points Enoki requests at a self-hosted or staging deployment
+    ///
+    /// # Example
+    /// ```rust
+    /// let services = Services::new(sui_client, Network::Testnet, api_key, client_id)
+    ///     .with_enoki_base_url("http://localhost:8080/v1".to_string());
+    /// ```
+    pub fn with_enoki_base_url(mut self, enoki_base_url: String) -> Self {
+        self.enoki_base_url = enoki_base_url;
+        self
+    }
+
+    /// Sends Sui requests through `node`, which can fall over between fullnodes
+    ///
+    /// # Example
+    /// ```rust
+    /// let node = ResilientSuiClient::connect(
+    ///     &NetworkConfig::new(primary_url).with_fallback_rpc(vec![fallback_url]),
+    /// )
+    /// .await?;
+    /// let services = Services::new(sui_client, Network::Testnet, api_key, client_id)
+    ///     .with_node(node);
+    /// ```
+    pub fn with_node(mut self, node: ResilientSuiClient) -> Self {
+        self.node = node;
+        self
+    }
+
+    /// Replaces the HTTP client used for Enoki requests
+    ///
+    /// Use together with [`super::tls::pinned_client`] to pin the Enoki
+    /// TLS certificate.
+    pub fn with_http_client(mut self, http_client: Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Sets the largest Enoki response body that will be read (default 1 MB)
+    pub fn with_max_response_size_bytes(mut self, max_response_size_bytes: u64) -> Self {
+        self.max_response_size_bytes = max_response_size_bytes;
+        self
+    }
+
+    /// Sets the largest sponsor transaction payload that will be sent (default 512 KB)
+    pub fn with_max_request_size_bytes(mut self, max_request_size_bytes: u64) -> Self {
+        self.max_request_size_bytes = max_request_size_bytes;
+        self
+    }
+
+    /// Sets how long each Enoki request of a batch call may take (default 30 seconds)
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Dry-runs every transaction before it is sent to Enoki for sponsorship
+    ///
+    /// Failing or under-budgeted transactions are rejected locally with
+    /// `ServiceError::DryRunFailed` instead of consuming Enoki quota.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Estimates the gas cost of a transaction without executing it
+    ///
+    /// # Arguments
+    /// * `transaction` - The transaction that will be sponsored
+    /// * `sender` - Expected sender; must match the transaction's sender
+    ///
+    /// # Returns
+    /// Gas usage from the dry run compared against the transaction's gas budget
+    /// and the network minimum (`MIN_GAS_UNITS` at the reference gas price)
+    pub async fn estimate_sponsor_cost(
+        &self,
+        transaction: &Transaction,
+        sender: SuiAddress,
+    ) -> Result<SponsorCostEstimate> {
+        let tx_data = transaction.transaction_data().clone();
+
+        if tx_data.sender() != sender {
+            return Err(ServiceError::DryRunFailed(format!(
+                "Transaction sender {} does not match {}",
+                tx_data.sender(),
+                sender
+            )));
+        }
+
+        let gas_budget = tx_data.gas_budget();
+
+        let gas_price = self
+            .node
+            .read_api()
+            .get_reference_gas_price()
+            .await
+            .map_err(|e| {
+                ServiceError::Network(format!("Failed to get reference gas price: {}", e))
+            })?;
+
+        let dry_run = self
+            .node
+            .read_api()
+            .dry_run_transaction_block(tx_data)
+            .await
+            .map_err(|e| ServiceError::DryRunFailed(e.to_string()))?;
+
+        if let SuiExecutionStatus::Failure { error } = dry_run.effects.status() {
+            return Err(ServiceError::DryRunFailed(error.clone()));
+        }
+
+        let gas_cost = dry_run.effects.gas_cost_summary();
+        let gas_used_mist = gas_cost.gas_used();
+        let total_cost_mist = gas_cost.net_gas_usage().max(0) as u64;
+        let minimum_budget = gas_price * MIN_GAS_UNITS;
+
+        Ok(SponsorCostEstimate {
+            gas_used_mist,
+            gas_price,
+            total_cost_mist,
+            budget_sufficient: gas_budget >= gas_used_mist.max(minimum_budget),
+        })
+    }
+
+    /// Looks up the zkLogin address of a Google user by email
+    ///
+    /// Enoki does not document an account search API; this targets a
+    /// `GET /zklogin/search?email=` endpoint returning the user's salt and
+    /// `sub` claim, and treats a 404 as "no such user". The address is then
+    /// derived offline from the salt, `sub`, this app's client ID and
+    /// Google's issuer. Users are only indexed by email when the OAuth
+    /// request asked for the `email` scope.
+    ///
+    /// # Arguments
+    /// * `email` - Email claim of the Google account
+    ///
+    /// # Returns
+    /// The zkLogin address, or `None` if no account matches
+    pub async fn get_zklogin_address_by_email(&self, email: &str) -> Result<Option<SuiAddress>> {
+        self.ensure_api_key_valid()?;
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
+        );
+
+        let search_response = self
+            .http_client
+            .get(EnokiEndpoints::AccountSearch.url(&self.enoki_base_url))
+            .query(&[("email", email)])
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
+
+        if search_response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let search_response = self
+            .check_enoki_status(search_response, "Account search")
+            .await?;
+
+        let search_data: ResponseData<AccountSearchResponse> =
+            self.read_json(search_response).await?;
+        let account = search_data.data;
+
+        compute_address_with_salt(&account.sub, &account.salt, GOOGLE_ISSUER, &self.client_id)
+            .map(Some)
+    }
+
+    /// Reads a JSON response body, refusing bodies above `max_response_size_bytes`
+    async fn read_json<T: DeserializeOwned>(&self, response: Response) -> Result<T> {
+        let body = read_body(response, self.max_response_size_bytes).await?;
+
+        serde_json::from_slice(&body)
+            .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))
+    }
+
+    /// Passes successful Enoki responses through and maps the others to errors
+    ///
+    /// 401 becomes `ServiceError::Unauthorized` and marks the API key as
+    /// invalid, 403 becomes `ServiceError::Forbidden`; any other failure
+    /// is a `ServiceError::Network`. `request` names the call in messages.
+    async fn check_enoki_status(&self, response: Response, request: &str) -> Result<Response> {
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
         }
+
+        let error_body = read_error_body(response, self.max_response_size_bytes).await;
+        let message = format!(
+            "{} request failed with status {}: {}",
+            request, status, error_body
+        );
+
+        Err(match status {
+            StatusCode::UNAUTHORIZED => {
+                self.api_key_valid.store(false, Ordering::Relaxed);
+                ServiceError::Unauthorized(message)
+            }
+            StatusCode::FORBIDDEN => ServiceError::Forbidden(message),
+            _ => ServiceError::Network(message),
+        })
+    }
+
+    /// Asks Enoki to sponsor the transaction kind, with allow-lists already validated
+    async fn request_sponsor_transaction(
+        &self,
+        transaction_kind_bytes: Base64,
+        sender: SuiAddress,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<SponsorTransactionResponse> {
+        self.ensure_api_key_valid()?;
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
+        );
+
+        let sponsor_transaction_payload = SponsorTransactionPayload::from((
+            self.network.to_string(),
+            transaction_kind_bytes,
+            sender.to_string(),
+            allowed_addresses,
+            allowed_move_call_targets,
+        ));
+
+        let sponsor_transaction_body = serde_json::to_vec(&sponsor_transaction_payload)
+            .map_err(|e| ServiceError::Service(format!("Failed to serialize payload: {}", e)))?;
+
+        if sponsor_transaction_body.len() as u64 > self.max_request_size_bytes {
+            return Err(ServiceError::InvalidResponse(
+                "Request exceeded size limit".to_string(),
+            ));
+        }
+
+        let sponsor_transaction_response = self
+            .http_client
+            .post(EnokiEndpoints::CreateSponsorTransaction.url(&self.enoki_base_url))
+            .headers(headers)
+            .header(CONTENT_TYPE, "application/json")
+            .body(sponsor_transaction_body)
+            .send()
+            .await
+            .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
+
+        let sponsor_transaction_response = self
+            .check_enoki_status(sponsor_transaction_response, "Sponsor transaction")
+            .await?;
+
+        let sponsor_transaction_data: ResponseData<SponsorTransactionResponse> =
+            self.read_json(sponsor_transaction_response).await?;
+
+        Ok(sponsor_transaction_data.data)
+    }
+
+    /// Fails with `ServiceError::Unauthorized` once Enoki has rejected the API key
+    fn ensure_api_key_valid(&self) -> Result<()> {
+        if self.is_api_key_valid() {
+            Ok(())
+        } else {
+            Err(ServiceError::Unauthorized(
+                "Enoki rejected the API key".to_string(),
+            ))
+        }
+    }
+
+    /// Whether the API key has not been rejected by Enoki yet
+    ///
+    /// A 401 from any Enoki call clears this, after which Enoki calls fail
+    /// with `ServiceError::Unauthorized` without reaching the network.
+    pub fn is_api_key_valid(&self) -> bool {
+        self.api_key_valid.load(Ordering::Relaxed)
+    }
+
+    /// Lets Enoki calls through again after the API key was rejected
+    pub fn reset_api_key_status(&mut self) {
+        self.api_key_valid.store(true, Ordering::Relaxed);
     }
 
     /// Returns a reference to the Sui client
@@ -115,6 +470,286 @@ impl Services {
     pub fn get_node(&self) -> &SuiClient {
         &self.node
     }
+
+    /// Returns the failover-aware client behind `get_node`
+    pub fn get_resilient_node(&self) -> &ResilientSuiClient {
+        &self.node
+    }
+
+    /// Returns the network the service targets
+    pub fn get_network(&self) -> &Network {
+        &self.network
+    }
+
+    /// Points the service at another network
+    ///
+    /// Nonce, randomness, ephemeral public key and max epoch are tied to the
+    /// network they were issued for, so they are cleared as well, along with
+    /// the cached OAuth providers. Fallback endpoints belong to the old
+    /// network and are replaced by those of `node`; pass a plain `SuiClient`
+    /// to go without fallbacks.
+    ///
+    /// # Arguments
+    /// * `network` - New target network
+    /// * `node` - Sui client connected to that network
+    pub fn set_network(&mut self, network: Network, node: impl Into<ResilientSuiClient>) {
+        self.network = network;
+        self.node = node.into();
+        self.oauth_providers = Arc::new(RwLock::new(None));
+        self.reset_zkp_session();
+    }
+
+    /// The nonce from `create_zkp_payload`, empty before it has run
+    pub fn get_nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// The nonce with the randomness, ephemeral public key and max epoch it commits to
+    pub fn get_nonce_params(&self) -> NonceParams {
+        NonceParams {
+            nonce: self.nonce.clone(),
+            randomness: self.randomness.clone(),
+            public_key: self.public_key.clone(),
+            max_epoch: self.max_epoch,
+        }
+    }
+
+    /// When the current nonce is expected to expire
+    ///
+    /// Enoki estimates this from `max_epoch` when the nonce is created;
+    /// `None` until `create_zkp_payload` has run.
+    pub fn nonce_expires_at(&self) -> Option<SystemTime> {
+        self.estimated_expiration.map(unix_ms_to_system_time)
+    }
+
+    /// Time left before the current nonce expires, zero once it has
+    pub fn nonce_expires_in(&self) -> Option<Duration> {
+        self.nonce_expires_at().map(|expires_at| {
+            expires_at
+                .duration_since(SystemTime::now())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Stores the JWT of the signed-in user
+    pub fn set_jwt(&mut self, jwt: String) {
+        self.jwt = jwt;
+        self.zk_proof_cache = Arc::default();
+    }
+
+    /// The stored JWT, empty when none was set
+    pub fn get_jwt(&self) -> &str {
+        &self.jwt
+    }
+
+    /// How long each Enoki request of a batch call may take
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Zeroizes the stored JWT
+    pub fn clear_jwt(&mut self) {
+        self.jwt.zeroize();
+        self.zk_proof_cache = Arc::default();
+    }
+
+    /// Requests a zkLogin proof for the stored JWT
+    ///
+    /// The proof is cached until the JWT or the session changes, so only
+    /// the first call reaches Enoki.
+    pub async fn zk_proof_stored(&self) -> Result<ZkLoginInputs> {
+        if let Some(zk_inputs) = self.cached_zk_proof() {
+            return Ok(zk_inputs);
+        }
+
+        let zk_inputs = self.zk_proof(&self.jwt).await?;
+
+        if let Ok(mut cache) = self.zk_proof_cache.write() {
+            *cache = Some(zk_inputs.clone());
+        }
+
+        Ok(zk_inputs)
+    }
+
+    /// The proof cached by `zk_proof_stored`, if any
+    pub fn cached_zk_proof(&self) -> Option<ZkLoginInputs> {
+        self.zk_proof_cache
+            .read()
+            .ok()
+            .and_then(|cache| cache.clone())
+    }
+
+    /// Clears all zkLogin session state
+    ///
+    /// Randomness, ephemeral public key and nonce are zeroized in memory
+    /// before being emptied, and `max_epoch` is reset to 0. This is the
+    /// clean-logout primitive; `create_zkp_payload` starts a new session.
+    pub fn reset_zkp_session(&mut self) {
+        self.randomness.zeroize();
+        self.public_key.zeroize();
+        self.nonce.zeroize();
+        self.max_epoch = 0;
+        self.estimated_expiration = None;
+        self.zk_proof_cache = Arc::default();
+    }
+}
+
+/// Fetches Google's OAuth signing keys
+///
+/// The key set is cached process-wide for one hour, so repeated JWT
+/// verifications do not hit Google's certs endpoint every time.
+///
+/// # Returns
+/// The JWKs currently published by Google
+pub async fn get_google_jwks() -> Result<Vec<Jwk>> {
+    if let Some((keys, fetched_at)) = GOOGLE_JWKS.read().await.as_ref()
+        && fetched_at.elapsed() < GOOGLE_JWKS_TTL
+    {
+        return Ok(keys.clone());
+    }
+
+    let mut cache = GOOGLE_JWKS.write().await;
+
+    // Another task may have refreshed the cache while we waited for the lock
+    if let Some((keys, fetched_at)) = cache.as_ref()
+        && fetched_at.elapsed() < GOOGLE_JWKS_TTL
+    {
+        return Ok(keys.clone());
+    }
+
+    let jwks_response = Client::new()
+        .get(GOOGLE_JWKS_URL)
+        .send()
+        .await
+        .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
+
+    if !jwks_response.status().is_success() {
+        let status = jwks_response.status();
+        let error_body = read_error_body(jwks_response, DEFAULT_MAX_RESPONSE_SIZE_BYTES).await;
+        return Err(ServiceError::Network(format!(
+            "Google JWKS request failed with status {}: {}",
+            status, error_body
+        )));
+    }
+
+    let body = read_body(jwks_response, DEFAULT_MAX_RESPONSE_SIZE_BYTES).await?;
+    let jwks: JwkSet = serde_json::from_slice(&body)
+        .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+
+    *cache = Some((jwks.keys.clone(), Instant::now()));
+
+    Ok(jwks.keys)
+}
+
+/// Reads a response body, refusing bodies above `max_bytes`
+///
+/// `Content-Length` is checked up front; when it is missing the body is
+/// read chunk by chunk and abandoned as soon as it crosses the limit.
+async fn read_body(mut response: Response, max_bytes: u64) -> Result<Vec<u8>> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes)
+    {
+        return Err(ServiceError::InvalidResponse(
+            "Response exceeded size limit".to_string(),
+        ));
+    }
+
+    let mut body = Vec::new();
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ServiceError::Network(format!("Failed to read response: {}", e)))?
+    {
+        if (body.len() + chunk.len()) as u64 > max_bytes {
+            return Err(ServiceError::InvalidResponse(
+                "Response exceeded size limit".to_string(),
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Body of a failed response for error messages, within the same size limit
+async fn read_error_body(response: Response, max_bytes: u64) -> String {
+    match read_body(response, max_bytes).await {
+        Ok(body) => String::from_utf8_lossy(&body).into_owned(),
+        Err(e) => format!("Unable to read error response: {}", e),
+    }
+}
+
+/// Extracts the `id_token` from an OAuth callback URL
+///
+/// The token is looked up in the query string first and then in the
+/// fragment, where providers put it for the implicit flow.
+pub fn extract_jwt_from_callback_url(callback_url: &str) -> Result<String> {
+    callback_param(callback_url, "id_token")?
+        .ok_or_else(|| ServiceError::JwtExtraction("No id_token found in callback URL".to_string()))
+}
+
+/// Extracts and deserializes the JSON `state` from an OAuth callback URL
+///
+/// Like the token, `state` may be in the query string or the fragment.
+pub fn extract_state_from_callback_url<T: for<'de> Deserialize<'de>>(
+    callback_url: &str,
+) -> Result<Option<T>> {
+    match callback_param(callback_url, "state")? {
+        Some(state_json) => {
+            let state: T = serde_json::from_str(&state_json).map_err(|e| {
+                ServiceError::JwtExtraction(format!("Failed to deserialize state: {}", e))
+            })?;
+            Ok(Some(state))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Rejects sponsor allow-lists Enoki would refuse, before any request is made
+fn validate_allow_lists(addresses: &[String], move_call_targets: &[String]) -> Result<()> {
+    validate_allowed_addresses(addresses)?;
+
+    for target in move_call_targets {
+        validate_move_call_target(target)?;
+    }
+
+    Ok(())
+}
+
+fn callback_param(callback_url: &str, key: &str) -> Result<Option<String>> {
+    let url = url::Url::parse(callback_url)
+        .map_err(|e| ServiceError::JwtExtraction(format!("Failed to parse callback URL: {}", e)))?;
+
+    let from_query = url
+        .query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned());
+
+    Ok(from_query.or_else(|| {
+        url::form_urlencoded::parse(url.fragment().unwrap_or_default().as_bytes())
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    }))
+}
+
+/// Decodes the claims of a JWT without verifying its signature
+///
+/// Only use the result for display or routing decisions; verify the token
+/// before trusting any claim.
+pub fn decode_jwt_claims(jwt: &str) -> Result<serde_json::Value> {
+    let payload = jwt
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| ServiceError::JwtFormat("JWT has no payload segment".to_string()))?;
+
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|e| ServiceError::JwtFormat(format!("Failed to decode JWT payload: {}", e)))?;
+
+    serde_json::from_slice(&bytes)
+        .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))
 }
 
 #[async_trait]
@@ -189,21 +824,7 @@ impl GoogleOauthProvider for Services {
     /// let jwt = services.extract_jwt_from_callback(callback)?;
     /// ```
     fn extract_jwt_from_callback(&self, callback_url: &str) -> Result<String> {
-        // Parse the callback URL
-        let url = url::Url::parse(callback_url).map_err(|e| {
-            ServiceError::JwtExtraction(format!("Failed to parse callback URL: {}", e))
-        })?;
-
-        // Extract the id_token parameter
-        let id_token = url
-            .query_pairs()
-            .find(|(key, _)| key == "id_token")
-            .map(|(_, value)| value.to_string())
-            .ok_or_else(|| {
-                ServiceError::JwtExtraction("No id_token found in callback URL".to_string())
-            })?;
-
-        Ok(id_token)
+        extract_jwt_from_callback_url(callback_url)
     }
 
     /// Creates ephemeral keypair and generates nonce for zkLogin
@@ -226,6 +847,8 @@ impl GoogleOauthProvider for Services {
     /// services.create_zkp_payload(keystore_path).await?;
     /// ```
     async fn create_zkp_payload(&mut self, path: PathBuf) -> Result<()> {
+        self.ensure_api_key_valid()?;
+
         let ephemeral_key_pair = {
             let mut seed = [0u8; 32];
             thread_rng().fill(&mut seed);
@@ -255,8 +878,9 @@ impl GoogleOauthProvider for Services {
             2,
         ));
 
-        let nonce_response = Client::new()
-            .post(EnokiEndpoints::Nonce.to_string())
+        let nonce_response = self
+            .http_client
+            .post(EnokiEndpoints::Nonce.url(&self.enoki_base_url))
             .json(&payload)
             .header(
                 "Authorization",
@@ -266,15 +890,15 @@ impl GoogleOauthProvider for Services {
             .await
             .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
 
-        let nonce_data: ResponseData<NonceResponse> = nonce_response
-            .json()
-            .await
-            .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+        let nonce_response = self.check_enoki_status(nonce_response, "Nonce").await?;
+        let nonce_data: ResponseData<NonceResponse> = self.read_json(nonce_response).await?;
 
         self.randomness = nonce_data.data.randomness;
         self.public_key = ephemeral_key_pair.public().encode_base64();
         self.max_epoch = nonce_data.data.max_epoch;
         self.nonce = nonce_data.data.nonce;
+        self.estimated_expiration = Some(nonce_data.data.estimated_expiration);
+        self.zk_proof_cache = Arc::default();
 
         Ok(())
     }
@@ -296,6 +920,8 @@ impl GoogleOauthProvider for Services {
     /// println!("ZK proof generated successfully");
     /// ```
     async fn zk_proof(&self, jwt: &str) -> Result<ZkLoginInputs> {
+        self.ensure_api_key_valid()?;
+
         // Validate the JWT and extract claims
         let mut headers = HeaderMap::new();
 
@@ -312,30 +938,20 @@ impl GoogleOauthProvider for Services {
             self.randomness.clone(),
         ));
 
-        let zk_proof_response = Client::new()
-            .post(&EnokiEndpoints::ZkProof.to_string())
+        let zk_proof_response = self
+            .http_client
+            .post(EnokiEndpoints::ZkProof.url(&self.enoki_base_url))
             .headers(headers)
             .json(&zkp_payload)
             .send()
             .await
             .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
 
-        if !zk_proof_response.status().is_success() {
-            let status = zk_proof_response.status();
-            let error_body = zk_proof_response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            return Err(ServiceError::Network(format!(
-                "ZK proof request failed with status {}: {}",
-                status, error_body
-            )));
-        }
+        let zk_proof_response = self
+            .check_enoki_status(zk_proof_response, "ZK proof")
+            .await?;
 
-        let zkp_data: ResponseData<ZkLoginInputs> = zk_proof_response
-            .json()
-            .await
-            .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+        let zkp_data: ResponseData<ZkLoginInputs> = self.read_json(zk_proof_response).await?;
 
         Ok(zkp_data.data)
     }
@@ -344,29 +960,12 @@ impl GoogleOauthProvider for Services {
         &self,
         callback_url: &str,
     ) -> Result<Option<T>> {
-        // Parse the callback URL
-        let url = url::Url::parse(callback_url).map_err(|e| {
-            ServiceError::JwtExtraction(format!("Failed to parse callback URL: {}", e))
-        })?;
-
-        // Extract the state parameter
-        let state_str = url
-            .query_pairs()
-            .find(|(key, _)| key == "state")
-            .map(|(_, value)| value.to_string());
-
-        match state_str {
-            Some(state_json) => {
-                let state: T = serde_json::from_str(&state_json).map_err(|e| {
-                    ServiceError::JwtExtraction(format!("Failed to deserialize state: {}", e))
-                })?;
-                Ok(Some(state))
-            }
-            None => Ok(None),
-        }
+        extract_state_from_callback_url(callback_url)
     }
 
     async fn get_account(&self, jwt: &str) -> Result<AccountResponse> {
+        self.ensure_api_key_valid()?;
+
         let mut headers = HeaderMap::new();
 
         headers.insert(
@@ -375,32 +974,66 @@ impl GoogleOauthProvider for Services {
         );
         headers.insert("zklogin-jwt", jwt.parse().unwrap());
 
-        let account_response = Client::new()
-            .get(&EnokiEndpoints::Address.to_string())
+        let account_response = self
+            .http_client
+            .get(EnokiEndpoints::Address.url(&self.enoki_base_url))
             .headers(headers)
             .send()
             .await
             .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
 
-        // Check if the response status indicates an error
-        if !account_response.status().is_success() {
-            let status = account_response.status();
-            let error_body = account_response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            return Err(ServiceError::Network(format!(
-                "Account request failed with status {}: {}",
-                status, error_body
-            )));
+        let account_response = self.check_enoki_status(account_response, "Account").await?;
+
+        let account_data: ResponseData<AccountResponse> = self.read_json(account_response).await?;
+
+        Ok(account_data.data)
+    }
+
+    /// Lists the OAuth providers enabled for the Enoki app
+    ///
+    /// Providers are configured per Enoki app (and therefore per API key and
+    /// network). The list is cached for one hour.
+    ///
+    /// # Returns
+    /// Provider identifiers such as `"google"` or `"twitch"`
+    async fn get_supported_oauth_providers(&self) -> Result<Vec<String>> {
+        if let Some((providers, fetched_at)) = self.oauth_providers.read().await.as_ref()
+            && fetched_at.elapsed() < OAUTH_PROVIDERS_TTL
+        {
+            return Ok(providers.clone());
         }
 
-        let account_data: ResponseData<AccountResponse> = account_response
-            .json()
+        self.ensure_api_key_valid()?;
+
+        let mut headers = HeaderMap::new();
+
+        headers.insert(
+            "Authorization",
+            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
+        );
+
+        let app_response = self
+            .http_client
+            .get(EnokiEndpoints::App.url(&self.enoki_base_url))
+            .headers(headers)
+            .send()
             .await
-            .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+            .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
 
-        Ok(account_data.data)
+        let app_response = self.check_enoki_status(app_response, "App").await?;
+
+        let app_data: ResponseData<AppResponse> = self.read_json(app_response).await?;
+
+        let providers: Vec<String> = app_data
+            .data
+            .authentication_providers
+            .into_iter()
+            .map(|provider| provider.provider_type)
+            .collect();
+
+        *self.oauth_providers.write().await = Some((providers.clone(), Instant::now()));
+
+        Ok(providers)
     }
 
     fn get_zk_proof_params(&self) -> (String, String, u64) {
@@ -415,6 +1048,15 @@ impl GoogleOauthProvider for Services {
         self.randomness = randomness;
         self.public_key = public_key;
         self.max_epoch = max_epoch;
+        self.zk_proof_cache = Arc::default();
+    }
+
+    fn set_jwt(&mut self, jwt: String) {
+        Services::set_jwt(self, jwt);
+    }
+
+    fn get_jwt(&self) -> &str {
+        Services::get_jwt(self)
     }
 
     /// Creates a sponsor transaction for gasless execution
@@ -447,50 +1089,60 @@ impl GoogleOauthProvider for Services {
         allowed_addresses: Vec<String>,
         allowed_move_call_targets: Vec<String>,
     ) -> Result<SponsorTransactionResponse> {
-        let mut headers = HeaderMap::new();
+        validate_allow_lists(&allowed_addresses, &allowed_move_call_targets)?;
 
-        headers.insert(
-            "Authorization",
-            HeaderValue::from_str(&format!("Bearer {}", self.api_key)).unwrap(),
-        );
+        if self.dry_run {
+            let estimate = self.estimate_sponsor_cost(&transaction, sender).await?;
+
+            if !estimate.budget_sufficient {
+                return Err(ServiceError::DryRunFailed(format!(
+                    "Gas budget too low: dry run used {} MIST",
+                    estimate.gas_used_mist
+                )));
+            }
+        }
 
         let (tx_bytes_base64, _signatures) = transaction.to_tx_bytes_and_signatures();
 
-        let sponsor_transaction_payload = SponsorTransactionPayload::from((
-            self.network.to_string(),
+        self.request_sponsor_transaction(
             tx_bytes_base64,
-            sender.to_string(),
+            sender,
             allowed_addresses,
             allowed_move_call_targets,
-        ));
-
-        let sponsor_transaction_response = Client::new()
-            .post(&EnokiEndpoints::CreateSponsorTransaction.to_string())
-            .headers(headers)
-            .json(&sponsor_transaction_payload)
-            .send()
-            .await
-            .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
-
-        if !sponsor_transaction_response.status().is_success() {
-            let status = sponsor_transaction_response.status();
-            let error_body = sponsor_transaction_response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            return Err(ServiceError::Network(format!(
-                "Sponsor transaction request failed with status {}: {}",
-                status, error_body
-            )));
-        }
+        )
+        .await
+    }
 
-        let sponsor_transaction_data: ResponseData<SponsorTransactionResponse> =
-            sponsor_transaction_response
-                .json()
-                .await
-                .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+    /// Creates a sponsor transaction from already serialized transaction kind bytes
+    ///
+    /// Use this when only `TransactionData` is at hand: the BCS-encoded
+    /// `TransactionKind` is sent to Enoki as-is, without building a signed
+    /// `Transaction` first.
+    ///
+    /// # Arguments
+    /// * `transaction_kind_bytes` - Base64 of the BCS-encoded `TransactionKind`
+    /// * `sender` - Address of the transaction sender
+    /// * `allowed_addresses` - List of addresses allowed to interact with
+    /// * `allowed_move_call_targets` - List of allowed Move function calls
+    ///
+    /// # Returns
+    /// SponsorTransactionResponse containing digest and transaction bytes
+    async fn create_sponsor_transaction_kind(
+        &mut self,
+        transaction_kind_bytes: Base64,
+        sender: SuiAddress,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<SponsorTransactionResponse> {
+        validate_allow_lists(&allowed_addresses, &allowed_move_call_targets)?;
 
-        Ok(sponsor_transaction_data.data)
+        self.request_sponsor_transaction(
+            transaction_kind_bytes,
+            sender,
+            allowed_addresses,
+            allowed_move_call_targets,
+        )
+        .await
     }
 
     async fn submit_sponsor_transaction(
@@ -498,6 +1150,8 @@ impl GoogleOauthProvider for Services {
         digest: String,
         signature: String,
     ) -> Result<SubmitSponsorTransactionResponse> {
+        self.ensure_api_key_valid()?;
+
         let mut headers = HeaderMap::new();
 
         headers.insert(
@@ -507,31 +1161,24 @@ impl GoogleOauthProvider for Services {
 
         let submit_sponsor_transaction_payload = SubmitSponsorTransactionPayload::from(signature);
 
-        let submit_sponsor_transaction_response = Client::new()
-            .post(&EnokiEndpoints::SubmitSponsorTransaction(digest).to_string())
+        let submit_sponsor_transaction_response = self
+            .http_client
+            .post(EnokiEndpoints::SubmitSponsorTransaction(digest).url(&self.enoki_base_url))
             .headers(headers)
             .json(&submit_sponsor_transaction_payload)
             .send()
             .await
             .map_err(|e| ServiceError::Network(format!("Failed to send request: {}", e)))?;
 
-        if !submit_sponsor_transaction_response.status().is_success() {
-            let status = submit_sponsor_transaction_response.status();
-            let error_body = submit_sponsor_transaction_response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unable to read error response".to_string());
-            return Err(ServiceError::Network(format!(
-                "Submit sponsor transaction request failed with status {}: {}",
-                status, error_body
-            )));
-        }
+        let submit_sponsor_transaction_response = self
+            .check_enoki_status(
+                submit_sponsor_transaction_response,
+                "Submit sponsor transaction",
+            )
+            .await?;
 
         let submit_sponsor_transaction_data: ResponseData<SubmitSponsorTransactionResponse> =
-            submit_sponsor_transaction_response
-                .json()
-                .await
-                .map_err(|e| ServiceError::JwtFormat(format!("Failed json parse: {}", e)))?;
+            self.read_json(submit_sponsor_transaction_response).await?;
 
         Ok(submit_sponsor_transaction_data.data)
     }
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::Client;
+use rustls::{
+    ClientConfig, DigitallySignedStruct, Error as TlsError, RootCertStore, SignatureScheme,
+    client::{
+        WebPkiServerVerifier,
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    },
+    crypto::ring,
+    pki_types::{CertificateDer, ServerName, UnixTime},
+};
+use sha2::{Digest, Sha256};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use super::types::{Result, ServiceError};
+
+/// Which part of the server certificate a pin is computed over
+#[derive(Debug, Clone)]
+pub enum PinValidation {
+    /// SHA-256 of the DER-encoded SubjectPublicKeyInfo, survives re-issuance with the same key
+    PublicKey,
+    /// SHA-256 of the whole DER-encoded leaf certificate
+    Certificate,
+}
+
+/// TLS certificate pinning configuration for Enoki requests
+///
+/// `pins` holds base64-encoded SHA-256 hashes. The server chain is still
+/// validated against the bundled web PKI roots; the pin is an extra check
+/// on top of it, so a compromised CA alone is not enough to intercept
+/// traffic.
+///
+/// Pins must be updated when Enoki rotates its certificate (or its key, for
+/// `PinValidation::PublicKey`), otherwise every request will fail the
+/// handshake.
+#[derive(Debug, Clone)]
+pub struct TlsPinConfig {
+    pub pins: Vec<String>,
+    pub validation: PinValidation,
+}
+
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<[u8; 32]>,
+    validation: PinValidation,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, TlsError> {
+        self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        let hash: [u8; 32] = match self.validation {
+            PinValidation::Certificate => Sha256::digest(end_entity.as_ref()).into(),
+            PinValidation::PublicKey => {
+                let (_, certificate) =
+                    X509Certificate::from_der(end_entity.as_ref()).map_err(|e| {
+                        TlsError::General(format!("Failed to parse server certificate: {}", e))
+                    })?;
+                Sha256::digest(certificate.public_key().raw).into()
+            }
+        };
+
+        if self.pins.contains(&hash) {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(TlsError::General(
+                "Server certificate does not match any pinned hash".to_string(),
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Builds an HTTP client that only accepts servers matching the configured pins
+pub fn pinned_client(config: &TlsPinConfig) -> Result<Client> {
+    let pins = config
+        .pins
+        .iter()
+        .map(|pin| {
+            let bytes = STANDARD.decode(pin).map_err(|e| {
+                ServiceError::Service(format!("Invalid base64 TLS pin {}: {}", pin, e))
+            })?;
+            <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                ServiceError::Service(format!("TLS pin {} is not a SHA-256 hash", pin))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let provider = Arc::new(ring::default_provider());
+
+    let mut roots = RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+
+    let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+        .build()
+        .map_err(|e| ServiceError::Service(format!("Failed to build TLS verifier: {}", e)))?;
+
+    let tls_config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| ServiceError::Service(format!("Failed to configure TLS: {}", e)))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(PinnedCertVerifier {
+            inner,
+            pins,
+            validation: config.validation.clone(),
+        }))
+        .with_no_client_auth();
+
+    Client::builder()
+        .use_preconfigured_tls(tls_config)
+        .build()
+        .map_err(|e| ServiceError::Service(format!("Failed to build HTTP client: {}", e)))
+}
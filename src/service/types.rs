@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use fastcrypto::encoding::Base64;
 use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
 use serde::{Deserialize, Serialize};
 use sui_sdk::types::{base_types::SuiAddress, transaction::Transaction};
@@ -27,6 +28,24 @@ pub enum ServiceError {
 
     #[error("Invalid JWT extraction: {0}")]
     JwtExtraction(String),
+
+    /// `available` is the balance held, `required` the amount that was asked for
+    #[error("Insufficient gas: {available} MIST available, {required} MIST required")]
+    InsufficientGas { available: u64, required: u64 },
+
+    #[error("Dry run failed: {0}")]
+    DryRunFailed(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// Enoki answered 401: the API key is missing, expired or invalid
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// Enoki answered 403: the API key is not allowed to make this request
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
 }
 
 pub type Result<T> = std::result::Result<T, ServiceError>;
@@ -45,6 +64,7 @@ pub trait GoogleOauthProvider {
     ) -> Result<Option<T>>;
     async fn zk_proof(&self, jwt: &str) -> Result<ZkLoginInputs>;
     async fn get_account(&self, jwt: &str) -> Result<AccountResponse>;
+    async fn get_supported_oauth_providers(&self) -> Result<Vec<String>>;
     async fn create_zkp_payload(&mut self, path: PathBuf) -> Result<()>;
     async fn create_sponsor_transaction(
         &mut self,
@@ -53,10 +73,25 @@ pub trait GoogleOauthProvider {
         allowed_addresses: Vec<String>,
         allowed_move_call_targets: Vec<String>,
     ) -> Result<SponsorTransactionResponse>;
+    async fn create_sponsor_transaction_kind(
+        &mut self,
+        transaction_kind_bytes: Base64,
+        sender: SuiAddress,
+        allowed_addresses: Vec<String>,
+        allowed_move_call_targets: Vec<String>,
+    ) -> Result<SponsorTransactionResponse>;
 
     fn get_zk_proof_params(&self) -> (String, String, u64);
     fn set_zk_proof_params(&mut self, randomness: String, public_key: String, max_epoch: u64);
 
+    /// Stores the JWT of the signed-in user; providers without session state ignore it
+    fn set_jwt(&mut self, _jwt: String) {}
+
+    /// The stored JWT, empty when none was set
+    fn get_jwt(&self) -> &str {
+        ""
+    }
+
     async fn submit_sponsor_transaction(
         &mut self,
         digest: String,
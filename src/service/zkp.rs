@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+use fastcrypto_zkp::bn254::{
+    utils::{gen_address_seed, get_zk_login_address},
+    zk_login::Bn254FrElement,
+};
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::service::types::{Result, ServiceError};
+
+/// zkLogin address of the user `sub` of issuer `iss` for the OAuth client `aud`
+///
+/// The address seed is the Poseidon hash of the `sub` claim, `aud` and
+/// `salt`, and the address is derived from `iss` and that seed, the same
+/// way Enoki does. No proof or network call is needed.
+pub fn compute_address_with_salt(
+    sub: &str,
+    salt: &str,
+    iss: &str,
+    aud: &str,
+) -> Result<SuiAddress> {
+    let address_seed = gen_address_seed(salt, "sub", sub, aud)
+        .map_err(|e| ServiceError::InvalidProof(format!("Failed to derive address seed: {}", e)))?;
+    let address_seed = Bn254FrElement::from_str(&address_seed)
+        .map_err(|e| ServiceError::InvalidProof(format!("Invalid address seed: {}", e)))?;
+    let address = get_zk_login_address(&address_seed, iss)
+        .map_err(|e| ServiceError::InvalidProof(format!("Failed to derive address: {}", e)))?;
+
+    SuiAddress::from_bytes(address)
+        .map_err(|e| ServiceError::InvalidProof(format!("Invalid zkLogin address: {}", e)))
+}
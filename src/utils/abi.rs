@@ -0,0 +1,256 @@
+use sui_sdk::{
+    rpc_types::{
+        EventFilter, SuiMoveAbility, SuiMoveAbilitySet, SuiMoveNormalizedFunction,
+        SuiMoveNormalizedModule, SuiMoveNormalizedType, SuiMoveVisibility,
+    },
+    types::{Identifier, TypeTag, base_types::ObjectID, parse_sui_type_tag, transaction::CallArg},
+};
+
+use crate::{
+    client::dtos::{MoveCallInfo, MoveEventTypeInfo},
+    service::types::{Result, ServiceError},
+};
+
+/// Structs that can be passed as pure values rather than objects
+const PURE_STRUCTS: [&str; 4] = [
+    "0x1::string::String",
+    "0x1::ascii::String",
+    "0x1::option::Option",
+    "0x2::object::ID",
+];
+
+/// Looks up a function in a package ABI returned by `get_package_abi`
+///
+/// # Arguments
+/// * `modules` - Normalized modules of the package
+/// * `module_name` - Name of the module declaring the function
+/// * `function_name` - Name of the function
+///
+/// # Returns
+/// The normalized function, or `None` if the module or function does not exist
+pub fn find_function<'a>(
+    modules: &'a [SuiMoveNormalizedModule],
+    module_name: &str,
+    function_name: &str,
+) -> Option<&'a SuiMoveNormalizedFunction> {
+    modules
+        .iter()
+        .find(|module| module.name == module_name)
+        .and_then(|module| module.exposed_functions.get(function_name))
+}
+
+/// Parses type arguments for a call to `function`
+///
+/// Fails if the number of type strings does not match the number of generic
+/// parameters the function declares, or if any string is not a valid type.
+///
+/// # Example
+/// ```rust
+/// let type_args = build_type_args_from_abi(function, &["0x2::sui::SUI"])?;
+/// ```
+pub fn build_type_args_from_abi(
+    function: &SuiMoveNormalizedFunction,
+    args: &[&str],
+) -> Result<Vec<TypeTag>> {
+    if function.type_parameters.len() != args.len() {
+        return Err(ServiceError::Service(format!(
+            "Function expects {} type arguments, got {}",
+            function.type_parameters.len(),
+            args.len()
+        )));
+    }
+
+    args.iter()
+        .map(|arg| {
+            parse_sui_type_tag(arg)
+                .map_err(|e| ServiceError::Service(format!("Invalid type argument {}: {}", arg, e)))
+        })
+        .collect()
+}
+
+/// Summarizes `function` with every type written as in Move source
+pub fn move_call_info(function: &SuiMoveNormalizedFunction) -> MoveCallInfo {
+    MoveCallInfo {
+        parameters: function.parameters.iter().map(type_name).collect(),
+        return_types: function.return_.iter().map(type_name).collect(),
+        type_parameters: function.type_parameters.iter().map(abilities).collect(),
+        is_entry: function.is_entry,
+        visibility: match function.visibility {
+            SuiMoveVisibility::Public => "public",
+            SuiMoveVisibility::Friend => "friend",
+            SuiMoveVisibility::Private => "private",
+        }
+        .to_string(),
+    }
+}
+
+/// Checks `args` against the parameters of a function before calling it
+///
+/// The trailing `TxContext` is supplied by the runtime and not counted.
+/// Pure values must go to primitive, vector, string, `ID` or `Option`
+/// parameters, or references to them, and objects to every other struct;
+/// arguments for generic parameters are not checked.
+pub fn validate_call_args(info: &MoveCallInfo, args: &[CallArg]) -> Result<()> {
+    let parameters: Vec<&String> = info
+        .parameters
+        .iter()
+        .filter(|parameter| !parameter.ends_with("::tx_context::TxContext"))
+        .collect();
+
+    if parameters.len() != args.len() {
+        return Err(ServiceError::Service(format!(
+            "Function expects {} arguments, got {}",
+            parameters.len(),
+            args.len()
+        )));
+    }
+
+    for (index, (parameter, arg)) in parameters.into_iter().zip(args).enumerate() {
+        let Some(expects_pure) = is_pure_type(parameter) else {
+            continue;
+        };
+
+        if expects_pure != matches!(arg, CallArg::Pure(_)) {
+            return Err(ServiceError::Service(format!(
+                "Argument {} must be {} for parameter of type {}",
+                index,
+                if expects_pure {
+                    "a pure value"
+                } else {
+                    "an object"
+                },
+                parameter
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Structs of `modules` that can be emitted as events
+///
+/// The normalized ABI does not mark events, so this relies on convention:
+/// events have `copy` and `drop` and are not objects. Plain value structs
+/// declared with the same abilities are listed too, and events of generic
+/// types are given without their type arguments.
+pub fn event_types(modules: &[SuiMoveNormalizedModule]) -> Vec<MoveEventTypeInfo> {
+    modules
+        .iter()
+        .flat_map(|module| {
+            module
+                .structs
+                .iter()
+                .filter(|(_, normalized)| {
+                    let abilities = &normalized.abilities.abilities;
+
+                    abilities.contains(&SuiMoveAbility::Copy)
+                        && abilities.contains(&SuiMoveAbility::Drop)
+                        && !abilities.contains(&SuiMoveAbility::Key)
+                })
+                .map(|(name, normalized)| MoveEventTypeInfo {
+                    event_type: format!("{}::{}::{}", module.address, module.name, name),
+                    module: module.name.clone(),
+                    fields: normalized
+                        .fields
+                        .iter()
+                        .map(|field| (field.name.clone(), type_name(&field.type_)))
+                        .collect(),
+                })
+        })
+        .collect()
+}
+
+/// Subscription filter for events whose type is declared in any of `modules` of `package`
+///
+/// Without modules every event of the package matches. Several modules
+/// are combined with `EventFilter::Any`, which only event subscriptions
+/// accept: `query_events` rejects it, so query each module with its own
+/// `EventFilter::MoveEventModule` instead. Fails with
+/// `ServiceError::Service` if a module name is not a valid identifier.
+pub fn build_event_filter_for_package(package: ObjectID, modules: &[&str]) -> Result<EventFilter> {
+    let mut filters = modules
+        .iter()
+        .map(|module| {
+            Identifier::new(*module)
+                .map(|module| EventFilter::MoveEventModule { package, module })
+                .map_err(|e| ServiceError::Service(format!("Invalid module {}: {}", module, e)))
+        })
+        .collect::<Result<Vec<EventFilter>>>()?;
+
+    Ok(match filters.len() {
+        0 => EventFilter::Package(package),
+        1 => filters.remove(0),
+        _ => EventFilter::Any(filters),
+    })
+}
+
+/// Whether a value of type `type_name` is passed as a pure value
+///
+/// References are judged by the type they point to, so `&vector<u8>` takes
+/// a pure value and `&mut Coin<T>` an object. `None` for generic types,
+/// which can be either.
+fn is_pure_type(type_name: &str) -> Option<bool> {
+    if let Some(referenced) = type_name
+        .strip_prefix("&mut ")
+        .or_else(|| type_name.strip_prefix('&'))
+    {
+        return is_pure_type(referenced);
+    }
+
+    if let Some(element) = type_name
+        .strip_prefix("vector<")
+        .and_then(|rest| rest.strip_suffix('>'))
+    {
+        return is_pure_type(element);
+    }
+
+    if type_name.starts_with('T') && type_name[1..].chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let base = type_name.split('<').next().unwrap_or(type_name);
+
+    Some(!base.contains("::") || PURE_STRUCTS.contains(&base))
+}
+
+fn type_name(normalized: &SuiMoveNormalizedType) -> String {
+    match normalized {
+        SuiMoveNormalizedType::Bool => "bool".to_string(),
+        SuiMoveNormalizedType::U8 => "u8".to_string(),
+        SuiMoveNormalizedType::U16 => "u16".to_string(),
+        SuiMoveNormalizedType::U32 => "u32".to_string(),
+        SuiMoveNormalizedType::U64 => "u64".to_string(),
+        SuiMoveNormalizedType::U128 => "u128".to_string(),
+        SuiMoveNormalizedType::U256 => "u256".to_string(),
+        SuiMoveNormalizedType::Address => "address".to_string(),
+        SuiMoveNormalizedType::Signer => "signer".to_string(),
+        SuiMoveNormalizedType::Struct { inner } => {
+            let name = format!("{}::{}::{}", inner.address, inner.module, inner.name);
+
+            if inner.type_arguments.is_empty() {
+                name
+            } else {
+                let type_arguments: Vec<String> =
+                    inner.type_arguments.iter().map(type_name).collect();
+                format!("{}<{}>", name, type_arguments.join(", "))
+            }
+        }
+        SuiMoveNormalizedType::Vector(element) => format!("vector<{}>", type_name(element)),
+        SuiMoveNormalizedType::TypeParameter(index) => format!("T{}", index),
+        SuiMoveNormalizedType::Reference(inner) => format!("&{}", type_name(inner)),
+        SuiMoveNormalizedType::MutableReference(inner) => format!("&mut {}", type_name(inner)),
+    }
+}
+
+fn abilities(set: &SuiMoveAbilitySet) -> String {
+    set.abilities
+        .iter()
+        .map(|ability| match ability {
+            SuiMoveAbility::Copy => "copy",
+            SuiMoveAbility::Drop => "drop",
+            SuiMoveAbility::Store => "store",
+            SuiMoveAbility::Key => "key",
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
@@ -0,0 +1,66 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use regex::Regex;
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::service::types::{Result, ServiceError};
+
+static MOVE_CALL_TARGET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^0x[0-9a-fA-F]{1,64}::[A-Za-z_][A-Za-z0-9_]*::[A-Za-z_][A-Za-z0-9_]*$")
+        .expect("Move call target pattern is valid")
+});
+
+/// Parses a `0x`-prefixed Sui address
+pub fn validate_sui_address(addr: &str) -> Result<SuiAddress> {
+    SuiAddress::from_str(addr)
+        .map_err(|e| ServiceError::InvalidResponse(format!("Invalid Sui address {}: {}", addr, e)))
+}
+
+/// Whether `zk_inputs` belong to the zkLogin address `expected`
+///
+/// The address is derived from the issuer and address seed of the proof.
+/// Both the current derivation and the legacy one with a zero-padded seed
+/// are accepted, as Sui accepts signatures from either address.
+pub fn zk_login_address_matches(zk_inputs: &ZkLoginInputs, expected: SuiAddress) -> bool {
+    [
+        SuiAddress::try_from_unpadded(zk_inputs),
+        SuiAddress::try_from_padded(zk_inputs),
+    ]
+    .into_iter()
+    .any(|address| address.is_ok_and(|address| address == expected))
+}
+
+/// Parses every address, reporting all invalid entries in a single error
+pub fn validate_allowed_addresses(addresses: &[String]) -> Result<Vec<SuiAddress>> {
+    let mut valid = Vec::with_capacity(addresses.len());
+    let mut invalid = Vec::new();
+
+    for address in addresses {
+        match SuiAddress::from_str(address) {
+            Ok(parsed) => valid.push(parsed),
+            Err(_) => invalid.push(address.as_str()),
+        }
+    }
+
+    if !invalid.is_empty() {
+        return Err(ServiceError::InvalidResponse(format!(
+            "Invalid Sui addresses: {}",
+            invalid.join(", ")
+        )));
+    }
+
+    Ok(valid)
+}
+
+/// Checks that `target` has the `0xpackage::module::function` form
+pub fn validate_move_call_target(target: &str) -> Result<()> {
+    if MOVE_CALL_TARGET.is_match(target) {
+        Ok(())
+    } else {
+        Err(ServiceError::InvalidResponse(format!(
+            "Invalid Move call target {}: expected package::module::function",
+            target
+        )))
+    }
+}
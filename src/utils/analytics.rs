@@ -0,0 +1,18 @@
+use serde_json::{Value, json};
+
+use crate::client::dtos::AddressActivity;
+
+/// `activity` as a JSON object for dashboards
+///
+/// Amounts are written as strings, as the Sui JSON-RPC API does, so they
+/// survive JavaScript's 53-bit numbers.
+pub fn activity_to_json(activity: &AddressActivity) -> Value {
+    json!({
+        "totalTx": activity.total_tx.to_string(),
+        "sentTx": activity.sent_tx.to_string(),
+        "receivedTx": activity.received_tx.to_string(),
+        "totalGasSpentMist": activity.total_gas_spent_mist.to_string(),
+        "firstTxMs": activity.first_tx_ms.map(|ms| ms.to_string()),
+        "lastTxMs": activity.last_tx_ms.map(|ms| ms.to_string()),
+    })
+}
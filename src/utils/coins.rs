@@ -0,0 +1,184 @@
+use sui_sdk::{
+    rpc_types::Coin,
+    types::{base_types::ObjectRef, parse_sui_struct_tag},
+};
+
+use crate::service::types::{Result, ServiceError};
+
+/// Decimals of SUI: 1 SUI is 10^9 MIST
+const SUI_DECIMALS: u8 = 9;
+
+/// How coins are picked to cover an amount
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinSelectionStrategy {
+    /// Largest coins first, touching as few objects as possible
+    Largest,
+    /// Smallest coins first, sweeping up dust
+    Smallest,
+    /// A single coin covering the amount with the least excess, falling back to `Largest`
+    Exact,
+    /// Largest coins first, to be merged into one coin covering the amount
+    Merge,
+}
+
+/// Outcome of `select_gas_coin_strategy`
+#[derive(Debug, Clone)]
+pub enum CoinSelectionResult {
+    /// The largest coin covers the budget on its own
+    Single(Coin),
+    /// No single coin does, but these coins together do once merged
+    Merge(Vec<Coin>),
+    /// All coins together hold only `available` MIST
+    Insufficient { available: u64 },
+}
+
+/// Picks coins whose balances add up to at least `amount`
+///
+/// # Returns
+/// The selected coins, or `None` if the combined balance is below `amount`
+pub fn select_coins(
+    coins: &[Coin],
+    amount: u64,
+    strategy: CoinSelectionStrategy,
+) -> Option<Vec<Coin>> {
+    let mut sorted = coins.to_vec();
+
+    match strategy {
+        CoinSelectionStrategy::Largest | CoinSelectionStrategy::Merge => {
+            sorted.sort_by(|a, b| b.balance.cmp(&a.balance))
+        }
+        CoinSelectionStrategy::Smallest => sorted.sort_by(|a, b| a.balance.cmp(&b.balance)),
+        CoinSelectionStrategy::Exact => {
+            if let Some(coin) = coins
+                .iter()
+                .filter(|coin| coin.balance >= amount)
+                .min_by_key(|coin| coin.balance)
+            {
+                return Some(vec![coin.clone()]);
+            }
+            sorted.sort_by(|a, b| b.balance.cmp(&a.balance));
+        }
+    }
+
+    let mut total: u128 = 0;
+    let mut selected = Vec::new();
+
+    for coin in sorted {
+        if total >= u128::from(amount) {
+            break;
+        }
+        total += u128::from(coin.balance);
+        selected.push(coin);
+    }
+
+    (total >= u128::from(amount)).then_some(selected)
+}
+
+/// Object references and combined balance of `coins`
+///
+/// Balances are summed with saturation so a malformed response can't wrap.
+pub fn aggregate_coins(coins: &[Coin]) -> (Vec<ObjectRef>, u64) {
+    let refs = coins.iter().map(|coin| coin.object_ref()).collect();
+    let total = coins
+        .iter()
+        .fold(0u64, |total, coin| total.saturating_add(coin.balance));
+
+    (refs, total)
+}
+
+/// Greedily picks gas coins covering `budget`
+///
+/// The largest coin is used alone if it is enough; otherwise coins are
+/// added largest first until their combined balance covers the budget.
+pub fn select_gas_coin_strategy(coins: &[Coin], budget: u64) -> CoinSelectionResult {
+    let Some(largest) = coins.iter().max_by_key(|coin| coin.balance) else {
+        return CoinSelectionResult::Insufficient { available: 0 };
+    };
+
+    if largest.balance >= budget {
+        return CoinSelectionResult::Single(largest.clone());
+    }
+
+    match select_coins(coins, budget, CoinSelectionStrategy::Merge) {
+        Some(selected) => CoinSelectionResult::Merge(selected),
+        None => CoinSelectionResult::Insufficient {
+            available: aggregate_coins(coins).1,
+        },
+    }
+}
+
+/// Formats a balance in base units for display, e.g. `1000000000` with 9 decimals as `"1.0"`
+///
+/// Trailing zeros of the fraction are dropped, keeping at least one digit.
+/// With 0 decimals only the integer is returned.
+pub fn format_balance(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let digits = format!("{:0>width$}", amount, width = usize::from(decimals) + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - usize::from(decimals));
+    let fraction = fraction.trim_end_matches('0');
+
+    format!(
+        "{}.{}",
+        whole,
+        if fraction.is_empty() { "0" } else { fraction }
+    )
+}
+
+/// A storage rebate in MIST for display, e.g. `"0.00198 SUI"`
+pub fn format_storage_rebate_display(mist: u64) -> String {
+    format!("{} SUI", format_balance(mist, SUI_DECIMALS))
+}
+
+/// Parses a balance written as `format_balance` does back into base units
+///
+/// Accepts an optional fraction of at most `decimals` digits. Fails with
+/// `ServiceError::Service` on anything else, or if the amount does not fit
+/// in a `u64`.
+pub fn parse_balance(formatted: &str, decimals: u8) -> Result<u64> {
+    let invalid = || ServiceError::Service(format!("Invalid balance: {}", formatted));
+
+    let (whole, fraction) = formatted
+        .trim()
+        .split_once('.')
+        .unwrap_or((formatted.trim(), ""));
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+
+    if fraction.len() > usize::from(decimals) {
+        return Err(ServiceError::Service(format!(
+            "Balance {} has more than {} decimals",
+            formatted, decimals
+        )));
+    }
+
+    let scale = 10u128
+        .checked_pow(u32::from(decimals))
+        .ok_or_else(invalid)?;
+    let whole: u128 = whole.parse().map_err(|_| invalid())?;
+    let fraction: u128 = format!("{:0<width$}", fraction, width = usize::from(decimals))
+        .parse()
+        .unwrap_or_default();
+
+    whole
+        .checked_mul(scale)
+        .and_then(|amount| amount.checked_add(fraction))
+        .and_then(|amount| u64::try_from(amount).ok())
+        .ok_or_else(|| ServiceError::Service(format!("Balance {} is too large", formatted)))
+}
+
+/// Writes `coin_type` as a canonical `StructTag`, e.g. `0x2::sui::SUI` with the full address
+///
+/// Type arguments are normalized too, so differently written types compare
+/// equal afterwards. Strings that are not a struct type are returned as
+/// they are.
+pub fn coin_type_to_move_type(coin_type: &str) -> String {
+    parse_sui_struct_tag(coin_type)
+        .map(|tag| tag.to_canonical_string(true))
+        .unwrap_or_else(|_| coin_type.to_string())
+}
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+/// `name` field of a rendered `Display`
+pub fn get_display_name(fields: &HashMap<String, String>) -> Option<&str> {
+    fields.get("name").map(String::as_str)
+}
+
+/// `image_url` field of a rendered `Display`
+pub fn get_display_image_url(fields: &HashMap<String, String>) -> Option<&str> {
+    fields.get("image_url").map(String::as_str)
+}
+
+/// `description` field of a rendered `Display`
+pub fn get_display_description(fields: &HashMap<String, String>) -> Option<&str> {
+    fields.get("description").map(String::as_str)
+}
+
+/// `link` field of a rendered `Display`
+pub fn get_display_link(fields: &HashMap<String, String>) -> Option<&str> {
+    fields.get("link").map(String::as_str)
+}
+
+/// Fills the `{field_name}` placeholders of `template` from `fields`
+///
+/// Uses the same syntax as `Display` templates, e.g. `"{name} #{id}"`.
+/// Placeholders without a matching field, and a `{` that is never
+/// closed, are kept as written.
+pub fn hydrate_display_template(template: &str, fields: &HashMap<String, String>) -> String {
+    let mut hydrated = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{')
+        && let Some(end) = rest[start..].find('}').map(|end| start + end)
+    {
+        let placeholder = &rest[start..=end];
+
+        hydrated.push_str(&rest[..start]);
+        match fields.get(&rest[start + 1..end]) {
+            Some(value) => hydrated.push_str(value),
+            None => hydrated.push_str(placeholder),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    hydrated.push_str(rest);
+    hydrated
+}
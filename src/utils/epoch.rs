@@ -0,0 +1,95 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+
+use crate::client::dtos::ProofValidity;
+
+/// Configured length of an epoch in milliseconds
+pub fn epoch_duration_ms(state: &SuiSystemStateSummary) -> u64 {
+    state.epoch_duration_ms
+}
+
+/// Expected start of the next epoch, as a Unix timestamp in milliseconds
+///
+/// Epoch changes happen at the first checkpoint after this time, so the
+/// actual switch may lag by a few seconds.
+pub fn next_epoch_start_ms(state: &SuiSystemStateSummary) -> u64 {
+    state.epoch_start_timestamp_ms + state.epoch_duration_ms
+}
+
+/// Converts a Unix timestamp in milliseconds, as used by Sui and Enoki
+pub fn unix_ms_to_system_time(timestamp_ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(timestamp_ms)
+}
+
+/// Estimated start of `epoch`, as a Unix timestamp in milliseconds
+///
+/// Assumes every epoch lasted `epoch_duration_ms`. Real epochs end at the
+/// first checkpoint after their scheduled end and safe mode can stretch
+/// them, so the estimate drifts for old epochs; use `get_epochs_range` for
+/// exact times. `NetworkConfig::estimated_epoch_start_ms` fills in the
+/// genesis of a configured network.
+pub fn epoch_to_estimated_timestamp(
+    epoch: u64,
+    genesis_timestamp_ms: u64,
+    epoch_duration_ms: u64,
+) -> u64 {
+    genesis_timestamp_ms.saturating_add(epoch.saturating_mul(epoch_duration_ms))
+}
+
+/// Estimated epoch in progress at `timestamp_ms`, the inverse of `epoch_to_estimated_timestamp`
+///
+/// Timestamps before genesis map to epoch 0, as does a zero epoch duration.
+/// `NetworkConfig::estimated_epoch_at` fills in the genesis of a configured
+/// network.
+pub fn timestamp_to_estimated_epoch(
+    timestamp_ms: u64,
+    genesis_timestamp_ms: u64,
+    epoch_duration_ms: u64,
+) -> u64 {
+    timestamp_ms
+        .saturating_sub(genesis_timestamp_ms)
+        .checked_div(epoch_duration_ms)
+        .unwrap_or_default()
+}
+
+/// Whether an ephemeral key valid through `max_epoch` should be replaced
+///
+/// True once `threshold_epochs` or fewer epochs are left, including when
+/// `max_epoch` has passed or no session was started (`max_epoch` 0).
+pub fn needs_key_rotation(max_epoch: u64, current_epoch: u64, threshold_epochs: u64) -> bool {
+    max_epoch.saturating_sub(current_epoch) <= threshold_epochs
+}
+
+/// Validity of a proof for `max_epoch` while `current_epoch` is in progress
+///
+/// The proof is valid through the end of `max_epoch`; a `max_epoch` of 0
+/// means no session was started. The time left is estimated from the start
+/// of the current epoch, assuming each remaining epoch lasts
+/// `epoch_duration_ms`.
+pub fn proof_validity(
+    max_epoch: u64,
+    current_epoch: u64,
+    epoch_start_timestamp_ms: u64,
+    epoch_duration_ms: u64,
+    now_ms: u64,
+) -> ProofValidity {
+    let is_valid = max_epoch != 0 && current_epoch <= max_epoch;
+
+    let estimated_seconds_remaining = if is_valid {
+        let expires_at_ms = epoch_start_timestamp_ms
+            .saturating_add((max_epoch - current_epoch + 1).saturating_mul(epoch_duration_ms));
+
+        expires_at_ms.saturating_sub(now_ms) / 1000
+    } else {
+        0
+    };
+
+    ProofValidity {
+        current_epoch,
+        max_epoch,
+        epochs_remaining: max_epoch.saturating_sub(current_epoch),
+        estimated_seconds_remaining,
+        is_valid,
+    }
+}
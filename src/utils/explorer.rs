@@ -0,0 +1,28 @@
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+use crate::service::dtos::Network;
+
+/// Explorer page of `address` on `network`
+pub fn address_url(base_url: &str, network: &Network, address: SuiAddress) -> String {
+    explorer_url(base_url, network, "address", &address.to_string())
+}
+
+/// Explorer page of the transaction `digest` on `network`
+pub fn transaction_url(base_url: &str, network: &Network, digest: &str) -> String {
+    explorer_url(base_url, network, "txblock", digest)
+}
+
+/// Explorer page of the object `id` on `network`
+pub fn object_url(base_url: &str, network: &Network, id: ObjectID) -> String {
+    explorer_url(base_url, network, "object", &id.to_string())
+}
+
+fn explorer_url(base_url: &str, network: &Network, kind: &str, id: &str) -> String {
+    format!(
+        "{}/{}/{}?network={}",
+        base_url.trim_end_matches('/'),
+        kind,
+        id,
+        network
+    )
+}
@@ -0,0 +1,10 @@
+pub mod abi;
+pub mod address;
+pub mod analytics;
+pub mod coins;
+pub mod display;
+pub mod epoch;
+pub mod explorer;
+pub mod multisig;
+pub mod protocol;
+pub mod staking;
@@ -0,0 +1,31 @@
+use sui_sdk::types::{base_types::SuiAddress, crypto::PublicKey, multisig::MultiSigPublicKey};
+
+use crate::service::types::{Result, ServiceError};
+
+/// Multi-sig committee of `public_keys`, each with its weight
+///
+/// Signatures are accepted once their weights add up to `threshold`. The
+/// order of `public_keys` is part of the committee: the same keys in another
+/// order give another address.
+pub fn multi_sig_public_key(
+    public_keys: Vec<(PublicKey, u8)>,
+    threshold: u16,
+) -> Result<MultiSigPublicKey> {
+    let (public_keys, weights) = public_keys.into_iter().unzip();
+
+    MultiSigPublicKey::new(public_keys, weights, threshold)
+        .map_err(|e| ServiceError::Service(format!("Invalid multi-sig committee: {}", e)))
+}
+
+/// Address of the multi-sig account of `public_keys` with `threshold`
+///
+/// zkLogin accounts take part through their `PublicKey::ZkLogin`, so a
+/// zkLogin key and a backup key can share one account.
+pub fn get_multi_sig_address(
+    public_keys: Vec<(PublicKey, u8)>,
+    threshold: u16,
+) -> Result<SuiAddress> {
+    let multi_sig_public_key = multi_sig_public_key(public_keys, threshold)?;
+
+    Ok(SuiAddress::from(&multi_sig_public_key))
+}
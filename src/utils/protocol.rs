@@ -0,0 +1,36 @@
+use sui_sdk::rpc_types::{ProtocolConfigResponse, SuiProtocolConfigValue};
+
+/// Protocol default for `max_tx_size_bytes`, used if the node omits it
+const DEFAULT_MAX_TX_SIZE_BYTES: u64 = 128 * 1024;
+
+/// Protocol default for `max_tx_gas`, used if the node omits it
+const DEFAULT_MAX_TX_GAS: u64 = 50_000_000_000;
+
+/// Protocol default for `max_programmable_tx_commands`, used if the node omits it
+const DEFAULT_MAX_PROGRAMMABLE_TX_COMMANDS: u64 = 1024;
+
+/// Reads an integer attribute of the protocol config
+pub fn protocol_attribute_u64(config: &ProtocolConfigResponse, name: &str) -> Option<u64> {
+    match config.attributes.get(name)?.as_ref()? {
+        SuiProtocolConfigValue::U16(value) => Some(u64::from(*value)),
+        SuiProtocolConfigValue::U32(value) => Some(u64::from(*value)),
+        SuiProtocolConfigValue::U64(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Largest serialized transaction the network accepts, in bytes
+pub fn max_tx_size_bytes(config: &ProtocolConfigResponse) -> u64 {
+    protocol_attribute_u64(config, "max_tx_size_bytes").unwrap_or(DEFAULT_MAX_TX_SIZE_BYTES)
+}
+
+/// Largest gas budget a single transaction may set, in MIST
+pub fn max_gas_budget(config: &ProtocolConfigResponse) -> u64 {
+    protocol_attribute_u64(config, "max_tx_gas").unwrap_or(DEFAULT_MAX_TX_GAS)
+}
+
+/// Most commands a single programmable transaction may contain
+pub fn max_programmable_tx_commands(config: &ProtocolConfigResponse) -> u64 {
+    protocol_attribute_u64(config, "max_programmable_tx_commands")
+        .unwrap_or(DEFAULT_MAX_PROGRAMMABLE_TX_COMMANDS)
+}
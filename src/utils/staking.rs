@@ -0,0 +1,185 @@
+use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
+
+use std::collections::{HashMap, HashSet};
+
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::client::dtos::{DelegationPortfolio, RebalanceAction, StakePosition, ValidatorSummary};
+
+/// Total voting power of the validator set
+const TOTAL_VOTING_POWER: f64 = 10_000.0;
+
+/// Basis points in a whole
+const BPS: f64 = 10_000.0;
+
+const MS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// APY gain, in basis points, below which moving stake is not worth it
+///
+/// Withdrawing forfeits the current epoch's rewards and restaking waits an
+/// epoch, so small differences are eaten by the switch.
+const MIN_REBALANCE_GAIN_BPS: u64 = 50;
+
+/// Compounds a per-epoch reward rate over a year of `epoch_duration_ms` epochs
+pub fn apy_from_epoch_rate(epoch_rate: f64, epoch_duration_ms: u64) -> f64 {
+    if epoch_duration_ms == 0 {
+        return 0.0;
+    }
+
+    let epochs_per_year = MS_PER_YEAR / epoch_duration_ms as f64;
+
+    (1.0 + epoch_rate).powf(epochs_per_year) - 1.0
+}
+
+/// Estimated yearly return for stakers of `validator`, as a fraction
+///
+/// Epoch rewards are split between validators by voting power, which is
+/// capped, and the validator keeps its commission. The pool's reward rate is
+/// what remains divided by the SUI staked in it. `epoch_reward_mist` is the
+/// reward paid out per epoch, e.g. the current stake subsidy; gas fees vary
+/// and are left out, so the estimate is a lower bound.
+pub fn estimate_validator_apy(
+    validator: &SuiValidatorSummary,
+    epoch_duration_ms: u64,
+    epoch_reward_mist: u64,
+) -> f64 {
+    if validator.staking_pool_sui_balance == 0 {
+        return 0.0;
+    }
+
+    let validator_reward =
+        epoch_reward_mist as f64 * validator.voting_power as f64 / TOTAL_VOTING_POWER;
+    let staker_reward = validator_reward * (1.0 - validator.commission_rate as f64 / BPS);
+    let pool_stake_reward_rate = staker_reward / validator.staking_pool_sui_balance as f64;
+
+    apy_from_epoch_rate(pool_stake_reward_rate, epoch_duration_ms)
+}
+
+/// SUI per pool token of `validator`'s staking pool
+///
+/// A new pool starts at 1.0 and the rate grows as rewards are added. Pools
+/// without pool tokens are treated as new.
+pub fn pool_exchange_rate(validator: &SuiValidatorSummary) -> f64 {
+    if validator.pool_token_balance == 0 {
+        return 1.0;
+    }
+
+    validator.staking_pool_sui_balance as f64 / validator.pool_token_balance as f64
+}
+
+/// Reward on `principal` MIST staked while the pool rate grew by `exchange_rate`
+///
+/// `exchange_rate` is the pool's current `pool_exchange_rate` divided by
+/// its rate at the stake's activation epoch. Rates below 1.0 yield no
+/// reward.
+pub fn calculate_stake_reward(principal: u64, exchange_rate: f64) -> u64 {
+    if exchange_rate <= 1.0 {
+        return 0;
+    }
+
+    (principal as f64 * (exchange_rate - 1.0)) as u64
+}
+
+/// Sorts `validators` by estimated APY, highest first
+pub fn sort_validators_by_apy(validators: &mut [ValidatorSummary]) {
+    validators.sort_by(|a, b| b.apy_bps.cmp(&a.apy_bps));
+}
+
+/// Validators with at least `min_stake` MIST staked
+pub fn filter_validators_by_min_stake(
+    validators: &[ValidatorSummary],
+    min_stake: u64,
+) -> Vec<&ValidatorSummary> {
+    validators
+        .iter()
+        .filter(|validator| validator.stake >= min_stake)
+        .collect()
+}
+
+/// Sums `positions` into a `DelegationPortfolio`
+pub fn delegation_portfolio(positions: Vec<StakePosition>) -> DelegationPortfolio {
+    let total_principal_mist = positions.iter().fold(0u64, |total, position| {
+        total.saturating_add(position.principal)
+    });
+    let total_estimated_reward_mist = positions.iter().fold(0u64, |total, position| {
+        total.saturating_add(position.estimated_reward)
+    });
+    let validator_count = positions
+        .iter()
+        .map(|position| position.validator)
+        .collect::<HashSet<_>>()
+        .len();
+
+    DelegationPortfolio {
+        positions,
+        total_principal_mist,
+        total_estimated_reward_mist,
+        validator_count,
+    }
+}
+
+/// Validator whose positions in `portfolio` have earned the most so far
+///
+/// `None` for an empty portfolio. Ties go to the validator staked with first.
+pub fn top_earning_validator(portfolio: &DelegationPortfolio) -> Option<SuiAddress> {
+    per_validator(portfolio, |position| position.estimated_reward)
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, reward)| *reward)
+        .map(|(validator, _)| validator)
+}
+
+/// Moves of stake to the highest-APY validator of `validator_summaries`
+///
+/// Stake with validators that left the active set, or whose APY is at
+/// least `MIN_REBALANCE_GAIN_BPS` below the best one, is suggested to
+/// move in full. Amounts are principal; rewards are paid out on
+/// withdrawal and are not included.
+pub fn rebalance_suggestion(
+    portfolio: &DelegationPortfolio,
+    validator_summaries: &[ValidatorSummary],
+) -> Vec<RebalanceAction> {
+    let Some(best) = validator_summaries
+        .iter()
+        .max_by_key(|validator| validator.apy_bps)
+    else {
+        return Vec::new();
+    };
+
+    per_validator(portfolio, |position| position.principal)
+        .into_iter()
+        .filter(|(validator, _)| *validator != best.address)
+        .filter(|(validator, _)| {
+            validator_summaries
+                .iter()
+                .find(|summary| summary.address == *validator)
+                .is_none_or(|summary| {
+                    best.apy_bps.saturating_sub(summary.apy_bps) >= MIN_REBALANCE_GAIN_BPS
+                })
+        })
+        .map(|(validator, amount_mist)| RebalanceAction {
+            from_validator: validator,
+            to_validator: best.address,
+            amount_mist,
+        })
+        .collect()
+}
+
+/// `value` of the positions summed per validator, in order of first position
+fn per_validator(
+    portfolio: &DelegationPortfolio,
+    value: impl Fn(&StakePosition) -> u64,
+) -> Vec<(SuiAddress, u64)> {
+    let mut index: HashMap<SuiAddress, usize> = HashMap::new();
+    let mut totals: Vec<(SuiAddress, u64)> = Vec::new();
+
+    for position in &portfolio.positions {
+        let slot = *index.entry(position.validator).or_insert_with(|| {
+            totals.push((position.validator, 0));
+            totals.len() - 1
+        });
+        totals[slot].1 = totals[slot].1.saturating_add(value(position));
+    }
+
+    totals
+}
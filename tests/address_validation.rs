@@ -0,0 +1,46 @@
+use squad_connect::utils::address::{
+    validate_allowed_addresses, validate_move_call_target, validate_sui_address,
+};
+
+const VALID: &str = "0x4a3a3b9c1e0ffc2c0a1e2e6c0d7c9b5e8f1a2b3c4d5e6f708192a3b4c5d6e7f8";
+const OTHER: &str = "0x0000000000000000000000000000000000000000000000000000000000000002";
+
+#[test]
+fn valid_address_parses() {
+    assert_eq!(validate_sui_address(VALID).unwrap().to_string(), VALID);
+}
+
+#[test]
+fn all_invalid_addresses_are_reported() {
+    let addresses = vec![
+        VALID.to_string(),
+        "0xnothex".to_string(),
+        "not an address".to_string(),
+    ];
+
+    let error = validate_allowed_addresses(&addresses)
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("0xnothex"));
+    assert!(error.contains("not an address"));
+    assert!(!error.contains(VALID));
+}
+
+#[test]
+fn valid_addresses_are_returned_in_order() {
+    let addresses = vec![VALID.to_string(), OTHER.to_string()];
+
+    let parsed = validate_allowed_addresses(&addresses).unwrap();
+
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed[0].to_string(), VALID);
+}
+
+#[test]
+fn move_call_targets_need_three_parts() {
+    assert!(validate_move_call_target("0x2::coin::split").is_ok());
+    assert!(validate_move_call_target("0x2::coin").is_err());
+    assert!(validate_move_call_target("coin::split::into").is_err());
+    assert!(validate_move_call_target("0x2::coin::split::extra").is_err());
+}
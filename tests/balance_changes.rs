@@ -0,0 +1,79 @@
+#![cfg(feature = "ws")]
+
+use std::collections::HashMap;
+
+use squad_connect::client::events::{BalanceChangeEvent, diff_balances};
+
+const SUI: &str = "0x2::sui::SUI";
+const USDC: &str = "0xa1ec::usdc::USDC";
+
+fn snapshot(balances: &[(&str, u64)]) -> HashMap<String, u64> {
+    balances
+        .iter()
+        .map(|(coin_type, balance)| (coin_type.to_string(), *balance))
+        .collect()
+}
+
+#[test]
+fn sequence_of_transactions_yields_one_change_per_moved_balance() {
+    // Balances re-read after each transaction notification
+    let snapshots = [
+        snapshot(&[(SUI, 1_000)]),
+        snapshot(&[(SUI, 1_500)]),
+        snapshot(&[(SUI, 1_400), (USDC, 25)]),
+        snapshot(&[(SUI, 1_400), (USDC, 25)]),
+        snapshot(&[(SUI, 1_390)]),
+    ];
+
+    let changes: Vec<BalanceChangeEvent> = snapshots
+        .windows(2)
+        .enumerate()
+        .flat_map(|(i, pair)| diff_balances(&pair[0], &pair[1], &format!("tx{}", i), i as u64))
+        .collect();
+
+    let summary: Vec<_> = changes
+        .iter()
+        .map(|change| {
+            (
+                change.digest.as_str(),
+                change.coin_type.as_str(),
+                change.delta,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        [
+            ("tx0", SUI, 500),
+            ("tx1", SUI, -100),
+            ("tx1", USDC, 25),
+            ("tx3", SUI, -10),
+            ("tx3", USDC, -25),
+        ]
+    );
+}
+
+#[test]
+fn change_carries_both_balances() {
+    let changes = diff_balances(&snapshot(&[(SUI, 7)]), &snapshot(&[(SUI, 3)]), "tx", 42);
+
+    assert_eq!(
+        changes,
+        [BalanceChangeEvent {
+            coin_type: SUI.to_string(),
+            old_balance: 7,
+            new_balance: 3,
+            delta: -4,
+            digest: "tx".to_string(),
+            timestamp_ms: 42,
+        }]
+    );
+}
+
+#[test]
+fn full_range_delta_does_not_overflow() {
+    let changes = diff_balances(&snapshot(&[]), &snapshot(&[(SUI, u64::MAX)]), "tx", 0);
+
+    assert_eq!(changes[0].delta, i128::from(u64::MAX));
+}
@@ -0,0 +1,50 @@
+use squad_connect::utils::coins::{format_balance, format_storage_rebate_display, parse_balance};
+
+#[test]
+fn whole_amounts_keep_one_decimal() {
+    assert_eq!(format_balance(1_000_000_000, 9), "1.0");
+    assert_eq!(format_balance(0, 9), "0.0");
+}
+
+#[test]
+fn fractions_drop_trailing_zeros() {
+    assert_eq!(format_balance(1_500_000_000, 9), "1.5");
+    assert_eq!(format_balance(1, 9), "0.000000001");
+    assert_eq!(format_balance(123_456, 2), "1234.56");
+}
+
+#[test]
+fn zero_decimals_format_as_integers() {
+    assert_eq!(format_balance(42, 0), "42");
+    assert_eq!(parse_balance("42", 0).unwrap(), 42);
+}
+
+#[test]
+fn max_balance_round_trips() {
+    let formatted = format_balance(u64::MAX, 9);
+
+    assert_eq!(formatted, "18446744073.709551615");
+    assert_eq!(parse_balance(&formatted, 9).unwrap(), u64::MAX);
+}
+
+#[test]
+fn parsing_accepts_short_or_missing_fractions() {
+    assert_eq!(parse_balance("1.5", 9).unwrap(), 1_500_000_000);
+    assert_eq!(parse_balance("2", 9).unwrap(), 2_000_000_000);
+    assert_eq!(parse_balance(" 0.000000001 ", 9).unwrap(), 1);
+}
+
+#[test]
+fn parsing_rejects_malformed_and_out_of_range_input() {
+    assert!(parse_balance("", 9).is_err());
+    assert!(parse_balance("-1", 9).is_err());
+    assert!(parse_balance("1.2.3", 9).is_err());
+    assert!(parse_balance("1.0000000001", 9).is_err());
+    assert!(parse_balance("18446744073.709551616", 9).is_err());
+}
+
+#[test]
+fn storage_rebates_display_in_sui() {
+    assert_eq!(format_storage_rebate_display(1_976_000), "0.001976 SUI");
+    assert_eq!(format_storage_rebate_display(0), "0.0 SUI");
+}
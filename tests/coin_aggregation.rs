@@ -0,0 +1,84 @@
+use squad_connect::utils::coins::{CoinSelectionResult, aggregate_coins, select_gas_coin_strategy};
+use sui_sdk::{
+    rpc_types::Coin,
+    types::{base_types::ObjectID, digests::TransactionDigest},
+};
+
+fn coin(balance: u64) -> Coin {
+    Coin {
+        coin_type: "0x2::sui::SUI".to_string(),
+        coin_object_id: ObjectID::random(),
+        version: 1.into(),
+        digest: Default::default(),
+        balance,
+        previous_transaction: TransactionDigest::default(),
+    }
+}
+
+#[test]
+fn balances_are_summed_and_refs_kept_in_order() {
+    let coins = vec![coin(10), coin(250), coin(1)];
+
+    let (refs, total) = aggregate_coins(&coins);
+
+    assert_eq!(total, 261);
+    assert_eq!(
+        refs.iter().map(|(id, _, _)| *id).collect::<Vec<_>>(),
+        coins
+            .iter()
+            .map(|coin| coin.coin_object_id)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn no_coins_means_zero_balance() {
+    let (refs, total) = aggregate_coins(&[]);
+
+    assert!(refs.is_empty());
+    assert_eq!(total, 0);
+}
+
+#[test]
+fn total_saturates_instead_of_wrapping() {
+    let (_, total) = aggregate_coins(&[coin(u64::MAX), coin(1)]);
+
+    assert_eq!(total, u64::MAX);
+}
+
+#[test]
+fn largest_coin_is_used_alone_when_it_covers_the_budget() {
+    let coins = vec![coin(30), coin(120), coin(80)];
+
+    match select_gas_coin_strategy(&coins, 100) {
+        CoinSelectionResult::Single(selected) => assert_eq!(selected.balance, 120),
+        other => panic!("unexpected selection {:?}", other),
+    }
+}
+
+#[test]
+fn coins_are_merged_largest_first_when_none_covers_the_budget() {
+    let coins = vec![coin(30), coin(60), coin(50), coin(5)];
+
+    match select_gas_coin_strategy(&coins, 100) {
+        CoinSelectionResult::Merge(selected) => assert_eq!(
+            selected.iter().map(|coin| coin.balance).collect::<Vec<_>>(),
+            [60, 50]
+        ),
+        other => panic!("unexpected selection {:?}", other),
+    }
+}
+
+#[test]
+fn insufficient_balance_reports_what_is_available() {
+    let coins = vec![coin(30), coin(20)];
+
+    assert!(matches!(
+        select_gas_coin_strategy(&coins, 100),
+        CoinSelectionResult::Insufficient { available: 50 }
+    ));
+    assert!(matches!(
+        select_gas_coin_strategy(&[], 1),
+        CoinSelectionResult::Insufficient { available: 0 }
+    ));
+}
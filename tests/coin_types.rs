@@ -0,0 +1,33 @@
+use squad_connect::utils::coins::coin_type_to_move_type;
+
+const SUI: &str = "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI";
+
+#[test]
+fn sui_is_written_with_the_full_address() {
+    assert_eq!(coin_type_to_move_type("0x2::sui::SUI"), SUI);
+    assert_eq!(coin_type_to_move_type(SUI), SUI);
+}
+
+#[test]
+fn custom_coin_types_are_padded() {
+    assert_eq!(
+        coin_type_to_move_type("0xabc::usdc::USDC"),
+        "0x0000000000000000000000000000000000000000000000000000000000000abc::usdc::USDC"
+    );
+}
+
+#[test]
+fn type_arguments_are_normalized() {
+    assert_eq!(
+        coin_type_to_move_type("0x2::coin::Coin<0x2::sui::SUI>"),
+        format!(
+            "0x0000000000000000000000000000000000000000000000000000000000000002::coin::Coin<{}>",
+            SUI
+        )
+    );
+}
+
+#[test]
+fn non_struct_types_are_returned_unchanged() {
+    assert_eq!(coin_type_to_move_type("not a type"), "not a type");
+}
@@ -0,0 +1,43 @@
+use squad_connect::service::dtos::{ENOKI_BASE_URL, EnokiEndpoints};
+
+const CUSTOM_BASE_URL: &str = "http://localhost:8080/v1";
+
+#[test]
+fn urls_use_the_custom_base() {
+    for (endpoint, route) in [
+        (EnokiEndpoints::Nonce, "/zklogin/nonce"),
+        (EnokiEndpoints::Address, "/zklogin"),
+        (EnokiEndpoints::ZkProof, "/zklogin/zkp"),
+        (
+            EnokiEndpoints::CreateSponsorTransaction,
+            "/transaction-blocks/sponsor",
+        ),
+        (
+            EnokiEndpoints::SubmitSponsorTransaction("digest".to_string()),
+            "/transaction-blocks/sponsor/digest",
+        ),
+        (EnokiEndpoints::App, "/app"),
+        (EnokiEndpoints::AccountSearch, "/zklogin/search"),
+    ] {
+        assert_eq!(
+            endpoint.url(CUSTOM_BASE_URL),
+            format!("{}{}", CUSTOM_BASE_URL, route)
+        );
+    }
+}
+
+#[test]
+fn trailing_slash_is_ignored() {
+    assert_eq!(
+        EnokiEndpoints::Nonce.url("http://localhost:8080/v1/"),
+        "http://localhost:8080/v1/zklogin/nonce"
+    );
+}
+
+#[test]
+fn display_uses_the_production_base() {
+    assert_eq!(
+        EnokiEndpoints::App.to_string(),
+        format!("{}/app", ENOKI_BASE_URL)
+    );
+}
@@ -0,0 +1,58 @@
+use squad_connect::{
+    client::resilient::NetworkConfig,
+    utils::epoch::{epoch_to_estimated_timestamp, timestamp_to_estimated_epoch},
+};
+
+const GENESIS_MS: u64 = 1_681_000_000_000;
+const DAY_MS: u64 = 86_400_000;
+
+#[test]
+fn epochs_start_one_duration_apart() {
+    assert_eq!(
+        epoch_to_estimated_timestamp(0, GENESIS_MS, DAY_MS),
+        GENESIS_MS
+    );
+    assert_eq!(
+        epoch_to_estimated_timestamp(10, GENESIS_MS, DAY_MS),
+        GENESIS_MS + 10 * DAY_MS
+    );
+}
+
+#[test]
+fn timestamps_round_down_to_the_epoch_in_progress() {
+    let start = epoch_to_estimated_timestamp(42, GENESIS_MS, DAY_MS);
+
+    assert_eq!(timestamp_to_estimated_epoch(start, GENESIS_MS, DAY_MS), 42);
+    assert_eq!(
+        timestamp_to_estimated_epoch(start + DAY_MS - 1, GENESIS_MS, DAY_MS),
+        42
+    );
+}
+
+#[test]
+fn timestamps_before_genesis_and_zero_durations_map_to_epoch_zero() {
+    assert_eq!(timestamp_to_estimated_epoch(0, GENESIS_MS, DAY_MS), 0);
+    assert_eq!(
+        timestamp_to_estimated_epoch(GENESIS_MS + DAY_MS, GENESIS_MS, 0),
+        0
+    );
+}
+
+#[test]
+fn network_estimates_use_the_configured_genesis() {
+    let config = NetworkConfig::new("http://127.0.0.1:9000".to_string());
+
+    assert_eq!(config.estimated_epoch_start_ms(10, DAY_MS), None);
+    assert_eq!(config.estimated_epoch_at(GENESIS_MS, DAY_MS), None);
+
+    let config = config.with_genesis_timestamp_ms(GENESIS_MS);
+
+    assert_eq!(
+        config.estimated_epoch_start_ms(10, DAY_MS),
+        Some(GENESIS_MS + 10 * DAY_MS)
+    );
+    assert_eq!(
+        config.estimated_epoch_at(GENESIS_MS + 10 * DAY_MS + 1, DAY_MS),
+        Some(10)
+    );
+}
@@ -0,0 +1,53 @@
+use squad_connect::{
+    service::dtos::Network,
+    utils::explorer::{address_url, object_url, transaction_url},
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+const ADDRESS: &str = "0x4a3a3b9c1e0ffc2c0a1e2e6c0d7c9b5e8f1a2b3c4d5e6f708192a3b4c5d6e7f8";
+const OBJECT: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+
+#[test]
+fn links_point_at_the_network() {
+    let address: SuiAddress = ADDRESS.parse().unwrap();
+    let object: ObjectID = OBJECT.parse().unwrap();
+
+    for (network, name) in [
+        (Network::Devnet, "devnet"),
+        (Network::Testnet, "testnet"),
+        (Network::Mainnet, "mainnet"),
+    ] {
+        let base_url = network.default_explorer_url();
+
+        assert_eq!(
+            address_url(base_url, &network, address),
+            format!(
+                "https://suiexplorer.com/address/{}?network={}",
+                ADDRESS, name
+            )
+        );
+        assert_eq!(
+            transaction_url(base_url, &network, DIGEST),
+            format!(
+                "https://suiexplorer.com/txblock/{}?network={}",
+                DIGEST, name
+            )
+        );
+        assert_eq!(
+            object_url(base_url, &network, object),
+            format!("https://suiexplorer.com/object/{}?network={}", OBJECT, name)
+        );
+    }
+}
+
+#[test]
+fn custom_explorer_keeps_the_url_scheme() {
+    assert_eq!(
+        transaction_url("https://explorer.example.com/", &Network::Testnet, DIGEST),
+        format!(
+            "https://explorer.example.com/txblock/{}?network=testnet",
+            DIGEST
+        )
+    );
+}
@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use sui_sdk::types::base_types::SuiAddress;
+use wiremock::{
+    Mock, MockServer, Request, ResponseTemplate,
+    matchers::{body_partial_json, method, path},
+};
+
+use crate::mock_enoki::{ADDRESS, JWT, MockEnoki, OBJECT_DIGEST, connect};
+
+const NFT: &str = "0x00000000000000000000000000000000000000000000000000000000000000c1";
+const VALIDATOR: &str = "0x8c2b3a4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809";
+const POOL_ID: &str = "0x1b2c3d4e5f60718293a4b5c6d7e8f9012b3c4d5e6f708192a3b4c5d6e7f8091a";
+const STAKE_ID: &str = "0x2c3d4e5f60718293a4b5c6d7e8f9012b3c4d5e6f708192a3b4c5d6e7f8091a2b";
+const USDC: &str = "0xa1ec::usdc::USDC";
+
+/// Long enough that no account query completes while a test is watching
+const HELD_BACK: Duration = Duration::from_secs(10);
+
+/// Answers `rpc_method` with `result` after `delay`
+async fn mount_delayed_rpc(server: &MockServer, rpc_method: &str, result: Value, delay: Duration) {
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(json!({ "method": rpc_method })))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": body["id"],
+                    "result": result
+                }))
+                .set_delay(delay)
+        })
+        .mount(server)
+        .await;
+}
+
+/// Answers the balance, NFT and stake queries of the account after `delay`
+async fn mount_account_queries(server: &MockServer, delay: Duration) {
+    mount_delayed_rpc(
+        server,
+        "suix_getAllBalances",
+        json!([
+            {
+                "coinType": "0x2::sui::SUI",
+                "coinObjectCount": 2,
+                "totalBalance": "3000000000",
+                "lockedBalance": {}
+            },
+            {
+                "coinType": USDC,
+                "coinObjectCount": 1,
+                "totalBalance": "5000000",
+                "lockedBalance": {}
+            }
+        ]),
+        delay,
+    )
+    .await;
+    mount_delayed_rpc(
+        server,
+        "suix_getOwnedObjects",
+        json!({
+            "data": [{
+                "data": {
+                    "objectId": NFT,
+                    "version": "3",
+                    "digest": OBJECT_DIGEST,
+                    "type": "0xf1::nft_squad::Nft",
+                    "display": { "data": { "name": "Squad #1" }, "error": null }
+                }
+            }],
+            "nextCursor": null,
+            "hasNextPage": false
+        }),
+        delay,
+    )
+    .await;
+    mount_delayed_rpc(
+        server,
+        "suix_getStakes",
+        json!([{
+            "validatorAddress": VALIDATOR,
+            "stakingPool": POOL_ID,
+            "stakes": [{
+                "stakedSuiId": STAKE_ID,
+                "stakeRequestEpoch": "10",
+                "stakeActiveEpoch": "11",
+                "principal": "1000000000",
+                "status": "Active",
+                "estimatedReward": "25000000"
+            }]
+        }]),
+        delay,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn account_queries_run_concurrently() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_account_queries(&mock.server, HELD_BACK).await;
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    let queries = mock.request_bodies("/").await.len();
+
+    // All three are in flight before the first response comes back
+    tokio::select! {
+        _ = squad_connect.get_full_account_state() => {
+            panic!("account queries were sent one after another")
+        }
+        _ = mock.wait_for_requests("/", queries + 3) => {}
+    }
+}
+
+#[tokio::test]
+async fn account_state_is_assembled_and_cached() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_account_queries(&mock.server, Duration::ZERO).await;
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    let state = squad_connect
+        .get_full_account_state()
+        .await
+        .expect("account state is returned");
+
+    assert_eq!(state.address, ADDRESS.parse::<SuiAddress>().unwrap());
+    assert_eq!(state.sui_balance, 3_000_000_000);
+    assert_eq!(state.all_balances[USDC], 5_000_000);
+    assert_eq!(state.nfts.len(), 1);
+    assert_eq!(state.nfts[0].name.as_deref(), Some("Squad #1"));
+    assert_eq!(state.stake_positions.len(), 1);
+    assert_eq!(state.pending_rewards_mist, 25_000_000);
+    assert!(!state.is_stale(Duration::from_secs(60)));
+    assert!(state.is_stale(Duration::ZERO));
+
+    // The second call is served from cache
+    let queries = mock.request_bodies("/").await.len();
+    let cached = squad_connect
+        .get_full_account_state()
+        .await
+        .expect("account state is returned");
+    assert_eq!(mock.request_bodies("/").await.len(), queries);
+    assert_eq!(cached.fetched_at, state.fetched_at);
+}
+
+#[tokio::test]
+async fn cached_state_is_served_without_asking_enoki() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_account_queries(&mock.server, Duration::ZERO).await;
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    let state = squad_connect
+        .get_full_account_state()
+        .await
+        .expect("account state is returned");
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 1);
+
+    let cached = squad_connect
+        .get_full_account_state()
+        .await
+        .expect("account state is returned");
+
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 1);
+    assert_eq!(cached.fetched_at, state.fetched_at);
+}
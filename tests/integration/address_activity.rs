@@ -0,0 +1,221 @@
+use serde_json::{Value, json};
+use squad_connect::utils::analytics::activity_to_json;
+use sui_sdk::types::base_types::SuiAddress;
+use wiremock::{
+    Mock, Request, ResponseTemplate,
+    matchers::{body_partial_json, method, path},
+};
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, connect};
+
+const SENT_OLD: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+const SENT_NEW: &str = "7Hk3oZ1n1vJ6yF8GxWkV9gTqD1rNnQzE4xW2mJpL5sYb";
+const SENT_BEFORE_RANGE: &str = "HWqjqPJ5QBBavZrc8yGYC3zsg6yHynrtFiEhkGCrhXG7";
+const RECEIVED: &str = "4btiuiMPvEENsttpZC7CZ53DruC3MAgfznDbASZ7DR6S";
+
+const GAS_OBJECT: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+
+/// A checkpointed transaction, with effects charging `net_gas` MIST if given
+fn transaction(digest: &str, timestamp_ms: u64, net_gas: Option<u64>) -> Value {
+    let mut response = json!({
+        "digest": digest,
+        "checkpoint": "42",
+        "timestampMs": timestamp_ms.to_string()
+    });
+
+    if let Some(net_gas) = net_gas {
+        response["effects"] = json!({
+            "messageVersion": "v1",
+            "status": { "status": "success" },
+            "executedEpoch": "10",
+            "gasUsed": {
+                "computationCost": net_gas.to_string(),
+                "storageCost": "0",
+                "storageRebate": "0",
+                "nonRefundableStorageFee": "0"
+            },
+            "modifiedAtVersions": [],
+            "transactionDigest": digest,
+            "gasObject": {
+                "owner": { "AddressOwner": ADDRESS },
+                "reference": {
+                    "objectId": GAS_OBJECT,
+                    "version": 2,
+                    "digest": SENT_OLD
+                }
+            },
+            "dependencies": []
+        });
+    }
+
+    response
+}
+
+#[tokio::test]
+async fn activity_is_aggregated_over_the_range() {
+    let (mock, _) = MockEnoki::start().await;
+
+    // Sent history, newest first; the last one is before the range
+    let sent = json!({
+        "data": [
+            transaction(SENT_NEW, 3_000, Some(1_000)),
+            transaction(SENT_OLD, 2_000, Some(500)),
+            transaction(SENT_BEFORE_RANGE, 500, Some(9_999)),
+        ],
+        "nextCursor": null,
+        "hasNextPage": false
+    });
+    // A self-transfer shows up as received too and is counted once
+    let received = json!({
+        "data": [transaction(SENT_NEW, 3_000, None), transaction(RECEIVED, 2_500, None)],
+        "nextCursor": null,
+        "hasNextPage": false
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(
+            json!({ "method": "suix_queryTransactionBlocks" }),
+        ))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            let result = if body["params"][0]["filter"]["FromAddress"].is_string() {
+                sent.clone()
+            } else {
+                received.clone()
+            };
+
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": result
+            }))
+        })
+        .mount(&mock.server)
+        .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let address: SuiAddress = ADDRESS.parse().unwrap();
+    let activity = squad_connect
+        .get_address_activity(address, 1_000, 5_000)
+        .await
+        .expect("activity is returned");
+
+    assert_eq!(activity.sent_tx, 2);
+    assert_eq!(activity.received_tx, 1);
+    assert_eq!(activity.total_tx, 3);
+    assert_eq!(activity.total_gas_spent_mist, 1_500);
+    assert_eq!(activity.first_tx_ms, Some(2_000));
+    assert_eq!(activity.last_tx_ms, Some(3_000));
+
+    let json = activity_to_json(&activity);
+    assert_eq!(json["totalTx"], "3");
+    assert_eq!(json["totalGasSpentMist"], "1500");
+    assert_eq!(json["firstTxMs"], "2000");
+
+    // The second call is served from cache
+    let queries = || async {
+        mock.request_bodies("/")
+            .await
+            .iter()
+            .filter(|body| body["method"] == "suix_queryTransactionBlocks")
+            .count()
+    };
+    let before = queries().await;
+    squad_connect
+        .get_address_activity(address, 1_000, 5_000)
+        .await
+        .expect("activity is returned");
+    assert_eq!(queries().await, before);
+}
+
+/// A transaction signed by `sender`, as returned with its input
+fn signed_by(digest: &str, sender: &str) -> Value {
+    json!({
+        "digest": digest,
+        "transaction": {
+            "data": {
+                "messageVersion": "v1",
+                "transaction": {
+                    "kind": "ProgrammableTransaction",
+                    "inputs": [],
+                    "transactions": []
+                },
+                "sender": sender,
+                "gasData": {
+                    "payment": [],
+                    "owner": sender,
+                    "price": "750",
+                    "budget": "5000000"
+                }
+            },
+            "txSignatures": []
+        }
+    })
+}
+
+#[tokio::test]
+async fn transaction_count_follows_every_page() {
+    let (mock, _) = MockEnoki::start().await;
+    let other = SuiAddress::random_for_testing_only().to_string();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(
+            json!({ "method": "suix_queryTransactionBlocks" }),
+        ))
+        .respond_with(move |request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            let first_page = body["params"][1].is_null();
+            let result = match (
+                body["params"][0]["filter"]["FromAddress"].is_string(),
+                first_page,
+            ) {
+                (true, true) => json!({
+                    "data": [{ "digest": SENT_NEW }, { "digest": SENT_OLD }],
+                    "nextCursor": SENT_OLD,
+                    "hasNextPage": true
+                }),
+                (true, false) => json!({
+                    "data": [{ "digest": SENT_BEFORE_RANGE }],
+                    "nextCursor": null,
+                    "hasNextPage": false
+                }),
+                // A self-transfer shows up as received too and is counted once
+                (false, _) => json!({
+                    "data": [signed_by(SENT_NEW, ADDRESS), signed_by(RECEIVED, &other)],
+                    "nextCursor": null,
+                    "hasNextPage": false
+                }),
+            };
+
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": result
+            }))
+        })
+        .mount(&mock.server)
+        .await;
+
+    let count = connect(&mock)
+        .await
+        .get_transaction_count(ADDRESS.parse().unwrap())
+        .await
+        .expect("transactions are counted");
+
+    assert_eq!(count, 4);
+
+    let cursors: Vec<Value> = mock
+        .request_bodies("/")
+        .await
+        .into_iter()
+        .filter(|body| {
+            body["method"] == "suix_queryTransactionBlocks"
+                && body["params"][0]["filter"]["FromAddress"].is_string()
+        })
+        .map(|body| body["params"][1].clone())
+        .collect();
+    assert_eq!(cursors, vec![Value::Null, json!(SENT_OLD)]);
+}
@@ -0,0 +1,76 @@
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{ADDRESS, JWT, MockEnoki, connect};
+
+/// Starts Enoki, answering account requests with `status` unless it is 200
+async fn connect_with_status(status: u16) -> (MockEnoki, SquadConnect) {
+    let (mock, _) = MockEnoki::start().await;
+
+    if status != 200 {
+        Mock::given(method("GET"))
+            .and(path("/v1/zklogin"))
+            .respond_with(ResponseTemplate::new(status).set_body_string("rejected"))
+            .with_priority(1)
+            .mount(&mock.server)
+            .await;
+    }
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    (mock, squad_connect)
+}
+
+#[tokio::test]
+async fn accepted_api_key_stays_valid() {
+    let (_mock, squad_connect) = connect_with_status(200).await;
+
+    let account = squad_connect
+        .get_address()
+        .await
+        .expect("account is returned");
+
+    assert_eq!(account.address, ADDRESS);
+    assert!(squad_connect.is_api_key_valid());
+}
+
+#[tokio::test]
+async fn forbidden_request_keeps_the_api_key_valid() {
+    let (_mock, squad_connect) = connect_with_status(403).await;
+
+    assert!(matches!(
+        squad_connect.get_address().await,
+        Err(ServiceError::Forbidden(_))
+    ));
+    assert!(squad_connect.is_api_key_valid());
+}
+
+#[tokio::test]
+async fn unauthorized_api_key_fails_fast_until_reset() {
+    let (mock, mut squad_connect) = connect_with_status(401).await;
+
+    assert!(matches!(
+        squad_connect.get_address().await,
+        Err(ServiceError::Unauthorized(_))
+    ));
+    assert!(!squad_connect.is_api_key_valid());
+
+    assert!(matches!(
+        squad_connect.get_address().await,
+        Err(ServiceError::Unauthorized(_))
+    ));
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 1);
+
+    squad_connect.reset_api_key_status();
+    assert!(squad_connect.is_api_key_valid());
+
+    assert!(matches!(
+        squad_connect.get_address().await,
+        Err(ServiceError::Unauthorized(_))
+    ));
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 2);
+}
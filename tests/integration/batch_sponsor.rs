@@ -0,0 +1,239 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use fastcrypto::{
+    encoding::{Base64, Encoding},
+    traits::ToFromBytes,
+};
+use serde_json::json;
+use squad_connect::{
+    client::{dtos::SponsorRequest, squad_connect::SquadConnect},
+    service::types::ServiceError,
+};
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    digests::ObjectDigest,
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    signature::GenericSignature,
+    transaction::{Transaction, TransactionData},
+};
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{
+    ADDRESS, MAX_EPOCH, MockEnoki, SPONSORED_DIGEST, builder, mount_json_rpc, zk_inputs,
+};
+
+const SPONSOR_ROUTE: &str = "/v1/transaction-blocks/sponsor";
+
+/// Long enough that no sponsorship completes while a test is watching
+const HELD_BACK: Duration = Duration::from_secs(10);
+
+/// How long a test waits for requests beyond the concurrency limit
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// A client with an ephemeral key in a fresh keystore, returned with its path
+async fn connect_with_limit(max_concurrent_sponsors: usize) -> (MockEnoki, SquadConnect, PathBuf) {
+    let (mock, _) = MockEnoki::start().await;
+
+    let mut squad_connect = builder(&mock)
+        .await
+        .max_concurrent_sponsors(max_concurrent_sponsors)
+        .build();
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    (mock, squad_connect, keystore)
+}
+
+fn submit_route() -> String {
+    format!("{}/{}", SPONSOR_ROUTE, SPONSORED_DIGEST)
+}
+
+/// Transaction data Enoki answers sponsorships with, and the node reports executed
+fn sponsored_bytes() -> String {
+    let sender: SuiAddress = ADDRESS.parse().unwrap();
+    let tx_data = TransactionData::new_programmable(
+        sender,
+        vec![],
+        ProgrammableTransactionBuilder::new().finish(),
+        10_000_000,
+        1000,
+    );
+
+    Base64::encode(bcs::to_bytes(&tx_data).unwrap())
+}
+
+/// Answers sponsorships with signable bytes and reports them executed
+async fn mount_sponsored_transactions(mock: &MockEnoki) {
+    Mock::given(method("POST"))
+        .and(path(SPONSOR_ROUTE))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "digest": SPONSORED_DIGEST, "bytes": sponsored_bytes() }
+        })))
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getTransactionBlock",
+        json!({ "digest": SPONSORED_DIGEST }),
+    )
+    .await;
+}
+
+async fn sponsor(
+    squad_connect: &mut SquadConnect,
+    requests: Vec<SponsorRequest>,
+    keystore: &Path,
+) -> Vec<Result<String, ServiceError>> {
+    squad_connect
+        .batch_sponsor_transactions(requests, zk_inputs(), MAX_EPOCH, keystore.to_path_buf())
+        .await
+}
+
+/// Holds sponsorship responses back by `HELD_BACK`
+async fn hold_sponsor_responses(mock: &MockEnoki) {
+    Mock::given(method("POST"))
+        .and(path(SPONSOR_ROUTE))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({
+                    "data": { "digest": SPONSORED_DIGEST, "bytes": "AA==" }
+                }))
+                .set_delay(HELD_BACK),
+        )
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+}
+
+/// A SUI transfer of `amount`, distinct per amount
+fn request(amount: u64, allowed_addresses: Vec<String>) -> SponsorRequest {
+    let sender: SuiAddress = ADDRESS.parse().unwrap();
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::random(),
+    );
+    let tx_data = TransactionData::new_transfer_sui(sender, sender, Some(amount), gas, 0, 1000);
+
+    SponsorRequest {
+        tx: Transaction::from_generic_sig_data(tx_data, vec![]),
+        sender,
+        allowed_addresses,
+        allowed_move_call_targets: vec![],
+    }
+}
+
+#[tokio::test]
+async fn sponsor_requests_run_concurrently() {
+    let (mock, mut squad_connect, keystore) = connect_with_limit(5).await;
+    hold_sponsor_responses(&mock).await;
+    let requests = (1..=5).map(|amount| request(amount, vec![])).collect();
+
+    // All five are in flight before the first response comes back
+    tokio::select! {
+        _ = sponsor(&mut squad_connect, requests, &keystore) => {
+            panic!("sponsor requests were sent one after another")
+        }
+        _ = mock.wait_for_requests(SPONSOR_ROUTE, 5) => {}
+    }
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn concurrency_is_limited() {
+    let (mock, mut squad_connect, keystore) = connect_with_limit(2).await;
+    hold_sponsor_responses(&mock).await;
+    let requests = (1..=4).map(|amount| request(amount, vec![])).collect();
+
+    tokio::select! {
+        _ = sponsor(&mut squad_connect, requests, &keystore) => {
+            panic!("sponsor responses are held back")
+        }
+        _ = async {
+            mock.wait_for_requests(SPONSOR_ROUTE, 2).await;
+            tokio::time::sleep(SETTLE).await;
+        } => {}
+    }
+
+    assert_eq!(mock.request_bodies(SPONSOR_ROUTE).await.len(), 2);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn every_request_is_sponsored() {
+    let (mock, mut squad_connect, keystore) = connect_with_limit(2).await;
+    mount_sponsored_transactions(&mock).await;
+    let requests = (1..=5).map(|amount| request(amount, vec![])).collect();
+
+    let results = sponsor(&mut squad_connect, requests, &keystore).await;
+
+    assert_eq!(results.len(), 5);
+    assert!(
+        results
+            .iter()
+            .all(|result| result.as_deref().ok() == Some(SPONSORED_DIGEST))
+    );
+    assert_eq!(mock.request_bodies(SPONSOR_ROUTE).await.len(), 5);
+    assert_eq!(mock.request_bodies(&submit_route()).await.len(), 5);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn sponsored_bytes_are_signed_before_submission() {
+    let (mock, mut squad_connect, keystore) = connect_with_limit(1).await;
+    mount_sponsored_transactions(&mock).await;
+
+    let results = sponsor(&mut squad_connect, vec![request(1, vec![])], &keystore).await;
+    assert_eq!(results[0].as_deref().ok(), Some(SPONSORED_DIGEST));
+
+    let submitted = mock.request_bodies(&submit_route()).await;
+    let signature = submitted[0]["signature"]
+        .as_str()
+        .expect("a signature is submitted");
+
+    assert_ne!(signature, sponsored_bytes());
+    assert!(matches!(
+        GenericSignature::from_bytes(&Base64::decode(signature).unwrap()),
+        Ok(GenericSignature::ZkLoginAuthenticator(_))
+    ));
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn failures_and_duplicates_do_not_affect_other_requests() {
+    let (mock, mut squad_connect, keystore) = connect_with_limit(5).await;
+    mount_sponsored_transactions(&mock).await;
+    let duplicate = request(1, vec![]);
+    let requests = vec![
+        duplicate.clone(),
+        request(2, vec!["not-an-address".to_string()]),
+        duplicate,
+        request(3, vec![]),
+    ];
+
+    let results = sponsor(&mut squad_connect, requests, &keystore).await;
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_deref().ok(), Some(SPONSORED_DIGEST));
+    assert!(matches!(results[1], Err(ServiceError::InvalidResponse(_))));
+    assert!(matches!(results[2], Err(ServiceError::Service(_))));
+    assert_eq!(results[3].as_deref().ok(), Some(SPONSORED_DIGEST));
+    assert_eq!(mock.request_bodies(SPONSOR_ROUTE).await.len(), 2);
+
+    let _ = std::fs::remove_file(keystore);
+}
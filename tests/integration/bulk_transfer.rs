@@ -0,0 +1,190 @@
+use serde_json::json;
+use squad_connect::{
+    client::squad_connect::{bulk_object_transfer, bulk_sui_transfer},
+    service::types::ServiceError,
+};
+use sui_sdk::types::{
+    base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
+    transaction::{Argument, Command},
+};
+
+use crate::mock_enoki::{
+    MAX_EPOCH, MockEnoki, OBJECT_DIGEST, SPONSORED_DIGEST, account, connect, mount_json_rpc,
+    zk_inputs,
+};
+
+async fn mount_command_limit(mock: &MockEnoki, limit: u32) {
+    mount_json_rpc(
+        &mock.server,
+        "sui_getProtocolConfig",
+        json!({
+            "minSupportedProtocolVersion": "1",
+            "maxSupportedProtocolVersion": "70",
+            "protocolVersion": "70",
+            "featureFlags": {},
+            "attributes": {
+                "max_programmable_tx_commands": { "u32": limit.to_string() }
+            }
+        }),
+    )
+    .await;
+}
+
+/// Whether any of the JSON-RPC `requests` called `rpc_method`
+fn called(requests: &[serde_json::Value], rpc_method: &str) -> bool {
+    requests.iter().any(|body| body["method"] == rpc_method)
+}
+
+#[test]
+fn sui_transfers_split_the_gas_coin_once() {
+    let transfers: Vec<(SuiAddress, u64)> = (1..=5)
+        .map(|amount| (SuiAddress::random_for_testing_only(), amount * 1_000))
+        .collect();
+
+    let pt = bulk_sui_transfer(&transfers).expect("transaction is built");
+
+    assert_eq!(pt.commands.len(), 6);
+    assert!(matches!(
+        &pt.commands[0],
+        Command::SplitCoins(Argument::GasCoin, amounts) if amounts.len() == 5
+    ));
+
+    for (index, command) in pt.commands[1..].iter().enumerate() {
+        let Command::TransferObjects(objects, _) = command else {
+            panic!("expected a transfer, got {:?}", command);
+        };
+        assert_eq!(objects, &vec![Argument::NestedResult(0, index as u16)]);
+    }
+}
+
+#[test]
+fn object_transfers_are_grouped_by_recipient() {
+    let alice = SuiAddress::random_for_testing_only();
+    let bob = SuiAddress::random_for_testing_only();
+    let object = || {
+        (
+            ObjectID::random(),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::random(),
+        )
+    };
+
+    let pt = bulk_object_transfer(vec![
+        (alice, object()),
+        (bob, object()),
+        (alice, object()),
+        (bob, object()),
+        (alice, object()),
+    ])
+    .expect("transaction is built");
+
+    let sizes: Vec<usize> = pt
+        .commands
+        .iter()
+        .map(|command| match command {
+            Command::TransferObjects(objects, _) => objects.len(),
+            other => panic!("expected a transfer, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(sizes, vec![3, 2]);
+}
+
+#[tokio::test]
+async fn transfers_over_the_command_limit_are_rejected() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_command_limit(&mock, 3).await;
+
+    let mut squad_connect = connect(&mock).await;
+    let transfers = (1..=5)
+        .map(|amount| (SuiAddress::random_for_testing_only(), amount))
+        .collect();
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = squad_connect
+        .bulk_transfer_sui(
+            transfers,
+            10_000_000,
+            account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(ServiceError::Service(message)) if message == "Too many transfers")
+    );
+    assert!(!called(
+        &mock.request_bodies("/").await,
+        "sui_executeTransactionBlock"
+    ));
+}
+
+#[tokio::test]
+async fn gas_coins_must_cover_the_amounts_too() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_command_limit(&mock, 1024).await;
+    // Enough for the gas budget, but not for the transfer on top of it
+    mount_json_rpc(
+        &mock.server,
+        "suix_getCoins",
+        json!({
+            "data": [{
+                "coinType": "0x2::sui::SUI",
+                "coinObjectId": "0x00000000000000000000000000000000000000000000000000000000000000a1",
+                "version": "3",
+                "digest": OBJECT_DIGEST,
+                "balance": "10000500",
+                "previousTransaction": SPONSORED_DIGEST
+            }],
+            "nextCursor": null,
+            "hasNextPage": false
+        }),
+    )
+    .await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = connect(&mock)
+        .await
+        .bulk_transfer_sui(
+            vec![(SuiAddress::random_for_testing_only(), 1_000)],
+            10_000_000,
+            account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(ServiceError::Network(message)) if message.contains("Insufficient fund"))
+    );
+    assert!(!called(
+        &mock.request_bodies("/").await,
+        "sui_executeTransactionBlock"
+    ));
+}
+
+#[tokio::test]
+async fn overflowing_amounts_are_rejected() {
+    let (mock, _) = MockEnoki::start().await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = connect(&mock)
+        .await
+        .bulk_transfer_sui(
+            vec![
+                (SuiAddress::random_for_testing_only(), u64::MAX / 2),
+                (SuiAddress::random_for_testing_only(), u64::MAX / 2),
+            ],
+            10_000_000,
+            account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::Service(message)) if message.contains("overflow")));
+    assert!(!called(&mock.request_bodies("/").await, "suix_getCoins"));
+}
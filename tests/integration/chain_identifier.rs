@@ -0,0 +1,79 @@
+use serde_json::json;
+use squad_connect::{
+    client::squad_connect::SquadConnect,
+    service::{
+        dtos::{MAINNET_CHAIN_IDENTIFIER, Network, TESTNET_CHAIN_IDENTIFIER},
+        types::ServiceError,
+    },
+};
+
+use crate::mock_enoki::{API_KEY, CLIENT_ID, MockEnoki, connect, mount_json_rpc};
+
+#[tokio::test]
+async fn testnet_node_matches_a_testnet_client() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getChainIdentifier",
+        json!(TESTNET_CHAIN_IDENTIFIER),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    squad_connect
+        .assert_chain_matches_network()
+        .await
+        .expect("chain matches");
+    assert!(
+        mock.request_bodies("/")
+            .await
+            .iter()
+            .all(|body| body["method"] != "sui_getCheckpoint")
+    );
+}
+
+#[tokio::test]
+async fn mainnet_node_is_rejected_for_a_testnet_client() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getChainIdentifier",
+        json!(MAINNET_CHAIN_IDENTIFIER),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    assert!(squad_connect.assert_chain_matches_network().await.is_err());
+}
+
+#[tokio::test]
+async fn custom_url_infers_the_network_from_the_chain() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getChainIdentifier",
+        json!(MAINNET_CHAIN_IDENTIFIER),
+    )
+    .await;
+
+    let squad_connect =
+        SquadConnect::connect_to_url(&mock.rpc_url(), CLIENT_ID.to_string(), API_KEY.to_string())
+            .await
+            .expect("mainnet node is recognized");
+
+    assert!(matches!(squad_connect.get_network(), Network::Mainnet));
+}
+
+#[tokio::test]
+async fn custom_url_with_an_unknown_chain_is_rejected() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "sui_getChainIdentifier", json!("0badc0de")).await;
+
+    let result =
+        SquadConnect::connect_to_url(&mock.rpc_url(), CLIENT_ID.to_string(), API_KEY.to_string())
+            .await;
+
+    assert!(matches!(result, Err(ServiceError::Service(_))));
+}
@@ -0,0 +1,100 @@
+use squad_connect::{client::dtos::ZkLoginSession, service::types::ServiceError};
+
+use crate::mock_enoki::{JWT, MAX_EPOCH, MockEnoki, RANDOMNESS, connect};
+
+const SECRET: &[u8] = b"cookie-signing-secret";
+const COOKIE: &str = "zk_session";
+const PUBLIC_KEY: &str = "AFakeEphemeralPublicKey";
+
+fn session() -> ZkLoginSession {
+    ZkLoginSession {
+        randomness: RANDOMNESS.to_string(),
+        public_key: PUBLIC_KEY.to_string(),
+        max_epoch: MAX_EPOCH,
+        jwt: JWT.to_string(),
+    }
+}
+
+/// The `name=value` pair of a `Set-Cookie` header, as a browser sends it back
+fn cookie_pair(set_cookie: &str) -> &str {
+    set_cookie.split(';').next().unwrap()
+}
+
+#[tokio::test]
+async fn session_survives_a_cookie_round_trip() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_zk_proof_params(RANDOMNESS.to_string(), PUBLIC_KEY.to_string(), MAX_EPOCH);
+    squad_connect.set_jwt(JWT.to_string());
+
+    let set_cookie = squad_connect
+        .export_zk_session_to_cookie(COOKIE, SECRET, true, true, 3600)
+        .expect("cookie is exported");
+
+    assert!(set_cookie.starts_with("zk_session="));
+    assert!(set_cookie.contains("; Max-Age=3600"));
+    assert!(set_cookie.contains("; Secure"));
+    assert!(set_cookie.contains("; HttpOnly"));
+
+    let cookie_header = format!("theme=dark; {}; lang=en", cookie_pair(&set_cookie));
+
+    let mut restored = connect(&mock).await;
+    restored
+        .restore_from_cookie(&cookie_header, COOKIE, SECRET)
+        .expect("session is restored");
+
+    assert_eq!(restored.zk_login_session(), session());
+}
+
+#[test]
+fn tampered_cookie_is_rejected() {
+    let set_cookie = session()
+        .export_to_cookie(COOKIE, SECRET, false, false, 60)
+        .unwrap();
+    let (payload, signature) = cookie_pair(&set_cookie)
+        .trim_start_matches("zk_session=")
+        .split_once('.')
+        .unwrap();
+
+    let mut forged = session();
+    forged.max_epoch += 100;
+    let forged_cookie = forged
+        .export_to_cookie(COOKIE, b"attacker-secret", false, false, 60)
+        .unwrap();
+    let forged_payload = cookie_pair(&forged_cookie)
+        .trim_start_matches("zk_session=")
+        .split_once('.')
+        .unwrap()
+        .0;
+
+    let tampered = format!("zk_session={}.{}", forged_payload, signature);
+    assert!(matches!(
+        ZkLoginSession::import_from_cookie(&tampered, COOKIE, SECRET),
+        Err(ServiceError::InvalidProof(_))
+    ));
+
+    let untouched = format!("zk_session={}.{}", payload, signature);
+    assert!(matches!(
+        ZkLoginSession::import_from_cookie(&untouched, COOKIE, b"wrong-secret"),
+        Err(ServiceError::InvalidProof(_))
+    ));
+    assert_eq!(
+        ZkLoginSession::import_from_cookie(&untouched, COOKIE, SECRET).unwrap(),
+        session()
+    );
+}
+
+#[test]
+fn missing_or_malformed_cookies_are_rejected() {
+    for header in [
+        "",
+        "theme=dark",
+        "zk_session=not-a-session",
+        "zk_session=abc.!!!",
+    ] {
+        assert!(matches!(
+            ZkLoginSession::import_from_cookie(header, COOKIE, SECRET),
+            Err(ServiceError::JwtExtraction(_))
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+use serde_json::{Value, json};
+use squad_connect::client::deepbook::DeepBookConfig;
+use sui_sdk::types::base_types::ObjectID;
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, connect, mount_json_rpc};
+
+const TX_DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+const SUI: &str = "0000000000000000000000000000000000000000000000000000000000000002::sui::SUI";
+const USDC: &str = "00000000000000000000000000000000000000000000000000000000000000c1::usdc::USDC";
+const OLD_SUI_USDC_POOL: &str =
+    "0x00000000000000000000000000000000000000000000000000000000000000d1";
+const SUI_USDC_POOL: &str = "0x00000000000000000000000000000000000000000000000000000000000000d2";
+const USDC_SUI_POOL: &str = "0x00000000000000000000000000000000000000000000000000000000000000d3";
+
+fn pool_created(seq: u64, pool_id: &str, base_asset: &str, quote_asset: &str) -> Value {
+    json!({
+        "id": { "txDigest": TX_DIGEST, "eventSeq": seq.to_string() },
+        "packageId": "0xdee9",
+        "transactionModule": "clob_v2",
+        "sender": ADDRESS,
+        "type": "0xdee9::clob_v2::PoolCreated",
+        "parsedJson": {
+            "pool_id": pool_id,
+            "base_asset": { "name": base_asset },
+            "quote_asset": { "name": quote_asset },
+            "taker_fee_rate": "2500000",
+            "maker_rebate_rate": "1500000",
+            "tick_size": "1000",
+            "lot_size": "1000"
+        },
+        "bcsEncoding": "base64",
+        "bcs": ""
+    })
+}
+
+fn pool_events(events: Vec<Value>) -> Value {
+    json!({ "data": events, "nextCursor": null, "hasNextPage": false })
+}
+
+fn object_id(id: &str) -> ObjectID {
+    ObjectID::from_hex_literal(id).unwrap()
+}
+
+#[tokio::test]
+async fn pools_are_loaded_by_asset_pair() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "suix_queryEvents",
+        pool_events(vec![
+            pool_created(0, OLD_SUI_USDC_POOL, SUI, USDC),
+            pool_created(1, SUI_USDC_POOL, SUI, USDC),
+            pool_created(2, USDC_SUI_POOL, USDC, SUI),
+        ]),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let config = squad_connect
+        .load_deepbook_pools(DeepBookConfig::mainnet())
+        .await
+        .expect("pools are loaded");
+
+    assert_eq!(
+        config.pair_pool("0x2::sui::SUI", "0xc1::usdc::USDC"),
+        Some(object_id(SUI_USDC_POOL))
+    );
+    assert_eq!(
+        config.pair_pool(&format!("0x{}", USDC), &format!("0x{}", SUI)),
+        Some(object_id(USDC_SUI_POOL))
+    );
+    assert_eq!(config.pair_pool("0x2::sui::SUI", "0x2::sui::SUI"), None);
+}
+
+#[tokio::test]
+async fn registered_pools_are_kept() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "suix_queryEvents",
+        pool_events(vec![pool_created(0, SUI_USDC_POOL, SUI, USDC)]),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let config = squad_connect
+        .load_deepbook_pools(DeepBookConfig::testnet().with_pool("mine", object_id(USDC_SUI_POOL)))
+        .await
+        .expect("pools are loaded");
+
+    assert_eq!(config.pool("mine"), Some(object_id(USDC_SUI_POOL)));
+    assert_eq!(
+        config.pair_pool("0x2::sui::SUI", "0xc1::usdc::USDC"),
+        Some(object_id(SUI_USDC_POOL))
+    );
+}
+
+#[tokio::test]
+async fn malformed_pool_events_are_rejected() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut event = pool_created(0, SUI_USDC_POOL, SUI, USDC);
+    event["parsedJson"]["pool_id"] = Value::Null;
+    mount_json_rpc(&mock.server, "suix_queryEvents", pool_events(vec![event])).await;
+
+    let squad_connect = connect(&mock).await;
+
+    assert!(
+        squad_connect
+            .load_deepbook_pools(DeepBookConfig::mainnet())
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use squad_connect::utils::display::{
+    get_display_description, get_display_image_url, get_display_link, get_display_name,
+    hydrate_display_template,
+};
+use sui_sdk::types::base_types::ObjectID;
+
+use crate::mock_enoki::{MockEnoki, OBJECT_DIGEST, connect, mount_json_rpc};
+
+const NFT: &str = "0x00000000000000000000000000000000000000000000000000000000000000c1";
+
+#[tokio::test]
+async fn display_fields_are_read_from_the_object() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getObject",
+        json!({
+            "data": {
+                "objectId": NFT,
+                "version": "3",
+                "digest": OBJECT_DIGEST,
+                "display": {
+                    "data": {
+                        "name": "Squad #1",
+                        "description": "First of the squad",
+                        "image_url": "https://example.com/1.png",
+                        "project_url": "https://example.com"
+                    },
+                    "error": null
+                }
+            }
+        }),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let fields = squad_connect
+        .get_object_display_fields(ObjectID::from_hex_literal(NFT).unwrap())
+        .await
+        .expect("display fields are returned");
+
+    assert_eq!(fields.len(), 4);
+    assert_eq!(get_display_name(&fields), Some("Squad #1"));
+    assert_eq!(get_display_description(&fields), Some("First of the squad"));
+    assert_eq!(
+        get_display_image_url(&fields),
+        Some("https://example.com/1.png")
+    );
+    assert_eq!(get_display_link(&fields), None);
+    assert_eq!(fields["project_url"], "https://example.com");
+
+    let queries = mock.request_bodies("/").await;
+    let query = queries
+        .iter()
+        .find(|body| body["method"] == "sui_getObject")
+        .expect("object is fetched");
+    assert_eq!(query["params"][1]["showDisplay"], true);
+}
+
+#[test]
+fn templates_are_hydrated_from_fields() {
+    let fields = HashMap::from([
+        ("name".to_string(), "Squad".to_string()),
+        ("id".to_string(), "7".to_string()),
+    ]);
+
+    assert_eq!(
+        hydrate_display_template("{name} #{id}", &fields),
+        "Squad #7"
+    );
+    assert_eq!(
+        hydrate_display_template("https://example.com/{id}.png", &fields),
+        "https://example.com/7.png"
+    );
+}
+
+#[test]
+fn unknown_and_unclosed_placeholders_are_kept() {
+    let fields = HashMap::from([("name".to_string(), "Squad".to_string())]);
+
+    assert_eq!(
+        hydrate_display_template("{name} by {creator}", &fields),
+        "Squad by {creator}"
+    );
+    assert_eq!(
+        hydrate_display_template("{name} {oops", &fields),
+        "Squad {oops"
+    );
+}
@@ -0,0 +1,38 @@
+use serde_json::json;
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{CLIENT_ID, MockEnoki, builder};
+
+const STAGING_APP_ROUTE: &str = "/staging/v1/app";
+
+#[tokio::test]
+async fn requests_go_to_the_custom_base() {
+    let (mock, _) = MockEnoki::start().await;
+    Mock::given(method("GET"))
+        .and(path(STAGING_APP_ROUTE))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "authenticationProviders": [
+                    { "providerType": "google", "clientId": CLIENT_ID }
+                ]
+            }
+        })))
+        .mount(&mock.server)
+        .await;
+
+    let squad_connect = builder(&mock)
+        .await
+        .enoki_base_url(format!("{}/staging/v1/", mock.rpc_url()))
+        .build();
+
+    let providers = squad_connect
+        .get_supported_oauth_providers()
+        .await
+        .expect("providers are returned");
+
+    assert_eq!(providers, vec!["google".to_string()]);
+    assert_eq!(mock.request_bodies(STAGING_APP_ROUTE).await.len(), 1);
+}
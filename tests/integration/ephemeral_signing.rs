@@ -0,0 +1,82 @@
+use shared_crypto::intent::{Intent, IntentMessage};
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber, SuiAddress},
+    crypto::{EncodeDecodeBase64, PublicKey, SignatureScheme, SuiSignature},
+    digests::ObjectDigest,
+    signature::GenericSignature,
+    transaction::TransactionData,
+};
+
+use crate::mock_enoki::{MockEnoki, connect};
+
+/// A client with an ephemeral key stored in a fresh keystore
+async fn with_ephemeral_key(mock: &MockEnoki) -> (SquadConnect, std::path::PathBuf) {
+    let mut squad_connect = connect(mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    (squad_connect, keystore)
+}
+
+fn ephemeral_address(squad_connect: &SquadConnect) -> SuiAddress {
+    let (_, public_key, _) = squad_connect.get_zk_proof_params();
+
+    SuiAddress::from(&PublicKey::decode_base64(&public_key).expect("public key is base64"))
+}
+
+fn transfer(sender: SuiAddress) -> TransactionData {
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::random(),
+    );
+
+    TransactionData::new_transfer_sui(sender, sender, Some(1), gas, 0, 1000)
+}
+
+#[tokio::test]
+async fn transaction_is_signed_by_the_ephemeral_key() {
+    let (mock, _) = MockEnoki::start().await;
+    let (squad_connect, keystore) = with_ephemeral_key(&mock).await;
+    let signer = ephemeral_address(&squad_connect);
+    let tx_data = transfer(signer);
+
+    let transaction = squad_connect
+        .sign_transaction_with_ephemeral_key_only(&tx_data, &keystore, signer)
+        .expect("transaction is signed");
+
+    let [GenericSignature::Signature(signature)] = transaction.data().tx_signatures() else {
+        panic!("expected a single plain signature");
+    };
+    signature
+        .verify_secure(
+            &IntentMessage::new(Intent::sui_transaction(), tx_data),
+            signer,
+            SignatureScheme::ED25519,
+        )
+        .expect("signature verifies for the ephemeral address");
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn signer_without_a_key_is_rejected() {
+    let (mock, _) = MockEnoki::start().await;
+    let (squad_connect, keystore) = with_ephemeral_key(&mock).await;
+    let stranger = SuiAddress::random_for_testing_only();
+
+    let result = squad_connect.sign_transaction_with_ephemeral_key_only(
+        &transfer(stranger),
+        &keystore,
+        stranger,
+    );
+
+    assert!(matches!(result, Err(ServiceError::InvalidResponse(_))));
+
+    let _ = std::fs::remove_file(keystore);
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use jsonrpsee::{
+    RpcModule,
+    server::{Server, ServerHandle, SubscriptionMessage},
+    types::ErrorObjectOwned,
+};
+use serde_json::{Value, json};
+use squad_connect::{
+    client::{builder::SquadConnectBuilder, squad_connect::SquadConnect},
+    service::dtos::Network,
+};
+use sui_sdk::{SuiClientBuilder, rpc_types::EventFilter};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+
+use crate::mock_enoki::{ADDRESS, API_KEY, CLIENT_ID, SPONSORED_DIGEST};
+
+const PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+
+fn event(seq: u64) -> Value {
+    json!({
+        "id": { "txDigest": SPONSORED_DIGEST, "eventSeq": seq.to_string() },
+        "packageId": PACKAGE,
+        "transactionModule": "squad",
+        "sender": ADDRESS,
+        "type": format!("{}::squad::Joined", PACKAGE),
+        "parsedJson": { "seq": seq },
+        "bcsEncoding": "base64",
+        "bcs": ""
+    })
+}
+
+/// A fullnode whose event subscriptions send two events and stay open
+///
+/// A message is sent on `closed` once the client ends a subscription.
+async fn start_node(closed: mpsc::UnboundedSender<()>) -> (ServerHandle, SquadConnect) {
+    let server = Server::builder()
+        .build("127.0.0.1:0")
+        .await
+        .expect("mock node starts");
+    let address = server.local_addr().expect("mock node has an address");
+
+    let mut module = RpcModule::new(closed);
+    module
+        .register_method("rpc.discover", |_, _, _| {
+            Ok::<_, ErrorObjectOwned>(json!({
+                "openrpc": "1.2.6",
+                "info": { "title": "Sui JSON-RPC", "version": "1.50.0" },
+                "methods": []
+            }))
+        })
+        .unwrap();
+    module
+        .register_subscription(
+            "suix_subscribeEvent",
+            "suix_subscribeEvent",
+            "suix_unsubscribeEvent",
+            |_, pending, closed, _| async move {
+                let sink = pending.accept().await?;
+
+                for seq in 0..2 {
+                    sink.send(SubscriptionMessage::from_json(&event(seq))?)
+                        .await?;
+                }
+
+                sink.closed().await;
+                let _ = closed.send(());
+
+                Ok(())
+            },
+        )
+        .unwrap();
+
+    let handle = server.start(module);
+
+    let node = SuiClientBuilder::default()
+        .ws_url(format!("ws://{}", address))
+        .build(format!("http://{}", address))
+        .await
+        .expect("Sui client connects to the mock node");
+    let squad_connect = SquadConnectBuilder::new(
+        node,
+        CLIENT_ID.to_string(),
+        Network::Testnet,
+        API_KEY.to_string(),
+    )
+    .build();
+
+    (handle, squad_connect)
+}
+
+#[tokio::test]
+async fn events_are_broadcast_until_the_subscription_is_dropped() {
+    let (closed, mut unsubscribed) = mpsc::unbounded_channel();
+    let (_node, squad_connect) = start_node(closed).await;
+
+    let mut subscription = squad_connect
+        .subscribe_to_events(EventFilter::Package(PACKAGE.parse().unwrap()))
+        .await
+        .expect("subscription starts");
+    let mut other = subscription.subscribe();
+
+    for seq in 0..2 {
+        let event = subscription.receiver.recv().await.expect("event arrives");
+        assert_eq!(event.parsed_json["seq"], seq);
+        assert_eq!(other.recv().await.unwrap().parsed_json["seq"], seq);
+    }
+
+    drop(subscription);
+
+    assert!(matches!(other.recv().await, Err(RecvError::Closed)));
+    tokio::time::timeout(Duration::from_secs(5), unsubscribed.recv())
+        .await
+        .expect("node sees the subscription end");
+}
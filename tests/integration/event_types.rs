@@ -0,0 +1,98 @@
+use serde_json::json;
+use squad_connect::utils::abi::build_event_filter_for_package;
+use sui_sdk::{rpc_types::EventFilter, types::base_types::ObjectID};
+
+use crate::mock_enoki::{MockEnoki, connect, mount_json_rpc};
+
+const PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000f1";
+
+#[tokio::test]
+async fn copy_drop_structs_are_reported_as_events() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getNormalizedMoveModulesByPackage",
+        json!({
+            "nft_squad": {
+                "fileFormatVersion": 6,
+                "address": PACKAGE,
+                "name": "nft_squad",
+                "friends": [],
+                "structs": {
+                    "Minted": {
+                        "abilities": { "abilities": ["Copy", "Drop"] },
+                        "typeParameters": [],
+                        "fields": [
+                            {
+                                "name": "object_id",
+                                "type": {
+                                    "Struct": {
+                                        "address": "0x2",
+                                        "module": "object",
+                                        "name": "ID",
+                                        "typeArguments": []
+                                    }
+                                }
+                            },
+                            { "name": "creator", "type": "Address" },
+                            { "name": "name", "type": { "Vector": "U8" } }
+                        ]
+                    },
+                    "Nft": {
+                        "abilities": { "abilities": ["Key", "Store"] },
+                        "typeParameters": [],
+                        "fields": []
+                    },
+                    "Config": {
+                        "abilities": { "abilities": ["Store"] },
+                        "typeParameters": [],
+                        "fields": [{ "name": "fee", "type": "U64" }]
+                    }
+                },
+                "exposedFunctions": {}
+            }
+        }),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let events = squad_connect
+        .get_move_event_types(ObjectID::from_hex_literal(PACKAGE).unwrap())
+        .await
+        .expect("event types are returned");
+
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events[0].event_type,
+        format!("{}::nft_squad::Minted", PACKAGE)
+    );
+    assert_eq!(events[0].module, "nft_squad");
+    assert_eq!(
+        events[0].fields,
+        vec![
+            ("object_id".to_string(), "0x2::object::ID".to_string()),
+            ("creator".to_string(), "address".to_string()),
+            ("name".to_string(), "vector<u8>".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn event_filter_combines_modules() {
+    let package = ObjectID::from_hex_literal(PACKAGE).unwrap();
+
+    assert!(matches!(
+        build_event_filter_for_package(package, &["nft_squad"]).unwrap(),
+        EventFilter::MoveEventModule { module, .. } if module.as_str() == "nft_squad"
+    ));
+    assert!(matches!(
+        build_event_filter_for_package(package, &["nft_squad", "market"]).unwrap(),
+        EventFilter::Any(filters) if filters.len() == 2
+    ));
+    assert!(matches!(
+        build_event_filter_for_package(package, &[]).unwrap(),
+        EventFilter::Package(id) if id == package
+    ));
+    assert!(build_event_filter_for_package(package, &["nft_squad", "not a module"]).is_err());
+}
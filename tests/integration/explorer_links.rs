@@ -0,0 +1,64 @@
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, builder, connect};
+
+const OBJECT: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+
+#[tokio::test]
+async fn links_follow_the_network_by_default() {
+    let (mock, _) = MockEnoki::start().await;
+    let squad_connect = connect(&mock).await;
+
+    let address: SuiAddress = ADDRESS.parse().unwrap();
+    let object: ObjectID = OBJECT.parse().unwrap();
+
+    assert_eq!(
+        squad_connect.get_address_link(address),
+        format!(
+            "https://suiexplorer.com/address/{}?network=testnet",
+            ADDRESS
+        )
+    );
+    assert_eq!(
+        squad_connect.get_transaction_link(DIGEST),
+        format!("https://suiexplorer.com/txblock/{}?network=testnet", DIGEST)
+    );
+    assert_eq!(
+        squad_connect.get_object_link(object),
+        format!("https://suiexplorer.com/object/{}?network=testnet", OBJECT)
+    );
+}
+
+#[tokio::test]
+async fn builder_explorer_is_used_for_links() {
+    let (mock, _) = MockEnoki::start().await;
+    let squad_connect = builder(&mock)
+        .await
+        .explorer_base_url("https://explorer.example.com/".to_string())
+        .build();
+
+    assert_eq!(
+        squad_connect.get_transaction_link(DIGEST),
+        format!(
+            "https://explorer.example.com/txblock/{}?network=testnet",
+            DIGEST
+        )
+    );
+}
+
+#[tokio::test]
+async fn explorer_can_be_changed_after_connecting() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect(&mock).await;
+
+    squad_connect.set_explorer_base_url("https://explorer.example.com".to_string());
+
+    assert_eq!(
+        squad_connect.get_object_link(OBJECT.parse().unwrap()),
+        format!(
+            "https://explorer.example.com/object/{}?network=testnet",
+            OBJECT
+        )
+    );
+}
@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use squad_connect::{
+    client::resilient::{NetworkConfig, ResilientSuiClient},
+    service::{dtos::Network, services::Services},
+};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{body_partial_json, method, path},
+};
+
+use crate::mock_enoki::{API_KEY, CLIENT_ID, mount_json_rpc};
+
+const LATEST_CHECKPOINT: u64 = 42;
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A fullnode that connects but, unless `healthy`, fails every checkpoint query
+async fn start_node(healthy: bool) -> MockServer {
+    let server = MockServer::start().await;
+    mount_node(&server, healthy).await;
+    server
+}
+
+async fn mount_node(server: &MockServer, healthy: bool) {
+    mount_json_rpc(
+        server,
+        "rpc.discover",
+        json!({
+            "openrpc": "1.2.6",
+            "info": { "title": "Sui JSON-RPC", "version": "1.50.0" },
+            "methods": []
+        }),
+    )
+    .await;
+
+    if healthy {
+        mount_json_rpc(
+            server,
+            "sui_getLatestCheckpointSequenceNumber",
+            json!(LATEST_CHECKPOINT.to_string()),
+        )
+        .await;
+    } else {
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .and(body_partial_json(
+                json!({ "method": "sui_getLatestCheckpointSequenceNumber" }),
+            ))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(server)
+            .await;
+    }
+}
+
+async fn connect(server: &MockServer) -> SuiClient {
+    SuiClientBuilder::default()
+        .build(server.uri())
+        .await
+        .expect("node connects")
+}
+
+/// Services on testnet behind `primary`, falling over to `fallbacks`
+async fn services(primary: &MockServer, fallbacks: Vec<String>) -> Services {
+    Services::new(
+        connect(primary).await,
+        Network::Testnet,
+        API_KEY.to_string(),
+        CLIENT_ID.to_string(),
+    )
+    .with_node(ResilientSuiClient::with_fallbacks(
+        connect(primary).await,
+        fallbacks,
+    ))
+}
+
+/// Checkpoint queries `server` has received
+async fn checkpoint_requests(server: &MockServer) -> usize {
+    server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| {
+            serde_json::from_slice::<Value>(&request.body).unwrap_or_default()["method"]
+                == "sui_getLatestCheckpointSequenceNumber"
+        })
+        .count()
+}
+
+async fn latest_checkpoint(node: &ResilientSuiClient) -> u64 {
+    node.call(|client| async move {
+        client
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await
+    })
+    .await
+    .expect("a healthy endpoint answers")
+}
+
+#[tokio::test]
+async fn requests_fall_over_when_the_primary_errors() {
+    let primary = start_node(false).await;
+    let fallback = start_node(true).await;
+
+    let config = NetworkConfig::new(primary.uri()).with_fallback_rpc(vec![fallback.uri()]);
+    let node = ResilientSuiClient::connect(&config)
+        .await
+        .expect("primary connects");
+    assert_eq!(node.active_index(), 0);
+
+    assert_eq!(latest_checkpoint(&node).await, LATEST_CHECKPOINT);
+    assert_eq!(node.active_index(), 1);
+}
+
+#[tokio::test]
+async fn health_check_moves_back_to_a_recovered_primary() {
+    let primary = start_node(false).await;
+    let fallback = start_node(true).await;
+
+    let config = NetworkConfig::new(primary.uri()).with_fallback_rpc(vec![fallback.uri()]);
+    let node = ResilientSuiClient::connect(&config)
+        .await
+        .expect("primary connects");
+
+    latest_checkpoint(&node).await;
+    assert_eq!(node.active_index(), 1);
+
+    primary.reset().await;
+    mount_node(&primary, true).await;
+
+    node.check_health().await;
+    assert_eq!(node.active_index(), 0);
+}
+
+#[tokio::test]
+async fn health_check_runs_at_the_configured_interval() {
+    let primary = start_node(false).await;
+    let fallback = start_node(true).await;
+
+    let config = NetworkConfig::new(primary.uri())
+        .with_fallback_rpc(vec![fallback.uri()])
+        .with_health_check_interval(HEALTH_CHECK_INTERVAL);
+    let node = ResilientSuiClient::connect(&config)
+        .await
+        .expect("primary connects");
+
+    latest_checkpoint(&node).await;
+    assert_eq!(node.active_index(), 1);
+
+    primary.reset().await;
+    mount_node(&primary, true).await;
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while node.active_index() != 0 {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        }
+    })
+    .await
+    .expect("the health check moves back to the primary");
+}
+
+#[tokio::test]
+async fn switching_networks_drops_the_old_fallbacks() {
+    let old_primary = start_node(true).await;
+    let old_fallback = start_node(true).await;
+    let new_primary = start_node(false).await;
+
+    let mut services = services(&old_primary, vec![old_fallback.uri()]).await;
+
+    services.set_network(Network::Mainnet, connect(&new_primary).await);
+
+    let checkpoint = services
+        .get_resilient_node()
+        .call(|client| async move {
+            client
+                .read_api()
+                .get_latest_checkpoint_sequence_number()
+                .await
+        })
+        .await;
+
+    assert!(checkpoint.is_err());
+    assert_eq!(checkpoint_requests(&old_fallback).await, 0);
+}
+
+#[tokio::test]
+async fn switching_networks_uses_the_new_fallbacks() {
+    let old_primary = start_node(true).await;
+    let old_fallback = start_node(true).await;
+    let new_primary = start_node(false).await;
+    let new_fallback = start_node(true).await;
+
+    let mut services = services(&old_primary, vec![old_fallback.uri()]).await;
+
+    services.set_network(
+        Network::Mainnet,
+        ResilientSuiClient::with_fallbacks(connect(&new_primary).await, vec![new_fallback.uri()]),
+    );
+
+    assert_eq!(
+        latest_checkpoint(services.get_resilient_node()).await,
+        LATEST_CHECKPOINT
+    );
+    assert_eq!(checkpoint_requests(&new_fallback).await, 1);
+    assert_eq!(checkpoint_requests(&old_fallback).await, 0);
+}
+
+#[tokio::test]
+async fn connect_fails_when_no_endpoint_is_reachable() {
+    let config = NetworkConfig::new("http://127.0.0.1:9".to_string());
+
+    assert!(ResilientSuiClient::connect(&config).await.is_err());
+}
@@ -0,0 +1,101 @@
+use serde_json::{Value, json};
+use squad_connect::service::types::ServiceError;
+
+use crate::mock_enoki::{
+    MAX_EPOCH, MockEnoki, OBJECT_DIGEST, SPONSORED_DIGEST, account, connect, mount_json_rpc,
+    zk_inputs,
+};
+
+/// Gas budget of the transaction `consolidate_coins` merges SUI coins with
+const MERGE_GAS_BUDGET: u64 = 10_000_000;
+const MIN_BALANCE: u64 = 10_000_000;
+
+fn sui_coin(object_id: &str, balance: u64) -> Value {
+    json!({
+        "coinType": "0x2::sui::SUI",
+        "coinObjectId": object_id,
+        "version": "3",
+        "digest": OBJECT_DIGEST,
+        "balance": balance.to_string(),
+        "previousTransaction": SPONSORED_DIGEST
+    })
+}
+
+/// Two coins that together exceed `MIN_BALANCE`, but not once a merge has paid its gas
+async fn mount_two_small_coins(mock: &MockEnoki) {
+    mount_json_rpc(
+        &mock.server,
+        "suix_getCoins",
+        json!({
+            "data": [
+                sui_coin(
+                    "0x00000000000000000000000000000000000000000000000000000000000000a1",
+                    6_000_000
+                ),
+                sui_coin(
+                    "0x00000000000000000000000000000000000000000000000000000000000000a2",
+                    6_000_000
+                )
+            ],
+            "nextCursor": null,
+            "hasNextPage": false
+        }),
+    )
+    .await;
+}
+
+/// Whether `result` is the shortfall of `mount_two_small_coins` once the merge fee is added
+fn short_of_the_merge_fee<T>(result: &Result<T, ServiceError>) -> bool {
+    matches!(
+        result,
+        Err(ServiceError::InsufficientGas {
+            available: 12_000_000,
+            required
+        }) if *required == MIN_BALANCE + MERGE_GAS_BUDGET
+    )
+}
+
+/// Whether `mock` was asked to execute a transaction
+async fn executed(mock: &MockEnoki) -> bool {
+    mock.request_bodies("/")
+        .await
+        .iter()
+        .any(|body| body["method"] == "sui_executeTransactionBlock")
+}
+
+#[tokio::test]
+async fn merged_coins_must_cover_the_merge_fee_too() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_two_small_coins(&mock).await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = connect(&mock)
+        .await
+        .get_gas_coin(MIN_BALANCE, account(), zk_inputs(), MAX_EPOCH, keystore)
+        .await;
+
+    assert!(short_of_the_merge_fee(&result));
+    assert!(!executed(&mock).await);
+}
+
+#[tokio::test]
+async fn coins_for_gas_must_cover_the_merge_fee_too() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_two_small_coins(&mock).await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = connect(&mock)
+        .await
+        .get_coins_for_gas(
+            MIN_BALANCE,
+            None,
+            &account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(short_of_the_merge_fee(&result));
+    assert!(!executed(&mock).await);
+}
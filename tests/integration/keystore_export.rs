@@ -0,0 +1,70 @@
+use squad_connect::service::types::ServiceError;
+
+use crate::mock_enoki::{MockEnoki, connect};
+
+const KEY: [u8; 32] = [7; 32];
+const KEYSTORE: &[u8] = br#"["AFakeEphemeralKey"]"#;
+
+#[tokio::test]
+async fn keystore_survives_export_and_import() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect(&mock).await;
+
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let source = dir.join("source.keystore");
+    let destination = dir.join("device_b").join("imported.keystore");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&source, KEYSTORE).unwrap();
+
+    let export = squad_connect
+        .export_keystore(source.clone(), &KEY)
+        .await
+        .expect("keystore is exported");
+    let secret = b"AFakeEphemeralKey";
+    assert!(!export.windows(secret.len()).any(|w| w == secret));
+
+    squad_connect
+        .import_keystore(&export, &KEY, destination.clone())
+        .await
+        .expect("keystore is imported");
+
+    assert_eq!(std::fs::read(&destination).unwrap(), KEYSTORE);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = std::fs::metadata(&destination)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn wrong_key_is_rejected_as_invalid_proof() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect(&mock).await;
+
+    let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+    let source = dir.join("source.keystore");
+    let destination = dir.join("imported.keystore");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(&source, b"[]").unwrap();
+
+    let export = squad_connect
+        .export_keystore(source, &KEY)
+        .await
+        .expect("keystore is exported");
+
+    let result = squad_connect
+        .import_keystore(&export, &[8; 32], destination.clone())
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::InvalidProof(_))));
+    assert!(!destination.exists());
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,70 @@
+use move_core_types::language_storage::TypeTag;
+use squad_connect::client::squad_connect::add_policy_confirm_request;
+use sui_sdk::types::{
+    Identifier, SUI_FRAMEWORK_PACKAGE_ID,
+    base_types::{ObjectID, SequenceNumber},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::{Argument, Command, ObjectArg},
+};
+
+const ITEM_TYPE: &str =
+    "0x00000000000000000000000000000000000000000000000000000000000000f1::nft::Nft";
+
+fn shared(id: ObjectID, mutable: bool) -> ObjectArg {
+    ObjectArg::SharedObject {
+        id,
+        initial_shared_version: SequenceNumber::from_u64(1),
+        mutable,
+    }
+}
+
+#[test]
+fn purchase_request_is_confirmed_in_the_same_transaction() {
+    let item_type: TypeTag = ITEM_TYPE.parse().unwrap();
+    let policy_id = ObjectID::random();
+    let mut builder = ProgrammableTransactionBuilder::new();
+
+    let kiosk = builder.obj(shared(ObjectID::random(), true)).unwrap();
+    let item_id = builder.pure(ObjectID::random()).unwrap();
+    let price = builder.pure(1_000u64).unwrap();
+    let payment = builder.command(Command::SplitCoins(Argument::GasCoin, vec![price]));
+    let purchase = builder.programmable_move_call(
+        SUI_FRAMEWORK_PACKAGE_ID,
+        Identifier::new("kiosk").unwrap(),
+        Identifier::new("purchase").unwrap(),
+        vec![item_type.clone()],
+        vec![kiosk, item_id, payment],
+    );
+    let Argument::Result(purchase_index) = purchase else {
+        panic!("expected a command result, got {:?}", purchase);
+    };
+    let request = Argument::NestedResult(purchase_index, 1);
+
+    let confirmed = add_policy_confirm_request(
+        &mut builder,
+        shared(policy_id, false),
+        request,
+        item_type.clone(),
+    )
+    .expect("confirm_request is added");
+
+    let pt = builder.finish();
+    assert_eq!(confirmed, Argument::Result(purchase_index + 1));
+    assert_eq!(pt.commands.len(), 3);
+
+    let Command::MoveCall(call) = &pt.commands[2] else {
+        panic!("expected a Move call, got {:?}", pt.commands[2]);
+    };
+    assert_eq!(call.package, SUI_FRAMEWORK_PACKAGE_ID);
+    assert_eq!(call.module.to_string(), "transfer_policy");
+    assert_eq!(call.function.to_string(), "confirm_request");
+    assert_eq!(call.type_arguments.len(), 1);
+    assert_eq!(call.arguments[1], request);
+
+    // The policy is the last input, taken by shared reference
+    let Argument::Input(policy_input) = call.arguments[0] else {
+        panic!("expected an input, got {:?}", call.arguments[0]);
+    };
+    assert_eq!(policy_input as usize, pt.inputs.len() - 1);
+    assert_eq!(pt.inputs.len(), 4);
+}
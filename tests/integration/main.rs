@@ -0,0 +1,301 @@
+mod account_state;
+mod address_activity;
+mod api_key_status;
+mod batch_sponsor;
+mod bulk_transfer;
+mod chain_identifier;
+#[cfg(feature = "cookie-session")]
+mod cookie_session;
+#[cfg(feature = "deepbook")]
+mod deepbook_pools;
+mod display_fields;
+mod enoki_base_url;
+mod ephemeral_signing;
+#[cfg(feature = "ws")]
+mod event_subscription;
+mod event_types;
+mod explorer_links;
+mod failover;
+mod gas_coin;
+#[cfg(feature = "encrypted-keystore")]
+mod keystore_export;
+mod kiosk_purchase;
+mod memo_events;
+mod mock_enoki;
+mod network_switch;
+mod oauth_redirect;
+mod object_snapshot;
+mod owned_nfts;
+mod pending_transactions;
+mod proof_refresh;
+mod readonly_handles;
+mod response_limits;
+mod session_expiry;
+mod sponsor_allow_lists;
+mod sponsor_fallback;
+mod sponsored_mint;
+mod stake_positions;
+mod storage_rebate;
+mod system_state;
+
+use std::time::{Duration, Instant};
+
+use mock_enoki::{
+    ADDRESS, CLIENT_ID, JWT, MAX_EPOCH, MockEnoki, NONCE, RANDOMNESS, SALT, builder, connect,
+    nonce_response,
+};
+use squad_connect::{
+    client::dtos::{ZkLoginFlowState, ZkLoginResult},
+    service::types::ServiceError,
+};
+use wiremock::{
+    Mock,
+    matchers::{method, path},
+};
+
+const REDIRECT_URL: &str = "http://localhost:3000/callback";
+const PROOF_DELAY: Duration = Duration::from_millis(500);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(1);
+/// Well past `REQUEST_TIMEOUT`; the mock holds responses back in real time
+const SLOW_RESPONSE_DELAY: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+async fn zklogin_flow_against_mocked_enoki() {
+    let (mock, _) = MockEnoki::start().await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    let (randomness, public_key, max_epoch) = squad_connect.get_zk_proof_params();
+    assert_eq!(randomness, RANDOMNESS);
+    assert_eq!(max_epoch, MAX_EPOCH);
+
+    let nonce_params = squad_connect.get_nonce_params();
+    assert!(nonce_params.is_valid());
+    assert_eq!(squad_connect.get_nonce(), NONCE);
+    assert_eq!(nonce_params.nonce, NONCE);
+    assert_eq!(nonce_params.public_key, public_key);
+
+    let url = squad_connect
+        .get_url(REDIRECT_URL.to_string(), Some("state".to_string()))
+        .await
+        .expect("OAuth URL is built");
+    let url = url::Url::parse(&url).unwrap();
+    let query: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+    assert_eq!(query["nonce"], NONCE);
+    assert_eq!(query["client_id"], CLIENT_ID);
+    assert_eq!(query["redirect_uri"], REDIRECT_URL);
+    assert_eq!(query["state"], "\"state\"");
+
+    squad_connect.set_jwt(JWT.to_string());
+
+    squad_connect
+        .recover_seed_address()
+        .await
+        .expect("proof is returned");
+
+    let account = squad_connect
+        .get_address()
+        .await
+        .expect("account is returned");
+    assert_eq!(account.address, ADDRESS);
+    assert_eq!(account.salt, SALT);
+
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 1);
+
+    let nonce_requests = mock.request_bodies("/v1/zklogin/nonce").await;
+    assert_eq!(nonce_requests.len(), 1);
+    assert_eq!(nonce_requests[0]["ephemeralPublicKey"], public_key);
+
+    let zkp_requests = mock.request_bodies("/v1/zklogin/zkp").await;
+    assert_eq!(zkp_requests.len(), 1);
+    assert_eq!(zkp_requests[0]["ephemeralPublicKey"], public_key);
+    assert_eq!(zkp_requests[0]["maxEpoch"], MAX_EPOCH);
+    assert_eq!(zkp_requests[0]["randomness"], RANDOMNESS);
+
+    squad_connect.reset_session();
+    assert!(squad_connect.get_nonce().is_empty());
+    assert!(!squad_connect.get_nonce_params().is_valid());
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn recreating_the_payload_drops_the_cached_proof() {
+    let (mock, _) = MockEnoki::start().await;
+
+    // Answers the first nonce request; the second reaches the default mock
+    Mock::given(method("POST"))
+        .and(path("/v1/zklogin/nonce"))
+        .respond_with(nonce_response())
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+    squad_connect.set_jwt(JWT.to_string());
+    squad_connect
+        .recover_seed_address()
+        .await
+        .expect("proof is returned");
+    assert_eq!(
+        squad_connect.get_zklogin_flow_state(),
+        ZkLoginFlowState::ReadyToSign
+    );
+
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created again");
+    assert_eq!(
+        squad_connect.get_zklogin_flow_state(),
+        ZkLoginFlowState::NeedsZkProof
+    );
+
+    squad_connect
+        .recover_seed_address()
+        .await
+        .expect("proof is returned for the new session");
+    assert_eq!(mock.request_bodies("/v1/zklogin/zkp").await.len(), 2);
+
+    let (randomness, public_key, max_epoch) = squad_connect.get_zk_proof_params();
+    squad_connect.set_zk_proof_params(randomness, public_key, max_epoch);
+    assert_eq!(
+        squad_connect.get_zklogin_flow_state(),
+        ZkLoginFlowState::NeedsZkProof
+    );
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn zk_proofs_are_requested_for_every_jwt() {
+    let (mock, _) = MockEnoki::start().await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    let proofs = squad_connect.get_multiple_zk_proofs(vec![JWT; 5]).await;
+
+    assert_eq!(proofs.len(), 5);
+    assert!(proofs.iter().all(|proof| proof.is_ok()));
+    assert_eq!(mock.request_bodies("/v1/zklogin/zkp").await.len(), 5);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn zk_proofs_are_requested_concurrently() {
+    let (mock, _) = MockEnoki::start_with_zkp_delay(SLOW_RESPONSE_DELAY).await;
+
+    let mut squad_connect = builder(&mock)
+        .await
+        .request_timeout(REQUEST_TIMEOUT)
+        .build();
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    // The clock only moves to the next timer from here, so every request
+    // times out without waiting on the mock
+    tokio::time::pause();
+    let started = tokio::time::Instant::now();
+
+    let proofs = squad_connect.get_multiple_zk_proofs(vec![JWT; 5]).await;
+
+    // One timeout, not five in a row
+    let elapsed = started.elapsed();
+    assert!(elapsed >= REQUEST_TIMEOUT);
+    assert!(elapsed < REQUEST_TIMEOUT * 2, "requests took {:?}", elapsed);
+
+    assert_eq!(proofs.len(), 5);
+    assert!(
+        proofs
+            .iter()
+            .all(|proof| matches!(proof, Err(ServiceError::Timeout(_))))
+    );
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn account_requests_time_out() {
+    let (mock, _) = MockEnoki::start_with_delays(Duration::ZERO, SLOW_RESPONSE_DELAY).await;
+
+    let squad_connect = builder(&mock)
+        .await
+        .request_timeout(REQUEST_TIMEOUT)
+        .build();
+
+    tokio::time::pause();
+    let started = tokio::time::Instant::now();
+
+    let accounts = squad_connect.get_multiple_accounts(vec![JWT; 3]).await;
+
+    let elapsed = started.elapsed();
+    assert!(elapsed >= REQUEST_TIMEOUT);
+    assert!(elapsed < REQUEST_TIMEOUT * 2, "requests took {:?}", elapsed);
+
+    assert_eq!(accounts.len(), 3);
+    assert!(
+        accounts
+            .iter()
+            .all(|account| matches!(account, Err(ServiceError::Timeout(_))))
+    );
+}
+
+#[tokio::test]
+async fn proof_and_account_are_requested_concurrently() {
+    let (mock, _) = MockEnoki::start_with_delays(PROOF_DELAY, PROOF_DELAY).await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    let started = Instant::now();
+
+    let result: ZkLoginResult = squad_connect
+        .get_zklogin_inputs_from_jwt(JWT.to_string())
+        .await
+        .expect("proof and account are returned")
+        .try_into()
+        .expect("account address is valid");
+
+    let elapsed = started.elapsed();
+    assert!(elapsed >= PROOF_DELAY);
+    assert!(elapsed < PROOF_DELAY * 2, "requests took {:?}", elapsed);
+
+    assert_eq!(result.address.to_string(), ADDRESS);
+    assert_eq!(result.account.salt, SALT);
+
+    assert_eq!(mock.request_bodies("/v1/zklogin/zkp").await.len(), 1);
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 1);
+
+    let _ = std::fs::remove_file(keystore);
+}
@@ -0,0 +1,80 @@
+use serde_json::{Value, json};
+use squad_connect::service::dtos::Network;
+use sui_sdk::{SuiClientBuilder, types::base_types::ObjectID};
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, connect, mount_json_rpc};
+
+const TX_DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+const MEMO_PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const OTHER_PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+
+fn event(seq: u64, package: &str, module: &str, name: &str, parsed_json: Value) -> Value {
+    json!({
+        "id": { "txDigest": TX_DIGEST, "eventSeq": seq.to_string() },
+        "packageId": package,
+        "transactionModule": module,
+        "sender": ADDRESS,
+        "type": format!("{}::{}::{}", package, module, name),
+        "parsedJson": parsed_json,
+        "bcsEncoding": "base64",
+        "bcs": ""
+    })
+}
+
+/// A memo transfer followed by an unrelated event of the same shape
+fn transaction() -> Value {
+    json!({
+        "digest": TX_DIGEST,
+        "events": [
+            event(
+                0,
+                MEMO_PACKAGE,
+                "squad_memo",
+                "MemoEvent",
+                json!({ "sender": ADDRESS, "recipient": ADDRESS, "memo": "rent for May" }),
+            ),
+            event(
+                1,
+                OTHER_PACKAGE,
+                "other",
+                "MemoEvent",
+                json!({ "memo": "not ours" }),
+            ),
+        ]
+    })
+}
+
+#[tokio::test]
+async fn memos_are_read_from_transaction_events() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "sui_getTransactionBlock", transaction()).await;
+
+    let squad_connect = connect(&mock).await;
+
+    let memos = squad_connect
+        .find_memo_events(TX_DIGEST)
+        .await
+        .expect("memos are returned");
+
+    assert_eq!(memos, vec!["rent for May".to_string()]);
+}
+
+#[tokio::test]
+async fn switching_networks_forgets_the_memo_package() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "sui_getChainIdentifier", json!("35834a8a")).await;
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_memo_package_id(Some(ObjectID::from_hex_literal(MEMO_PACKAGE).unwrap()));
+
+    let node = SuiClientBuilder::default()
+        .build(mock.rpc_url())
+        .await
+        .expect("Sui client connects to the mock");
+    squad_connect
+        .with_network(Network::Mainnet, node)
+        .await
+        .expect("network is switched");
+
+    assert_eq!(squad_connect.memo_package_id(), None);
+}
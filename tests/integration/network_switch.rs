@@ -0,0 +1,89 @@
+use serde_json::json;
+use squad_connect::{client::dtos::ZkLoginFlowState, service::dtos::Network};
+use sui_sdk::SuiClientBuilder;
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{CLIENT_ID, JWT, MockEnoki, connect, mount_json_rpc};
+
+const APP_ROUTE: &str = "/v1/app";
+
+async fn mount_app(mock: &MockEnoki) {
+    Mock::given(method("GET"))
+        .and(path(APP_ROUTE))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "authenticationProviders": [
+                    { "providerType": "google", "clientId": CLIENT_ID }
+                ]
+            }
+        })))
+        .mount(&mock.server)
+        .await;
+}
+
+#[tokio::test]
+async fn switching_networks_clears_the_session() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "sui_getChainIdentifier", json!("35834a8a")).await;
+    mount_app(&mock).await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+    squad_connect.set_jwt(JWT.to_string());
+
+    let providers = squad_connect
+        .get_supported_oauth_providers()
+        .await
+        .expect("providers are returned");
+    assert_eq!(providers, vec!["google".to_string()]);
+
+    let node = SuiClientBuilder::default()
+        .build(mock.rpc_url())
+        .await
+        .expect("Sui client connects to the mock");
+    squad_connect
+        .with_network(Network::Mainnet, node)
+        .await
+        .expect("network is switched");
+
+    assert!(matches!(squad_connect.get_network(), Network::Mainnet));
+    assert_eq!(squad_connect.get_nonce(), "");
+    assert_eq!(
+        squad_connect.get_zklogin_flow_state(),
+        ZkLoginFlowState::NeedsPayloadInit
+    );
+
+    // The providers are fetched again for the new network
+    squad_connect
+        .get_supported_oauth_providers()
+        .await
+        .expect("providers are returned");
+    assert_eq!(mock.request_bodies(APP_ROUTE).await.len(), 2);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn oauth_providers_are_cached_on_one_network() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_app(&mock).await;
+
+    let squad_connect = connect(&mock).await;
+
+    for _ in 0..2 {
+        squad_connect
+            .get_supported_oauth_providers()
+            .await
+            .expect("providers are returned");
+    }
+
+    assert_eq!(mock.request_bodies(APP_ROUTE).await.len(), 1);
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
+use sui_sdk::types::base_types::SuiAddress;
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{ADDRESS, JWT, MockEnoki, SALT, connect, zk_inputs};
+
+/// A client past `create_zkp_payload`, waiting for the OAuth callback
+async fn awaiting_callback(mock: &MockEnoki) -> (SquadConnect, PathBuf) {
+    let mut squad_connect = connect(mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+
+    (squad_connect, keystore)
+}
+
+#[tokio::test]
+async fn redirect_signs_in_with_the_token_and_state() {
+    let (mock, _) = MockEnoki::start().await;
+    let derived = SuiAddress::try_from_unpadded(&zk_inputs()).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/v1/zklogin"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "salt": SALT,
+                "address": derived.to_string(),
+                "publicKey": "BQADAAAAAAAA"
+            }
+        })))
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+
+    let (mut squad_connect, keystore) = awaiting_callback(&mock).await;
+    let callback_url = format!(
+        "http://localhost:3000/callback#id_token={}&state=%22squad%22",
+        JWT
+    );
+
+    let (auth, state) = squad_connect
+        .handle_oauth_redirect_with_state::<String>(&callback_url)
+        .await
+        .expect("redirect is handled");
+
+    assert_eq!(auth.address, derived);
+    assert_eq!(auth.account.salt, SALT);
+    assert_eq!(state.as_deref(), Some("squad"));
+    assert_eq!(squad_connect.zk_login_session().jwt, JWT);
+
+    let zkp_requests = mock.request_bodies("/v1/zklogin/zkp").await;
+    assert_eq!(zkp_requests.len(), 1);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn mismatched_address_is_rejected_and_the_token_cleared() {
+    let (mock, _) = MockEnoki::start().await;
+    let (mut squad_connect, keystore) = awaiting_callback(&mock).await;
+
+    // The mocked account address is not derived from the mocked proof
+    let result = squad_connect
+        .handle_oauth_redirect(&format!("http://localhost:3000/callback?id_token={}", JWT))
+        .await;
+
+    assert!(
+        matches!(result, Err(ServiceError::InvalidProof(message)) if message.contains(ADDRESS))
+    );
+    assert!(squad_connect.zk_login_session().jwt.is_empty());
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn callback_without_token_sends_no_request() {
+    let (mock, _) = MockEnoki::start().await;
+    let (mut squad_connect, keystore) = awaiting_callback(&mock).await;
+
+    let result = squad_connect
+        .handle_oauth_redirect("http://localhost:3000/callback?state=%22squad%22")
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::JwtExtraction(_))));
+    assert!(mock.request_bodies("/v1/zklogin").await.is_empty());
+
+    let _ = std::fs::remove_file(keystore);
+}
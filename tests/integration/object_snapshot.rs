@@ -0,0 +1,122 @@
+use serde_json::{Value, json};
+use sui_sdk::types::base_types::ObjectID;
+use wiremock::{
+    Mock, Request, ResponseTemplate,
+    matchers::{body_partial_json, method, path},
+};
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, OBJECT_DIGEST, connect, mount_json_rpc};
+
+/// Transaction that mutated, deleted and created an object on the mocked node
+const TX_DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+
+const MUTATED: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const DELETED: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+const CREATED: &str = "0x00000000000000000000000000000000000000000000000000000000000000c1";
+const UNTOUCHED: &str = "0x00000000000000000000000000000000000000000000000000000000000000d1";
+const GAS: &str = "0x00000000000000000000000000000000000000000000000000000000000000e1";
+
+fn owned_ref(object_id: &str, version: u64) -> Value {
+    json!({
+        "owner": { "AddressOwner": ADDRESS },
+        "reference": { "objectId": object_id, "version": version, "digest": OBJECT_DIGEST }
+    })
+}
+
+fn transaction() -> Value {
+    json!({
+        "digest": TX_DIGEST,
+        "effects": {
+            "messageVersion": "v1",
+            "status": { "status": "success" },
+            "executedEpoch": "500",
+            "gasUsed": {
+                "computationCost": "1000000",
+                "storageCost": "2000000",
+                "storageRebate": "1000000",
+                "nonRefundableStorageFee": "10000"
+            },
+            "modifiedAtVersions": [
+                { "objectId": MUTATED, "sequenceNumber": "1" },
+                { "objectId": DELETED, "sequenceNumber": "2" },
+                { "objectId": GAS, "sequenceNumber": "1" }
+            ],
+            "transactionDigest": TX_DIGEST,
+            "created": [owned_ref(CREATED, 3)],
+            "mutated": [owned_ref(MUTATED, 3), owned_ref(GAS, 3)],
+            "deleted": [{ "objectId": DELETED, "version": 3, "digest": OBJECT_DIGEST }],
+            "gasObject": owned_ref(GAS, 3),
+            "dependencies": []
+        }
+    })
+}
+
+/// Answers `sui_tryGetPastObject` for every version the transaction read or wrote
+async fn mount_past_objects(mock: &MockEnoki) {
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .and(body_partial_json(
+            json!({ "method": "sui_tryGetPastObject" }),
+        ))
+        .respond_with(|request: &Request| {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            let object_id = body["params"][0].as_str().unwrap_or_default();
+            let version = match &body["params"][1] {
+                Value::String(version) => version.parse().unwrap_or_default(),
+                version => version.as_u64().unwrap_or_default(),
+            };
+
+            let result = match (object_id, version) {
+                (MUTATED, 1 | 3) | (DELETED, 2) | (CREATED, 3) | (GAS, 1 | 3) => json!({
+                    "status": "VersionFound",
+                    "details": {
+                        "objectId": object_id,
+                        "version": version.to_string(),
+                        "digest": OBJECT_DIGEST
+                    }
+                }),
+                _ => json!({ "status": "VersionNotFound", "details": [object_id, version] }),
+            };
+
+            ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": body["id"],
+                "result": result
+            }))
+        })
+        .mount(&mock.server)
+        .await;
+}
+
+#[tokio::test]
+async fn snapshots_show_objects_before_and_after_a_transaction() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "sui_getTransactionBlock", transaction()).await;
+    mount_past_objects(&mock).await;
+
+    let squad_connect = connect(&mock).await;
+
+    let ids: Vec<ObjectID> = [MUTATED, DELETED, CREATED, UNTOUCHED]
+        .iter()
+        .map(|id| id.parse().unwrap())
+        .collect();
+
+    let snapshots = squad_connect
+        .object_snapshot_before_and_after(TX_DIGEST, ids.clone())
+        .await
+        .expect("snapshots are returned");
+
+    let versions = |id: &ObjectID| {
+        let (before, after) = &snapshots[id];
+        (
+            before.as_ref().map(|object| object.version.value()),
+            after.as_ref().map(|object| object.version.value()),
+        )
+    };
+
+    assert_eq!(snapshots.len(), 4);
+    assert_eq!(versions(&ids[0]), (Some(1), Some(3)));
+    assert_eq!(versions(&ids[1]), (Some(2), None));
+    assert_eq!(versions(&ids[2]), (None, Some(3)));
+    assert_eq!(versions(&ids[3]), (None, None));
+}
@@ -0,0 +1,200 @@
+use serde_json::{Value, json};
+use squad_connect::{
+    client::{
+        nft::{NftInfo, filter_by_package},
+        squad_connect::SquadConnect,
+    },
+    service::types::ServiceError,
+};
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::mock_enoki::{
+    ADDRESS, JWT, MAX_EPOCH, MockEnoki, OBJECT_DIGEST, account, connect, mount_json_rpc, zk_inputs,
+};
+
+const COIN: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const PLAIN: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+const NFT: &str = "0x00000000000000000000000000000000000000000000000000000000000000c1";
+const PARTIAL_NFT: &str = "0x00000000000000000000000000000000000000000000000000000000000000d1";
+
+const COLLECTION: &str = "0x00000000000000000000000000000000000000000000000000000000000000f1";
+
+fn owned_object(object_id: &str, type_: &str, display: Value) -> Value {
+    json!({
+        "data": {
+            "objectId": object_id,
+            "version": "3",
+            "digest": OBJECT_DIGEST,
+            "type": type_,
+            "display": display
+        }
+    })
+}
+
+/// A coin, an object without `Display` and two NFTs, one missing fields
+fn owned_objects() -> Value {
+    json!({
+        "data": [
+            owned_object(
+                COIN,
+                "0x2::coin::Coin<0x2::sui::SUI>",
+                json!({ "data": null, "error": null }),
+            ),
+            owned_object(
+                PLAIN,
+                &format!("{}::vault::Vault", COLLECTION),
+                json!({ "data": null, "error": null }),
+            ),
+            owned_object(
+                NFT,
+                &format!("{}::nft_squad::Nft", COLLECTION),
+                json!({
+                    "data": {
+                        "name": "Squad #1",
+                        "description": "First of the squad",
+                        "image_url": "https://example.com/1.png"
+                    },
+                    "error": null
+                }),
+            ),
+            owned_object(
+                PARTIAL_NFT,
+                "0x3::badge::Badge",
+                json!({ "data": { "name": "Badge" }, "error": null }),
+            ),
+        ],
+        "nextCursor": null,
+        "hasNextPage": false
+    })
+}
+
+/// A client whose wallet holds `owned_objects`
+async fn connect_with_objects(mock: &MockEnoki) -> SquadConnect {
+    mount_json_rpc(&mock.server, "suix_getOwnedObjects", owned_objects()).await;
+
+    connect(mock).await
+}
+
+#[tokio::test]
+async fn owned_nfts_skip_coins_and_objects_without_display() {
+    let (mock, _) = MockEnoki::start().await;
+    let squad_connect = connect_with_objects(&mock).await;
+
+    let nfts = squad_connect
+        .get_owned_nfts(ADDRESS.parse::<SuiAddress>().unwrap())
+        .await
+        .expect("NFTs are returned");
+
+    assert_eq!(nfts.len(), 2);
+
+    assert_eq!(nfts[0].object_id.to_string(), NFT);
+    assert_eq!(nfts[0].name.as_deref(), Some("Squad #1"));
+    assert_eq!(nfts[0].description.as_deref(), Some("First of the squad"));
+    assert_eq!(
+        nfts[0].image_url.as_deref(),
+        Some("https://example.com/1.png")
+    );
+    assert!(nfts[0].type_.ends_with("::nft_squad::Nft"));
+
+    assert_eq!(nfts[1].object_id.to_string(), PARTIAL_NFT);
+    assert_eq!(nfts[1].name.as_deref(), Some("Badge"));
+    assert_eq!(nfts[1].description, None);
+    assert_eq!(nfts[1].image_url, None);
+}
+
+#[tokio::test]
+async fn my_nfts_are_listed_for_the_enoki_account() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect_with_objects(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    let nfts = squad_connect
+        .get_my_nfts()
+        .await
+        .expect("NFTs are returned");
+
+    assert_eq!(nfts.len(), 2);
+
+    let queries = mock.request_bodies("/").await;
+    let query = queries
+        .iter()
+        .find(|body| body["method"] == "suix_getOwnedObjects")
+        .expect("owned objects are queried");
+    assert_eq!(
+        query["params"][0]
+            .as_str()
+            .unwrap()
+            .parse::<SuiAddress>()
+            .unwrap(),
+        ADDRESS.parse::<SuiAddress>().unwrap()
+    );
+}
+
+#[test]
+fn nfts_are_filtered_by_package() {
+    let nft = |type_: &str| NftInfo {
+        object_id: NFT.parse().unwrap(),
+        name: None,
+        description: None,
+        image_url: None,
+        type_: type_.to_string(),
+    };
+    let nfts = vec![
+        nft(&format!("{}::nft_squad::Nft", COLLECTION)),
+        nft("0x3::badge::Badge"),
+        nft("0x0000000000000000000000000000000000000000000000000000000000000003::badge::Badge"),
+    ];
+
+    assert_eq!(filter_by_package(&nfts, COLLECTION).len(), 1);
+    assert_eq!(filter_by_package(&nfts, "0xf1").len(), 1);
+    assert_eq!(filter_by_package(&nfts, "0x3").len(), 2);
+    assert!(filter_by_package(&nfts, "0x4").is_empty());
+    assert!(filter_by_package(&nfts, "not-an-id").is_empty());
+}
+
+#[tokio::test]
+async fn transferring_someone_elses_nft_reads_it_once() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getObject",
+        json!({
+            "data": {
+                "objectId": NFT,
+                "version": "3",
+                "digest": OBJECT_DIGEST,
+                "owner": { "AddressOwner": SuiAddress::random_for_testing_only() }
+            }
+        }),
+    )
+    .await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = connect(&mock)
+        .await
+        .transfer_nft(
+            NFT.parse().unwrap(),
+            SuiAddress::random_for_testing_only(),
+            10_000_000,
+            account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::InvalidProof(_))));
+    let requests = mock.request_bodies("/").await;
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|body| body["method"] == "sui_getObject")
+            .count(),
+        1
+    );
+    assert!(
+        requests
+            .iter()
+            .all(|body| body["method"] != "suix_getCoins")
+    );
+}
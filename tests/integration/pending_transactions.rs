@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use squad_connect::service::types::ServiceError;
+use sui_sdk::types::base_types::SuiAddress;
+
+use crate::mock_enoki::{ADDRESS, MockEnoki, connect, mount_json_rpc};
+
+const PENDING_DIGEST: &str = "2qo2mC7GvBBEPZTmZeYXsc5KhzboKSen6DekBytar1fy";
+const FINAL_DIGEST: &str = "7Hk3oZ1n1vJ6yF8GxWkV9gTqD1rNnQzE4xW2mJpL5sYb";
+
+fn transaction(digest: &str, checkpoint: Option<u64>) -> Value {
+    let mut response = uncheckpointed(digest);
+
+    if let Some(checkpoint) = checkpoint {
+        response["checkpoint"] = json!(checkpoint.to_string());
+        response["timestampMs"] = json!("1700000000000");
+    }
+
+    response
+}
+
+/// A transaction as the node reports it before any checkpoint includes it
+fn uncheckpointed(digest: &str) -> Value {
+    json!({
+        "digest": digest,
+        "transaction": {
+            "data": {
+                "messageVersion": "v1",
+                "transaction": {
+                    "kind": "ProgrammableTransaction",
+                    "inputs": [],
+                    "transactions": []
+                },
+                "sender": ADDRESS,
+                "gasData": {
+                    "payment": [],
+                    "owner": ADDRESS,
+                    "price": "750",
+                    "budget": "5000000"
+                }
+            },
+            "txSignatures": []
+        }
+    })
+}
+
+#[tokio::test]
+async fn only_transactions_outside_checkpoints_are_pending() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "suix_queryTransactionBlocks",
+        json!({
+            "data": [transaction(PENDING_DIGEST, None), transaction(FINAL_DIGEST, Some(42))],
+            "nextCursor": null,
+            "hasNextPage": false
+        }),
+    )
+    .await;
+
+    let squad_connect = connect(&mock).await;
+
+    let address: SuiAddress = ADDRESS.parse().unwrap();
+    let pending = squad_connect
+        .get_pending_transactions(address)
+        .await
+        .expect("pending transactions are returned");
+
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].digest, PENDING_DIGEST);
+    assert_eq!(pending[0].sender, address);
+    assert_eq!(pending[0].gas_price, 750);
+    assert_eq!(pending[0].gas_budget, 5_000_000);
+    assert_eq!(pending[0].submitted_at, None);
+
+    let queries = mock.request_bodies("/").await;
+    let query = queries
+        .iter()
+        .find(|body| body["method"] == "suix_queryTransactionBlocks")
+        .expect("transactions are queried");
+    assert!(query["params"][0]["filter"]["FromAddress"].is_string());
+    assert_eq!(query["params"][3], true);
+
+    assert!(matches!(
+        squad_connect
+            .cancel_pending_transaction(PENDING_DIGEST)
+            .await,
+        Err(ServiceError::Service(_))
+    ));
+}
+
+#[tokio::test]
+async fn node_timestamps_are_kept_for_uncheckpointed_transactions() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut executed = uncheckpointed(PENDING_DIGEST);
+    executed["timestampMs"] = json!("1700000000000");
+    mount_json_rpc(
+        &mock.server,
+        "suix_queryTransactionBlocks",
+        json!({ "data": [executed], "nextCursor": null, "hasNextPage": false }),
+    )
+    .await;
+
+    let transactions = connect(&mock)
+        .await
+        .get_pending_transactions(ADDRESS.parse().unwrap())
+        .await
+        .expect("uncheckpointed transactions are returned");
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].digest, PENDING_DIGEST);
+    assert_eq!(transactions[0].submitted_at, Some(1_700_000_000_000));
+}
+
+#[tokio::test]
+async fn checkpoint_wait_follows_the_transactions_checkpoint() {
+    let (mock, _) = MockEnoki::start().await;
+    // Far behind the latest checkpoint, which is never asked for
+    mount_json_rpc(
+        &mock.server,
+        "sui_getTransactionBlock",
+        transaction(FINAL_DIGEST, Some(42)),
+    )
+    .await;
+    mount_json_rpc(
+        &mock.server,
+        "sui_getCheckpoint",
+        json!({
+            "epoch": "1",
+            "sequenceNumber": "42",
+            "digest": PENDING_DIGEST,
+            "networkTotalTransactions": "100",
+            "epochRollingGasCostSummary": {
+                "computationCost": "0",
+                "storageCost": "0",
+                "storageRebate": "0",
+                "nonRefundableStorageFee": "0"
+            },
+            "timestampMs": "1700000000000",
+            "transactions": [FINAL_DIGEST],
+            "checkpointCommitments": [],
+            // Compressed generator of G1
+            "validatorSignature": "l/HTpzGX15QmlWOMT6msD8NojE+XdLkFoU46PxcbrFhsVeg/+Xoa7/s68ArbIsa7"
+        }),
+    )
+    .await;
+
+    let checkpoint = connect(&mock)
+        .await
+        .wait_for_checkpoint_containing_tx(FINAL_DIGEST, Duration::from_secs(5))
+        .await
+        .expect("checkpoint is returned");
+
+    assert_eq!(checkpoint.sequence_number, 42);
+    let requests = mock.request_bodies("/").await;
+    assert!(
+        requests
+            .iter()
+            .all(|body| body["method"] != "sui_getLatestCheckpointSequenceNumber")
+    );
+}
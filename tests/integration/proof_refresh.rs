@@ -0,0 +1,73 @@
+use squad_connect::{
+    client::{dtos::ZkLoginFlowState, squad_connect::SquadConnect},
+    service::types::ServiceError,
+};
+
+use crate::mock_enoki::{JWT, MAX_EPOCH, MockEnoki, connect, mount_system_state, zk_inputs};
+
+/// A client with a session for `MAX_EPOCH` while the network is in `epoch`
+async fn session_in_epoch(mock: &MockEnoki, epoch: u64) -> SquadConnect {
+    mount_system_state(&mock.server, epoch).await;
+
+    let mut squad_connect = connect(mock).await;
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+    squad_connect.set_jwt(JWT.to_string());
+
+    let _ = std::fs::remove_file(keystore);
+
+    squad_connect
+}
+
+#[tokio::test]
+async fn valid_proof_is_reused() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = session_in_epoch(&mock, MAX_EPOCH).await;
+
+    let zk_inputs = squad_connect
+        .use_proof_or_refresh(Some(zk_inputs()))
+        .await
+        .expect("existing proof is still valid");
+
+    assert_eq!(
+        serde_json::to_value(zk_inputs).unwrap(),
+        serde_json::to_value(crate::mock_enoki::zk_inputs()).unwrap()
+    );
+    assert!(mock.request_bodies("/v1/zklogin/zkp").await.is_empty());
+}
+
+#[tokio::test]
+async fn missing_proof_is_fetched_from_enoki() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = session_in_epoch(&mock, MAX_EPOCH - 1).await;
+
+    let zk_inputs = squad_connect
+        .use_proof_or_refresh(None)
+        .await
+        .expect("proof is fetched");
+
+    assert_eq!(
+        serde_json::to_value(zk_inputs).unwrap(),
+        serde_json::to_value(crate::mock_enoki::zk_inputs()).unwrap()
+    );
+    assert_eq!(mock.request_bodies("/v1/zklogin/zkp").await.len(), 1);
+}
+
+#[tokio::test]
+async fn proof_past_max_epoch_clears_the_session() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = session_in_epoch(&mock, MAX_EPOCH + 1).await;
+
+    let result = squad_connect.use_proof_or_refresh(Some(zk_inputs())).await;
+
+    assert!(matches!(result, Err(ServiceError::InvalidProof(_))));
+    assert_eq!(
+        squad_connect.get_zklogin_flow_state(),
+        ZkLoginFlowState::NeedsOauthCallback
+    );
+    assert!(mock.request_bodies("/v1/zklogin/zkp").await.is_empty());
+}
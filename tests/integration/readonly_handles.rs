@@ -0,0 +1,58 @@
+use squad_connect::client::squad_connect::SquadConnect;
+
+use crate::mock_enoki::{ADDRESS, JWT, MockEnoki, SALT, connect};
+
+async fn signed_in(mock: &MockEnoki) -> SquadConnect {
+    let mut squad_connect = connect(mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    squad_connect
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn read_only_copies_read_concurrently() {
+    let (mock, _) = MockEnoki::start().await;
+    let readonly = signed_in(&mock).await.clone_readonly();
+
+    let tasks: Vec<_> = (0..4)
+        .map(|_| {
+            let readonly = readonly.clone();
+            tokio::spawn(async move { readonly.get_address().await })
+        })
+        .collect();
+
+    for task in tasks {
+        let account = task
+            .await
+            .expect("read task completes")
+            .expect("account is returned");
+
+        assert_eq!(account.address, ADDRESS);
+        assert_eq!(account.salt, SALT);
+    }
+
+    assert_eq!(mock.request_bodies("/v1/zklogin").await.len(), 4);
+}
+
+#[tokio::test]
+async fn borrowed_handles_share_the_instance() {
+    let (mock, _) = MockEnoki::start().await;
+    let squad_connect = signed_in(&mock).await;
+
+    let first = squad_connect.readonly();
+    let second = squad_connect.readonly();
+
+    let (first, second) = tokio::join!(first.get_address(), second.get_address());
+
+    assert_eq!(first.expect("account is returned").address, ADDRESS);
+    assert_eq!(second.expect("account is returned").address, ADDRESS);
+}
+
+#[tokio::test]
+#[should_panic(expected = "SquadConnect::set_jwt called on a ReadOnlySquadConnect")]
+async fn mutating_a_read_only_copy_panics() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut readonly = connect(&mock).await.clone_readonly();
+
+    readonly.set_jwt(JWT.to_string());
+}
@@ -0,0 +1,60 @@
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{JWT, MockEnoki, builder};
+
+const MAX_RESPONSE_SIZE_BYTES: u64 = 64;
+
+/// A client limited to `MAX_RESPONSE_SIZE_BYTES`, with Enoki answering the
+/// account request with `status` and a body one byte over the limit
+async fn oversized_account_response(status: u16) -> (MockEnoki, SquadConnect) {
+    let (mock, _) = MockEnoki::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/zklogin"))
+        .respond_with(
+            ResponseTemplate::new(status)
+                .set_body_string("x".repeat(MAX_RESPONSE_SIZE_BYTES as usize + 1)),
+        )
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+
+    let mut squad_connect = builder(&mock)
+        .await
+        .max_response_size_bytes(MAX_RESPONSE_SIZE_BYTES)
+        .build();
+    squad_connect.set_jwt(JWT.to_string());
+
+    (mock, squad_connect)
+}
+
+#[tokio::test]
+async fn oversized_response_is_refused() {
+    let (_mock, squad_connect) = oversized_account_response(200).await;
+
+    let Err(ServiceError::InvalidResponse(message)) = squad_connect.get_address().await else {
+        panic!("an oversized response is invalid");
+    };
+
+    assert_eq!(message, "Response exceeded size limit");
+}
+
+#[tokio::test]
+async fn oversized_error_body_is_not_read() {
+    let (_mock, squad_connect) = oversized_account_response(500).await;
+
+    let Err(ServiceError::Network(message)) = squad_connect.get_address().await else {
+        panic!("a failed request is a network error");
+    };
+
+    assert!(
+        message.contains("Response exceeded size limit"),
+        "{}",
+        message
+    );
+    assert!(!message.contains("xxxx"), "{}", message);
+}
@@ -0,0 +1,83 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use squad_connect::client::squad_connect::SquadConnect;
+
+use crate::mock_enoki::{MockEnoki, RANDOMNESS, connect, mount_system_state};
+
+const CURRENT_EPOCH: u64 = 10;
+
+/// A client with a session for `max_epoch` while the network is in `CURRENT_EPOCH`
+async fn session_until(mock: &MockEnoki, max_epoch: u64) -> SquadConnect {
+    mount_system_state(&mock.server, CURRENT_EPOCH).await;
+
+    let mut squad_connect = connect(mock).await;
+    squad_connect.set_zk_proof_params(RANDOMNESS.to_string(), String::new(), max_epoch);
+
+    squad_connect
+}
+
+/// An unsigned JWT expiring at `exp`, in Unix seconds
+fn jwt_expiring_at(exp: u64) -> String {
+    let payload = URL_SAFE_NO_PAD.encode(format!(r#"{{"sub":"123","exp":{}}}"#, exp));
+
+    format!("eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl", payload)
+}
+
+#[tokio::test]
+async fn far_max_epoch_saturates_instead_of_overflowing() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = session_until(&mock, u64::MAX).await;
+    squad_connect.set_jwt(jwt_expiring_at(u64::MAX));
+
+    let remaining = squad_connect
+        .get_zklogin_session_duration_estimate()
+        .await
+        .expect("estimate is returned");
+    assert!(!remaining.is_zero());
+
+    let advice = squad_connect
+        .get_zklogin_nonce_expiry_advice()
+        .await
+        .expect("advice is returned");
+    assert!(advice.starts_with("Session expires in ~"), "{}", advice);
+    assert!(
+        advice.ends_with(&format!("Re-authenticate before epoch {}.", u64::MAX)),
+        "{}",
+        advice
+    );
+}
+
+#[tokio::test]
+async fn session_past_max_epoch_has_expired() {
+    let (mock, _) = MockEnoki::start().await;
+    let squad_connect = session_until(&mock, CURRENT_EPOCH - 1).await;
+
+    assert!(
+        squad_connect
+            .get_zklogin_session_duration_estimate()
+            .await
+            .expect("estimate is returned")
+            .is_zero()
+    );
+    assert_eq!(
+        squad_connect
+            .get_zklogin_nonce_expiry_advice()
+            .await
+            .expect("advice is returned"),
+        "Session expired. Call create_zkp_payload to restart."
+    );
+}
+
+#[tokio::test]
+async fn expired_jwt_asks_for_a_new_sign_in() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = session_until(&mock, CURRENT_EPOCH + 2).await;
+    squad_connect.set_jwt(jwt_expiring_at(1));
+
+    assert_eq!(
+        squad_connect
+            .get_zklogin_nonce_expiry_advice()
+            .await
+            .expect("advice is returned"),
+        "JWT expired. Complete the OAuth flow again to obtain a new one."
+    );
+}
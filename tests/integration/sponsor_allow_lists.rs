@@ -0,0 +1,42 @@
+use squad_connect::service::types::ServiceError;
+use sui_sdk::types::{
+    base_types::SuiAddress, programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::TransactionData,
+};
+
+use crate::mock_enoki::{ADDRESS, MAX_EPOCH, MockEnoki, account, connect, zk_inputs};
+
+#[tokio::test]
+async fn invalid_allowed_addresses_never_reach_enoki() {
+    let (mock, _) = MockEnoki::start().await;
+    let sender: SuiAddress = ADDRESS.parse().unwrap();
+    let tx = TransactionData::new_programmable(
+        sender,
+        vec![],
+        ProgrammableTransactionBuilder::new().finish(),
+        10_000_000,
+        1000,
+    );
+
+    let result = connect(&mock)
+        .await
+        .sponsor_transaction_data(
+            tx,
+            account(),
+            zk_inputs(),
+            MAX_EPOCH,
+            std::env::temp_dir().join("unused.keystore"),
+            vec!["not-an-address".to_string()],
+            vec![],
+        )
+        .await;
+
+    assert!(
+        matches!(result, Err(ServiceError::InvalidResponse(message)) if message.contains("not-an-address"))
+    );
+    assert!(
+        mock.request_bodies("/v1/transaction-blocks/sponsor")
+            .await
+            .is_empty()
+    );
+}
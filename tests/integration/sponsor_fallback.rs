@@ -0,0 +1,74 @@
+use serde_json::json;
+use squad_connect::service::types::ServiceError;
+use sui_sdk::types::{
+    base_types::SuiAddress, programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::TransactionData,
+};
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{
+    ADDRESS, MAX_EPOCH, MockEnoki, account, connect, mount_json_rpc, zk_inputs,
+};
+
+const GAS_BUDGET: u64 = 10_000_000;
+
+#[tokio::test]
+async fn forbidden_sponsorship_falls_back_to_the_wallet() {
+    let (mock, _) = MockEnoki::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/transaction-blocks/sponsor"))
+        .respond_with(ResponseTemplate::new(403).set_body_string("address not allowed"))
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+    mount_json_rpc(&mock.server, "suix_getReferenceGasPrice", json!("1000")).await;
+    mount_json_rpc(
+        &mock.server,
+        "suix_getCoins",
+        json!({ "data": [], "nextCursor": null, "hasNextPage": false }),
+    )
+    .await;
+
+    let sender: SuiAddress = ADDRESS.parse().unwrap();
+    // A set budget skips the dev-inspect estimate
+    let tx = TransactionData::new_programmable(
+        sender,
+        vec![],
+        ProgrammableTransactionBuilder::new().finish(),
+        GAS_BUDGET,
+        1000,
+    );
+    let account = account();
+
+    let result = connect(&mock)
+        .await
+        .sponsor_with_fallback(
+            tx,
+            account,
+            zk_inputs(),
+            MAX_EPOCH,
+            std::env::temp_dir().join("unused.keystore"),
+            vec![],
+            vec![],
+        )
+        .await;
+
+    // Reaching coin selection shows the 403 was not returned as is
+    assert!(matches!(
+        result,
+        Err(ServiceError::InsufficientGas {
+            available: 0,
+            required: GAS_BUDGET
+        })
+    ));
+    assert_eq!(
+        mock.request_bodies("/v1/transaction-blocks/sponsor")
+            .await
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,196 @@
+use fastcrypto::encoding::{Base64, Encoding};
+use serde_json::json;
+use sui_sdk::types::{
+    base_types::{ObjectID, SuiAddress},
+    programmable_transaction_builder::ProgrammableTransactionBuilder,
+    transaction::TransactionData,
+};
+use wiremock::{
+    Mock, ResponseTemplate,
+    matchers::{method, path},
+};
+
+use crate::mock_enoki::{
+    ADDRESS, JWT, MockEnoki, OBJECT_DIGEST, SPONSORED_DIGEST, connect, mount_json_rpc, zk_inputs,
+};
+
+/// Upgraded version of `ORIGINAL_PACKAGE`, the one the mint is called on
+const PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000f2";
+const ORIGINAL_PACKAGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000f1";
+const NFT: &str = "0x00000000000000000000000000000000000000000000000000000000000000c1";
+const BADGE: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+const COIN: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+
+fn created(object_id: &str, object_type: &str) -> serde_json::Value {
+    json!({
+        "type": "created",
+        "sender": ADDRESS,
+        "owner": { "AddressOwner": ADDRESS },
+        "objectType": object_type,
+        "objectId": object_id,
+        "version": "3",
+        "digest": OBJECT_DIGEST
+    })
+}
+
+/// `PACKAGE` as an upgrade whose `nft_squad` types come from `ORIGINAL_PACKAGE`
+async fn mount_package(mock: &MockEnoki) {
+    mount_json_rpc(
+        &mock.server,
+        "sui_getObject",
+        json!({
+            "data": {
+                "objectId": PACKAGE,
+                "version": "2",
+                "digest": OBJECT_DIGEST,
+                "bcs": {
+                    "dataType": "package",
+                    "id": PACKAGE,
+                    "version": "2",
+                    "moduleMap": {},
+                    "typeOriginTable": [
+                        {
+                            "module_name": "nft_squad",
+                            "datatype_name": "Nft",
+                            "package": ORIGINAL_PACKAGE
+                        },
+                        {
+                            "module_name": "other",
+                            "datatype_name": "Badge",
+                            "package": PACKAGE
+                        }
+                    ],
+                    "linkageTable": {}
+                }
+            }
+        }),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn sponsored_mint_returns_the_minted_nft() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_package(&mock).await;
+    let sender: SuiAddress = ADDRESS.parse().unwrap();
+
+    // Enoki answers with the transaction to sign, gas included
+    let sponsored = TransactionData::new_programmable(
+        sender,
+        vec![],
+        ProgrammableTransactionBuilder::new().finish(),
+        0,
+        0,
+    );
+    Mock::given(method("POST"))
+        .and(path("/v1/transaction-blocks/sponsor"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "digest": SPONSORED_DIGEST,
+                "bytes": Base64::encode(bcs::to_bytes(&sponsored).unwrap())
+            }
+        })))
+        .with_priority(1)
+        .mount(&mock.server)
+        .await;
+
+    mount_json_rpc(
+        &mock.server,
+        "sui_getTransactionBlock",
+        json!({
+            "digest": SPONSORED_DIGEST,
+            "objectChanges": [
+                created(COIN, "0x2::coin::Coin<0x2::sui::SUI>"),
+                created(BADGE, &format!("{}::other::Badge", PACKAGE)),
+                created(NFT, &format!("{}::nft_squad::Nft", ORIGINAL_PACKAGE)),
+            ]
+        }),
+    )
+    .await;
+
+    let mut squad_connect = connect(&mock).await;
+
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+    squad_connect
+        .create_zkp_payload(keystore.clone())
+        .await
+        .expect("nonce is created");
+    squad_connect.set_jwt(JWT.to_string());
+
+    let (zk_inputs, account) = squad_connect
+        .get_zklogin_proof_and_account()
+        .await
+        .expect("proof and account are returned");
+    let (_, _, max_epoch) = squad_connect.get_zk_proof_params();
+
+    let result = squad_connect
+        .create_sponsored_nft_mint(
+            ObjectID::from_hex_literal(PACKAGE).unwrap(),
+            "nft_squad::mint",
+            "Squad #1",
+            "First of the squad",
+            "https://example.com/1.png",
+            sender,
+            account,
+            zk_inputs,
+            max_epoch,
+            keystore.clone(),
+            vec![],
+        )
+        .await
+        .expect("NFT is minted");
+
+    assert_eq!(result.digest, SPONSORED_DIGEST);
+    assert_eq!(result.nft_id, ObjectID::from_hex_literal(NFT).unwrap());
+
+    let sponsor_requests = mock.request_bodies("/v1/transaction-blocks/sponsor").await;
+    assert_eq!(
+        sponsor_requests[0]["allowedMoveCallTargets"],
+        json!([format!("{}::nft_squad::mint", PACKAGE)])
+    );
+    let submissions = mock
+        .request_bodies(&format!(
+            "/v1/transaction-blocks/sponsor/{}",
+            SPONSORED_DIGEST
+        ))
+        .await;
+    assert_eq!(submissions.len(), 1);
+
+    let _ = std::fs::remove_file(keystore);
+}
+
+#[tokio::test]
+async fn mint_function_must_name_the_module() {
+    let (mock, _) = MockEnoki::start().await;
+
+    let mut squad_connect = connect(&mock).await;
+    squad_connect.set_jwt(JWT.to_string());
+
+    let account = squad_connect
+        .get_address()
+        .await
+        .expect("account is returned");
+
+    let result = squad_connect
+        .create_sponsored_nft_mint(
+            ObjectID::from_hex_literal(PACKAGE).unwrap(),
+            "mint",
+            "Squad #1",
+            "First of the squad",
+            "https://example.com/1.png",
+            ADDRESS.parse().unwrap(),
+            account,
+            zk_inputs(),
+            12,
+            std::env::temp_dir().join("unused.keystore"),
+            vec![],
+        )
+        .await;
+
+    assert!(result.is_err());
+    assert!(
+        mock.request_bodies("/v1/transaction-blocks/sponsor")
+            .await
+            .is_empty()
+    );
+}
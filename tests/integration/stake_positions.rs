@@ -0,0 +1,95 @@
+use serde_json::{Value, json};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+use crate::mock_enoki::{
+    ADDRESS, MAX_EPOCH, MockEnoki, account, connect, mount_json_rpc, zk_inputs,
+};
+
+const VALIDATOR: &str = "0x8c2b3a4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809";
+const POOL_ID: &str = "0x1b2c3d4e5f60718293a4b5c6d7e8f9012b3c4d5e6f708192a3b4c5d6e7f8091a";
+const ACTIVE_STAKE_ID: &str = "0x2c3d4e5f60718293a4b5c6d7e8f9012b3c4d5e6f708192a3b4c5d6e7f8091a2b";
+const PENDING_STAKE_ID: &str = "0x3d4e5f60718293a4b5c6d7e8f9012b3c4d5e6f708192a3b4c5d6e7f8091a2b3c";
+
+fn stakes(stakes: Value) -> Value {
+    json!([{
+        "validatorAddress": VALIDATOR,
+        "stakingPool": POOL_ID,
+        "stakes": stakes
+    }])
+}
+
+#[tokio::test]
+async fn stake_positions_carry_the_node_reward_estimate() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(
+        &mock.server,
+        "suix_getStakes",
+        stakes(json!([
+            {
+                "stakedSuiId": ACTIVE_STAKE_ID,
+                "stakeRequestEpoch": "10",
+                "stakeActiveEpoch": "11",
+                "principal": "1000000000",
+                "status": "Active",
+                "estimatedReward": "25000000"
+            },
+            {
+                "stakedSuiId": PENDING_STAKE_ID,
+                "stakeRequestEpoch": "20",
+                "stakeActiveEpoch": "21",
+                "principal": "2000000000",
+                "status": "Pending"
+            }
+        ])),
+    )
+    .await;
+
+    let staker: SuiAddress = ADDRESS.parse().unwrap();
+    let positions = connect(&mock)
+        .await
+        .get_user_stake_positions(staker)
+        .await
+        .expect("stake positions are returned");
+
+    assert_eq!(positions.len(), 2);
+    assert_eq!(
+        positions[0].staked_sui_id,
+        ObjectID::from_hex_literal(ACTIVE_STAKE_ID).unwrap()
+    );
+    assert_eq!(positions[0].validator, VALIDATOR.parse().unwrap());
+    assert_eq!(positions[0].principal, 1_000_000_000);
+    assert_eq!(positions[0].estimated_reward, 25_000_000);
+    assert_eq!(positions[0].activation_epoch, 11);
+    assert_eq!(positions[1].estimated_reward, 0);
+}
+
+#[tokio::test]
+async fn claiming_without_stakes_sends_nothing() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_json_rpc(&mock.server, "suix_getStakes", json!([])).await;
+
+    let account = account();
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let claims = connect(&mock)
+        .await
+        .claim_all_staking_rewards(
+            ADDRESS.parse().unwrap(),
+            10_000_000,
+            account,
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await
+        .expect("nothing to claim is not an error");
+
+    assert!(claims.is_empty());
+    assert!(
+        !mock
+            .request_bodies("/")
+            .await
+            .iter()
+            .any(|body| body["method"] == "sui_executeTransactionBlock")
+    );
+}
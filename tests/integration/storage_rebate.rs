@@ -0,0 +1,94 @@
+use serde_json::{Value, json};
+use squad_connect::{client::squad_connect::SquadConnect, service::types::ServiceError};
+use sui_sdk::types::base_types::{ObjectDigest, ObjectID, SequenceNumber};
+
+use crate::mock_enoki::{
+    MAX_EPOCH, MockEnoki, OBJECT_DIGEST, account, connect, mount_json_rpc, zk_inputs,
+};
+
+const FIRST: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+const SECOND: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+
+fn coin(object_id: &str, balance: &str, storage_rebate: u64) -> Value {
+    json!({
+        "data": {
+            "objectId": object_id,
+            "version": "3",
+            "digest": OBJECT_DIGEST,
+            "type": "0x2::coin::Coin<0x2::sui::SUI>",
+            "storageRebate": storage_rebate.to_string(),
+            "content": {
+                "dataType": "moveObject",
+                "type": "0x2::coin::Coin<0x2::sui::SUI>",
+                "hasPublicTransfer": true,
+                "fields": { "balance": balance, "id": { "id": object_id } }
+            }
+        }
+    })
+}
+
+async fn connect_with_coins(mock: &MockEnoki) -> SquadConnect {
+    mount_json_rpc(
+        &mock.server,
+        "sui_multiGetObjects",
+        json!([coin(FIRST, "0", 988_000), coin(SECOND, "5", 1_976_000)]),
+    )
+    .await;
+
+    connect(mock).await
+}
+
+#[tokio::test]
+async fn rebates_of_all_objects_are_summed() {
+    let (mock, _) = MockEnoki::start().await;
+
+    let rebate = connect_with_coins(&mock)
+        .await
+        .estimate_storage_rebate(&[
+            ObjectID::from_hex_literal(FIRST).unwrap(),
+            ObjectID::from_hex_literal(SECOND).unwrap(),
+        ])
+        .await
+        .expect("rebate is estimated");
+
+    assert_eq!(rebate, 2_964_000);
+}
+
+#[tokio::test]
+async fn coins_with_a_balance_are_not_destroyed() {
+    let (mock, _) = MockEnoki::start().await;
+    let mut squad_connect = connect_with_coins(&mock).await;
+
+    let objects = [FIRST, SECOND]
+        .iter()
+        .map(|id| {
+            (
+                ObjectID::from_hex_literal(id).unwrap(),
+                SequenceNumber::from_u64(3),
+                ObjectDigest::random(),
+            )
+        })
+        .collect();
+    let account = account();
+    let keystore = std::env::temp_dir().join(format!("{}.keystore", uuid::Uuid::new_v4()));
+
+    let result = squad_connect
+        .reclaim_storage(
+            objects,
+            10_000_000,
+            account,
+            zk_inputs(),
+            MAX_EPOCH,
+            keystore,
+        )
+        .await;
+
+    assert!(matches!(result, Err(ServiceError::Service(message)) if message.contains(SECOND)));
+    assert!(
+        !mock
+            .request_bodies("/")
+            .await
+            .iter()
+            .any(|body| body["method"] == "sui_executeTransactionBlock")
+    );
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::mock_enoki::{MockEnoki, connect, mount_system_state};
+
+#[tokio::test]
+async fn system_state_is_fetched_from_the_node() {
+    let (mock, _) = MockEnoki::start().await;
+    mount_system_state(&mock.server, 7).await;
+
+    let squad_connect = connect(&mock).await;
+
+    let state = squad_connect
+        .get_system_state()
+        .await
+        .expect("system state is returned");
+
+    assert_eq!(state.epoch, 7);
+    assert_eq!(state.epoch_duration_ms, 86_400_000);
+}
+
+#[tokio::test]
+async fn no_time_is_left_in_an_overdue_epoch() {
+    let (mock, _) = MockEnoki::start().await;
+    // The mocked epoch started in November 2023 and lasts a day
+    mount_system_state(&mock.server, 7).await;
+
+    let squad_connect = connect(&mock).await;
+
+    assert_eq!(
+        squad_connect
+            .time_until_epoch_end()
+            .await
+            .expect("time is estimated"),
+        Duration::ZERO
+    );
+}
+
+#[tokio::test]
+async fn system_state_errors_are_reported() {
+    let (mock, _) = MockEnoki::start().await;
+
+    let squad_connect = connect(&mock).await;
+
+    assert!(squad_connect.get_system_state().await.is_err());
+    assert!(squad_connect.time_until_epoch_end().await.is_err());
+}
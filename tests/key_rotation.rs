@@ -0,0 +1,26 @@
+use squad_connect::utils::epoch::needs_key_rotation;
+
+#[test]
+fn keys_far_from_expiry_are_kept() {
+    assert!(!needs_key_rotation(110, 100, 1));
+    assert!(!needs_key_rotation(102, 100, 1));
+}
+
+#[test]
+fn keys_within_the_threshold_are_rotated() {
+    assert!(needs_key_rotation(101, 100, 1));
+    assert!(needs_key_rotation(100, 100, 1));
+    assert!(needs_key_rotation(105, 100, 5));
+}
+
+#[test]
+fn expired_keys_and_missing_sessions_are_rotated() {
+    assert!(needs_key_rotation(90, 100, 1));
+    assert!(needs_key_rotation(0, 100, 0));
+}
+
+#[test]
+fn zero_threshold_rotates_only_in_the_last_epoch() {
+    assert!(!needs_key_rotation(101, 100, 0));
+    assert!(needs_key_rotation(100, 100, 0));
+}
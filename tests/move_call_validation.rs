@@ -0,0 +1,84 @@
+use squad_connect::{client::dtos::MoveCallInfo, utils::abi::validate_call_args};
+use sui_sdk::types::{
+    base_types::{ObjectID, SequenceNumber},
+    digests::ObjectDigest,
+    transaction::{CallArg, ObjectArg},
+};
+
+fn info(parameters: &[&str]) -> MoveCallInfo {
+    MoveCallInfo {
+        parameters: parameters.iter().map(|p| p.to_string()).collect(),
+        return_types: vec![],
+        type_parameters: vec![],
+        is_entry: true,
+        visibility: "public".to_string(),
+    }
+}
+
+fn object() -> CallArg {
+    CallArg::Object(ObjectArg::ImmOrOwnedObject((
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    )))
+}
+
+fn pure() -> CallArg {
+    CallArg::Pure(bcs::to_bytes(&42u64).unwrap())
+}
+
+#[test]
+fn tx_context_is_not_counted() {
+    let info = info(&[
+        "&mut 0x2::coin::Coin<T0>",
+        "u64",
+        "&mut 0x2::tx_context::TxContext",
+    ]);
+
+    assert!(validate_call_args(&info, &[object(), pure()]).is_ok());
+}
+
+#[test]
+fn wrong_argument_count_is_rejected() {
+    let info = info(&["u64", "address"]);
+
+    assert!(validate_call_args(&info, &[pure()]).is_err());
+}
+
+#[test]
+fn pure_value_for_object_parameter_is_rejected() {
+    let info = info(&["&0x2::clock::Clock"]);
+
+    assert!(validate_call_args(&info, &[pure()]).is_err());
+}
+
+#[test]
+fn object_for_pure_parameter_is_rejected() {
+    let info = info(&["vector<u8>"]);
+
+    assert!(validate_call_args(&info, &[object()]).is_err());
+}
+
+#[test]
+fn strings_and_generics_accept_their_arguments() {
+    let info = info(&["0x1::string::String", "T0", "T0"]);
+
+    assert!(validate_call_args(&info, &[pure(), pure(), object()]).is_ok());
+}
+
+#[test]
+fn references_to_pure_types_take_pure_values() {
+    let info = info(&["&0x1::string::String", "&vector<u8>"]);
+
+    assert!(validate_call_args(&info, &[pure(), pure()]).is_ok());
+    assert!(validate_call_args(&info, &[object(), pure()]).is_err());
+    assert!(validate_call_args(&info, &[pure(), object()]).is_err());
+}
+
+#[test]
+fn mutable_references_to_objects_take_objects() {
+    let info = info(&["&mut 0x2::coin::Coin<0x2::sui::SUI>"]);
+
+    assert!(validate_call_args(&info, &[object()]).is_ok());
+    assert!(validate_call_args(&info, &[pure()]).is_err());
+}
@@ -0,0 +1,47 @@
+use squad_connect::utils::multisig::get_multi_sig_address;
+use sui_sdk::types::{
+    base_types::SuiAddress,
+    crypto::{EncodeDecodeBase64, PublicKey},
+};
+
+/// Flagged Ed25519 keys of the secrets `[1; 32]`, `[2; 32]` and `[3; 32]`
+const KEYS: [&str; 3] = [
+    "AIqI4910CfGV/VLbLTy6XXLKZwm/HZQSG/N0iAG0D29c",
+    "AIE5dw6ofRdfVqNUZsNMfszLjYqRtO43ol32D1uPybOU",
+    "AO1JKMYo0cLG6ukDOJBZlWEpWSc6XGP5NjbBRhSshzfR",
+];
+
+/// blake2b-256 of the multi-sig flag, threshold and flagged, weighted keys
+const TWO_OF_THREE: &str = "0xc89be3da99a6d04b32f1d18490210046412411d185a4817d62bb49d60f3d4d9d";
+const THREE_OF_THREE: &str = "0x97e52cb11126811c019c17396ba1eb5d2efed603fe83e6be889b7b23852ed707";
+
+fn committee(keys: &[&str]) -> Vec<(PublicKey, u8)> {
+    keys.iter()
+        .map(|key| (PublicKey::decode_base64(key).unwrap(), 1))
+        .collect()
+}
+
+#[test]
+fn two_of_three_address_matches_the_sui_derivation() {
+    let address = get_multi_sig_address(committee(&KEYS), 2).unwrap();
+
+    assert_eq!(address, TWO_OF_THREE.parse::<SuiAddress>().unwrap());
+}
+
+#[test]
+fn threshold_and_key_order_change_the_address() {
+    let three_of_three = get_multi_sig_address(committee(&KEYS), 3).unwrap();
+    let reordered = get_multi_sig_address(committee(&[KEYS[2], KEYS[1], KEYS[0]]), 2).unwrap();
+
+    assert_eq!(
+        three_of_three,
+        THREE_OF_THREE.parse::<SuiAddress>().unwrap()
+    );
+    assert_ne!(reordered, TWO_OF_THREE.parse::<SuiAddress>().unwrap());
+}
+
+#[test]
+fn unreachable_threshold_is_rejected() {
+    assert!(get_multi_sig_address(committee(&KEYS), 4).is_err());
+    assert!(get_multi_sig_address(committee(&KEYS), 0).is_err());
+}
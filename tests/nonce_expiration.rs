@@ -0,0 +1,18 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use squad_connect::utils::epoch::unix_ms_to_system_time;
+
+#[test]
+fn estimated_expiration_converts_to_system_time() {
+    let expires_at = unix_ms_to_system_time(1_700_000_000_123);
+
+    assert_eq!(
+        expires_at.duration_since(UNIX_EPOCH).unwrap(),
+        Duration::from_millis(1_700_000_000_123)
+    );
+}
+
+#[test]
+fn zero_is_the_unix_epoch() {
+    assert_eq!(unix_ms_to_system_time(0), UNIX_EPOCH);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "portfolio-sim")]
+
+use std::collections::HashMap;
+
+use squad_connect::client::portfolio_sim::compute_net_balance_change;
+
+const SUI: &str = "0x2::sui::SUI";
+const SUI_CANONICAL: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI";
+const USDC: &str = "0xa1ec::usdc::USDC";
+const USDC_CANONICAL: &str =
+    "0x000000000000000000000000000000000000000000000000000000000000a1ec::usdc::USDC";
+
+#[test]
+fn changes_apply_across_short_and_canonical_coin_types() {
+    let before = HashMap::from([(SUI.to_string(), 1_000), (USDC.to_string(), 50)]);
+    // A swap of SUI for USDC, as a dry run reports it
+    let changes = HashMap::from([
+        (SUI_CANONICAL.to_string(), -400),
+        (USDC_CANONICAL.to_string(), 25),
+    ]);
+
+    let after = compute_net_balance_change(&before, &changes);
+
+    assert_eq!(after.len(), 2);
+    assert_eq!(after[SUI_CANONICAL], 600);
+    assert_eq!(after[USDC_CANONICAL], 75);
+}
+
+#[test]
+fn balances_are_clamped_and_new_coin_types_start_at_zero() {
+    let before = HashMap::from([(SUI.to_string(), 100)]);
+    let changes = HashMap::from([(SUI.to_string(), -1_000), (USDC.to_string(), 30)]);
+
+    let after = compute_net_balance_change(&before, &changes);
+
+    assert_eq!(after[SUI_CANONICAL], 0);
+    assert_eq!(after[USDC_CANONICAL], 30);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "price-oracle")]
+
+use squad_connect::client::price_oracle::{convert_mist_to_usd, convert_usd_to_mist};
+
+const MIST_PER_SUI: u64 = 1_000_000_000;
+
+#[test]
+fn one_sui_is_worth_the_price() {
+    assert_eq!(convert_mist_to_usd(MIST_PER_SUI, 3.5), 3.5);
+    assert_eq!(convert_mist_to_usd(MIST_PER_SUI / 2, 3.5), 1.75);
+    assert_eq!(convert_mist_to_usd(0, 3.5), 0.0);
+}
+
+#[test]
+fn usd_converts_to_whole_mist() {
+    assert_eq!(convert_usd_to_mist(3.5, 3.5), MIST_PER_SUI);
+    assert_eq!(convert_usd_to_mist(1.75, 3.5), MIST_PER_SUI / 2);
+}
+
+#[test]
+fn fractions_of_a_mist_are_rounded_down() {
+    // 1 USD at 3 USD per SUI is 333_333_333.33... MIST
+    assert_eq!(convert_usd_to_mist(1.0, 3.0), 333_333_333);
+}
+
+#[test]
+fn non_positive_prices_convert_to_zero_mist() {
+    assert_eq!(convert_usd_to_mist(10.0, 0.0), 0);
+    assert_eq!(convert_usd_to_mist(10.0, -1.0), 0);
+}
+
+#[test]
+fn conversions_round_trip() {
+    let mist = 123_456_789_000;
+    let usd = convert_mist_to_usd(mist, 2.0);
+
+    assert_eq!(usd, 246.913578);
+    assert_eq!(convert_usd_to_mist(usd, 2.0), mist);
+}
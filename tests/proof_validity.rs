@@ -0,0 +1,59 @@
+use squad_connect::utils::epoch::proof_validity;
+
+const EPOCH_START_MS: u64 = 1_700_000_000_000;
+const EPOCH_DURATION_MS: u64 = 86_400_000;
+
+#[test]
+fn proof_before_max_epoch_is_valid() {
+    let now_ms = EPOCH_START_MS + EPOCH_DURATION_MS / 2;
+    let validity = proof_validity(12, 10, EPOCH_START_MS, EPOCH_DURATION_MS, now_ms);
+
+    assert!(validity.is_valid);
+    assert_eq!(validity.current_epoch, 10);
+    assert_eq!(validity.max_epoch, 12);
+    assert_eq!(validity.epochs_remaining, 2);
+    // Half of epoch 10 plus epochs 11 and 12
+    assert_eq!(
+        validity.estimated_seconds_remaining,
+        (EPOCH_DURATION_MS / 2 + 2 * EPOCH_DURATION_MS) / 1000
+    );
+}
+
+#[test]
+fn proof_stays_valid_during_max_epoch() {
+    let now_ms = EPOCH_START_MS + EPOCH_DURATION_MS / 4;
+    let validity = proof_validity(10, 10, EPOCH_START_MS, EPOCH_DURATION_MS, now_ms);
+
+    assert!(validity.is_valid);
+    assert_eq!(validity.epochs_remaining, 0);
+    assert_eq!(
+        validity.estimated_seconds_remaining,
+        EPOCH_DURATION_MS * 3 / 4 / 1000
+    );
+}
+
+#[test]
+fn proof_past_max_epoch_is_invalid() {
+    let validity = proof_validity(9, 10, EPOCH_START_MS, EPOCH_DURATION_MS, EPOCH_START_MS);
+
+    assert!(!validity.is_valid);
+    assert_eq!(validity.epochs_remaining, 0);
+    assert_eq!(validity.estimated_seconds_remaining, 0);
+}
+
+#[test]
+fn missing_session_is_invalid() {
+    let validity = proof_validity(0, 0, EPOCH_START_MS, EPOCH_DURATION_MS, EPOCH_START_MS);
+
+    assert!(!validity.is_valid);
+    assert_eq!(validity.estimated_seconds_remaining, 0);
+}
+
+#[test]
+fn overdue_epoch_change_leaves_no_time() {
+    let now_ms = EPOCH_START_MS + 2 * EPOCH_DURATION_MS;
+    let validity = proof_validity(10, 10, EPOCH_START_MS, EPOCH_DURATION_MS, now_ms);
+
+    assert!(validity.is_valid);
+    assert_eq!(validity.estimated_seconds_remaining, 0);
+}
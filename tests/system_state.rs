@@ -0,0 +1,65 @@
+use serde_json::json;
+use squad_connect::utils::epoch::{epoch_duration_ms, next_epoch_start_ms};
+use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+
+const OBJECT_ID_ZERO: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+const EPOCH_START_MS: u64 = 1_700_000_000_000;
+const DAY_MS: u64 = 86_400_000;
+
+/// A validator-less state, as `suix_getLatestSuiSystemState` returns it
+fn system_state(epoch: u64, epoch_start_ms: u64, epoch_duration_ms: u64) -> SuiSystemStateSummary {
+    serde_json::from_value(json!({
+        "epoch": epoch.to_string(),
+        "protocolVersion": "70",
+        "systemStateVersion": "2",
+        "storageFundTotalObjectStorageRebates": "0",
+        "storageFundNonRefundableBalance": "0",
+        "referenceGasPrice": "1000",
+        "safeMode": false,
+        "safeModeStorageRewards": "0",
+        "safeModeComputationRewards": "0",
+        "safeModeStorageRebates": "0",
+        "safeModeNonRefundableStorageFee": "0",
+        "epochStartTimestampMs": epoch_start_ms.to_string(),
+        "epochDurationMs": epoch_duration_ms.to_string(),
+        "stakeSubsidyStartEpoch": "0",
+        "maxValidatorCount": "150",
+        "minValidatorJoiningStake": "30000000000000000",
+        "validatorLowStakeThreshold": "20000000000000000",
+        "validatorVeryLowStakeThreshold": "15000000000000000",
+        "validatorLowStakeGracePeriod": "7",
+        "stakeSubsidyBalance": "0",
+        "stakeSubsidyDistributionCounter": "0",
+        "stakeSubsidyCurrentDistributionAmount": "0",
+        "stakeSubsidyPeriodLength": "10",
+        "stakeSubsidyDecreaseRate": 1000,
+        "totalStake": "0",
+        "activeValidators": [],
+        "pendingActiveValidatorsId": OBJECT_ID_ZERO,
+        "pendingActiveValidatorsSize": "0",
+        "pendingRemovals": [],
+        "stakingPoolMappingsId": OBJECT_ID_ZERO,
+        "stakingPoolMappingsSize": "0",
+        "inactivePoolsId": OBJECT_ID_ZERO,
+        "inactivePoolsSize": "0",
+        "validatorCandidatesId": OBJECT_ID_ZERO,
+        "validatorCandidatesSize": "0",
+        "atRiskValidators": [],
+        "validatorReportRecords": []
+    }))
+    .expect("system state parses")
+}
+
+#[test]
+fn epoch_duration_is_read_from_the_state() {
+    let state = system_state(42, EPOCH_START_MS, DAY_MS);
+
+    assert_eq!(epoch_duration_ms(&state), DAY_MS);
+}
+
+#[test]
+fn next_epoch_starts_one_duration_after_the_current_one() {
+    let state = system_state(42, EPOCH_START_MS, DAY_MS);
+
+    assert_eq!(next_epoch_start_ms(&state), EPOCH_START_MS + DAY_MS);
+}
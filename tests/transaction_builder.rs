@@ -0,0 +1,94 @@
+use squad_connect::client::pipeline::{ProgrammableTxBuilder, TransactionPipeline};
+use sui_sdk::types::{
+    base_types::{ObjectID, SuiAddress},
+    transaction::{Argument, CallArg, Command},
+};
+
+fn pure(name: &str) -> CallArg {
+    CallArg::Pure(bcs::to_bytes(name).unwrap())
+}
+
+#[test]
+fn three_commands_chain_their_results() {
+    let package = ObjectID::random();
+    let recipient = SuiAddress::random_for_testing_only();
+    let mut builder = ProgrammableTxBuilder::new();
+
+    let name = builder.pure("sword".to_string()).unwrap();
+    let item = builder
+        .add_move_call(package, "shop", "mint", vec![], vec![name])
+        .unwrap();
+    builder.add_object_transfer(recipient, item);
+    let amount = builder.pure(1_000u64).unwrap();
+    builder.add_sui_transfer(recipient, amount);
+
+    let pt = builder.finish();
+
+    assert_eq!(pt.inputs.len(), 3);
+    assert!(matches!(
+        &pt.commands[..],
+        [
+            Command::MoveCall(call),
+            Command::TransferObjects(items, _),
+            Command::SplitCoins(Argument::GasCoin, amounts),
+            Command::TransferObjects(coins, _),
+        ] if call.package == package
+            && call.module.as_str() == "shop"
+            && items == &[Argument::Result(0)]
+            && amounts == &[Argument::Input(2)]
+            && coins == &[Argument::Result(2)]
+    ));
+}
+
+#[test]
+fn invalid_identifiers_are_rejected() {
+    let mut builder = ProgrammableTxBuilder::new();
+
+    assert!(
+        builder
+            .add_move_call(ObjectID::random(), "not a module", "mint", vec![], vec![])
+            .is_err()
+    );
+}
+
+#[test]
+fn pipeline_builds_its_calls_into_one_transaction() {
+    let package = ObjectID::random();
+    let recipient = SuiAddress::random_for_testing_only();
+
+    let pt = TransactionPipeline::new()
+        .add_move_call(package, "shop", "mint", vec![], vec![pure("sword")])
+        .add_move_call(package, "forge", "upgrade", vec![], vec![pure("steel")])
+        .add_transfer(recipient, Argument::Result(1))
+        .add_merge_coins(Argument::GasCoin, vec![Argument::Result(0)])
+        .build()
+        .unwrap();
+
+    assert_eq!(pt.inputs.len(), 3);
+    assert!(matches!(
+        &pt.commands[..],
+        [
+            Command::MoveCall(mint),
+            Command::MoveCall(upgrade),
+            Command::TransferObjects(items, Argument::Input(2)),
+            Command::MergeCoins(Argument::GasCoin, coins),
+        ] if mint.package == package
+            && mint.module.as_str() == "shop"
+            && mint.function.as_str() == "mint"
+            && mint.arguments == [Argument::Input(0)]
+            && upgrade.module.as_str() == "forge"
+            && upgrade.function.as_str() == "upgrade"
+            && upgrade.arguments == [Argument::Input(1)]
+            && items == &[Argument::Result(1)]
+            && coins == &[Argument::Result(0)]
+    ));
+}
+
+#[test]
+fn pipeline_rejects_invalid_identifiers() {
+    let pipeline = TransactionPipeline::new()
+        .add_move_call(ObjectID::random(), "shop", "mint", vec![], vec![])
+        .add_move_call(ObjectID::random(), "shop", "not a function", vec![], vec![]);
+
+    assert!(pipeline.build().is_err());
+}
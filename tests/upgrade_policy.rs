@@ -0,0 +1,27 @@
+use squad_connect::{client::upgrade::UpgradePolicy, service::types::ServiceError};
+
+#[test]
+fn policies_map_to_upgrade_cap_bytes() {
+    assert_eq!(u8::from(UpgradePolicy::Compatible), 0);
+    assert_eq!(u8::from(UpgradePolicy::Additive), 128);
+    assert_eq!(u8::from(UpgradePolicy::Dep), 192);
+}
+
+#[test]
+fn policy_bytes_round_trip() {
+    for policy in [
+        UpgradePolicy::Compatible,
+        UpgradePolicy::Additive,
+        UpgradePolicy::Dep,
+    ] {
+        assert_eq!(UpgradePolicy::try_from(u8::from(policy)).unwrap(), policy);
+    }
+}
+
+#[test]
+fn unknown_policy_bytes_are_rejected() {
+    assert!(matches!(
+        UpgradePolicy::try_from(255),
+        Err(ServiceError::Service(_))
+    ));
+}
@@ -0,0 +1,91 @@
+use proptest::prelude::*;
+use squad_connect::service::services::{
+    extract_jwt_from_callback_url, extract_state_from_callback_url,
+};
+use url::form_urlencoded::byte_serialize;
+
+const CALLBACK: &str = "https://example.com/callback";
+
+fn encode(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
+
+/// JWTs are three base64url segments joined by dots
+fn jwt_strategy() -> impl Strategy<Value = String> {
+    "[A-Za-z0-9_-]{1,64}\\.[A-Za-z0-9_-]{1,256}\\.[A-Za-z0-9_-]{1,128}"
+}
+
+proptest! {
+    #[test]
+    fn jwt_is_read_from_query(token in jwt_strategy(), other in "[a-z]{1,16}") {
+        let url = format!("{}?foo={}&id_token={}", CALLBACK, other, encode(&token));
+
+        prop_assert_eq!(extract_jwt_from_callback_url(&url).unwrap(), token);
+    }
+
+    #[test]
+    fn jwt_is_read_from_fragment(token in jwt_strategy()) {
+        let url = format!("{}#id_token={}&state=%22x%22", CALLBACK, encode(&token));
+
+        prop_assert_eq!(extract_jwt_from_callback_url(&url).unwrap(), token);
+    }
+
+    #[test]
+    fn percent_encoded_token_is_decoded(token in "\\PC{1,128}") {
+        let url = format!("{}?id_token={}", CALLBACK, encode(&token));
+
+        prop_assert_eq!(extract_jwt_from_callback_url(&url).unwrap(), token);
+    }
+
+    #[test]
+    fn query_takes_precedence_over_empty_fragment(token in jwt_strategy()) {
+        let url = format!("{}?id_token={}#", CALLBACK, encode(&token));
+
+        prop_assert_eq!(extract_jwt_from_callback_url(&url).unwrap(), token);
+    }
+
+    #[test]
+    fn missing_jwt_is_an_error(key in "[a-z_]{1,16}", value in "[A-Za-z0-9]{0,32}") {
+        prop_assume!(key != "id_token");
+        let url = format!("{}?{}={}#{}={}", CALLBACK, key, value, key, value);
+
+        prop_assert!(extract_jwt_from_callback_url(&url).is_err());
+    }
+
+    #[test]
+    fn malformed_utf8_does_not_panic(bytes in proptest::collection::vec(any::<u8>(), 1..64)) {
+        let encoded: String = bytes.iter().map(|byte| format!("%{:02X}", byte)).collect();
+        let url = format!("{}?id_token={}#state={}", CALLBACK, encoded, encoded);
+
+        prop_assert!(extract_jwt_from_callback_url(&url).is_ok());
+        let _ = extract_state_from_callback_url::<String>(&url);
+    }
+
+    #[test]
+    fn arbitrary_input_does_not_panic(input in "\\PC*") {
+        let _ = extract_jwt_from_callback_url(&input);
+        let _ = extract_state_from_callback_url::<String>(&input);
+    }
+
+    #[test]
+    fn state_round_trips(state in "\\PC*") {
+        let state_json = serde_json::to_string(&state).unwrap();
+        let url = format!("{}?id_token=a.b.c&state={}", CALLBACK, encode(&state_json));
+
+        prop_assert_eq!(
+            extract_state_from_callback_url::<String>(&url).unwrap(),
+            Some(state)
+        );
+    }
+
+    #[test]
+    fn state_round_trips_in_fragment(state in "\\PC*") {
+        let state_json = serde_json::to_string(&state).unwrap();
+        let url = format!("{}#id_token=a.b.c&state={}", CALLBACK, encode(&state_json));
+
+        prop_assert_eq!(
+            extract_state_from_callback_url::<String>(&url).unwrap(),
+            Some(state)
+        );
+    }
+}
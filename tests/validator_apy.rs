@@ -0,0 +1,160 @@
+use squad_connect::{
+    client::dtos::{RebalanceAction, StakePosition, ValidatorSummary},
+    utils::staking::{
+        apy_from_epoch_rate, calculate_stake_reward, delegation_portfolio,
+        filter_validators_by_min_stake, rebalance_suggestion, sort_validators_by_apy,
+        top_earning_validator,
+    },
+};
+use sui_sdk::types::base_types::{ObjectID, SuiAddress};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+fn validator(name: &str, stake: u64, apy_bps: u64) -> ValidatorSummary {
+    ValidatorSummary {
+        name: name.to_string(),
+        address: SuiAddress::random_for_testing_only(),
+        stake,
+        commission_rate: 200,
+        apy_bps,
+        voting_power: 100,
+    }
+}
+
+fn position(validator: SuiAddress, principal: u64, estimated_reward: u64) -> StakePosition {
+    StakePosition {
+        staked_sui_id: ObjectID::random(),
+        validator,
+        principal,
+        estimated_reward,
+        activation_epoch: 1,
+    }
+}
+
+#[test]
+fn zero_rate_has_zero_apy() {
+    assert_eq!(apy_from_epoch_rate(0.0, DAY_MS), 0.0);
+}
+
+#[test]
+fn daily_rate_compounds_over_a_year() {
+    // 0.01% a day over 365.25 daily epochs
+    let apy = apy_from_epoch_rate(0.0001, DAY_MS);
+
+    assert!((apy - 0.037198).abs() < 1e-5, "apy was {}", apy);
+}
+
+#[test]
+fn longer_epochs_compound_less_often() {
+    let daily = apy_from_epoch_rate(0.0001, DAY_MS);
+    let weekly = apy_from_epoch_rate(0.0007, 7 * DAY_MS);
+
+    assert!(weekly < daily);
+}
+
+#[test]
+fn zero_epoch_duration_is_not_a_division_by_zero() {
+    assert_eq!(apy_from_epoch_rate(0.0001, 0), 0.0);
+}
+
+#[test]
+fn validators_are_sorted_by_apy_descending() {
+    let mut validators = vec![
+        validator("a", 10, 310),
+        validator("b", 10, 420),
+        validator("c", 10, 150),
+    ];
+
+    sort_validators_by_apy(&mut validators);
+
+    let names: Vec<_> = validators.iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(names, ["b", "a", "c"]);
+}
+
+#[test]
+fn small_validators_are_filtered_out() {
+    let validators = vec![
+        validator("a", 1_000, 300),
+        validator("b", 999, 300),
+        validator("c", 5_000, 300),
+    ];
+
+    let names: Vec<_> = filter_validators_by_min_stake(&validators, 1_000)
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect();
+    assert_eq!(names, ["a", "c"]);
+}
+
+#[test]
+fn stake_reward_follows_exchange_rate_growth() {
+    assert_eq!(calculate_stake_reward(1_000_000_000, 1.05), 50_000_000);
+    assert_eq!(calculate_stake_reward(1_000_000_000, 1.0), 0);
+}
+
+#[test]
+fn falling_exchange_rate_has_no_reward() {
+    assert_eq!(calculate_stake_reward(1_000_000_000, 0.9), 0);
+}
+
+#[test]
+fn portfolio_totals_span_validators() {
+    let a = SuiAddress::random_for_testing_only();
+    let b = SuiAddress::random_for_testing_only();
+
+    let portfolio = delegation_portfolio(vec![
+        position(a, 1_000, 10),
+        position(b, 2_000, 30),
+        position(a, 500, 25),
+    ]);
+
+    assert_eq!(portfolio.total_principal_mist, 3_500);
+    assert_eq!(portfolio.total_estimated_reward_mist, 65);
+    assert_eq!(portfolio.validator_count, 2);
+    assert_eq!(top_earning_validator(&portfolio), Some(a));
+}
+
+#[test]
+fn empty_portfolio_has_no_top_validator() {
+    assert_eq!(
+        top_earning_validator(&delegation_portfolio(Vec::new())),
+        None
+    );
+}
+
+#[test]
+fn stake_moves_to_the_best_validator() {
+    let mut best = validator("best", 10, 500);
+    let mut close = validator("close", 10, 480);
+    let mut poor = validator("poor", 10, 300);
+    best.address = SuiAddress::random_for_testing_only();
+    close.address = SuiAddress::random_for_testing_only();
+    poor.address = SuiAddress::random_for_testing_only();
+    let inactive = SuiAddress::random_for_testing_only();
+
+    let portfolio = delegation_portfolio(vec![
+        position(best.address, 1_000, 0),
+        position(close.address, 2_000, 0),
+        position(poor.address, 3_000, 0),
+        position(inactive, 4_000, 0),
+        position(poor.address, 500, 0),
+    ]);
+
+    let actions = rebalance_suggestion(&portfolio, &[best.clone(), close, poor.clone()]);
+
+    assert_eq!(
+        actions,
+        vec![
+            RebalanceAction {
+                from_validator: poor.address,
+                to_validator: best.address,
+                amount_mist: 3_500,
+            },
+            RebalanceAction {
+                from_validator: inactive,
+                to_validator: best.address,
+                amount_mist: 4_000,
+            },
+        ]
+    );
+}
@@ -0,0 +1,64 @@
+use fastcrypto::hash::{Blake2b256, HashFunction};
+use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use serde_json::json;
+use squad_connect::utils::address::zk_login_address_matches;
+use sui_sdk::types::base_types::SuiAddress;
+
+const ISSUER: &str = "https://accounts.google.com";
+
+/// Proof for address seed 1 issued by Google; only the address inputs matter here
+fn zk_inputs() -> ZkLoginInputs {
+    serde_json::from_value(json!({
+        "proofPoints": {
+            "a": ["1", "2", "1"],
+            "b": [["1", "0"], ["1", "0"], ["1", "0"]],
+            "c": ["1", "2", "1"]
+        },
+        "issBase64Details": {
+            "value": "yJpc3MiOiJodHRwczovL2FjY291bnRzLmdvb2dsZS5jb20iLC",
+            "indexMod4": 1
+        },
+        "headerBase64": "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9",
+        "addressSeed": "1"
+    }))
+    .expect("inputs deserialize")
+}
+
+/// zkLogin address as defined by Sui: blake2b(flag || len(iss) || iss || seed)
+fn derive_address(seed: &[u8]) -> SuiAddress {
+    let mut hasher = Blake2b256::default();
+    hasher.update([0x05]);
+    hasher.update([ISSUER.len() as u8]);
+    hasher.update(ISSUER.as_bytes());
+    hasher.update(seed);
+
+    SuiAddress::from_bytes(hasher.finalize().digest).unwrap()
+}
+
+#[test]
+fn matching_address_is_accepted() {
+    assert!(zk_login_address_matches(&zk_inputs(), derive_address(&[1])));
+}
+
+#[test]
+fn legacy_padded_address_is_accepted() {
+    let mut padded = [0u8; 32];
+    padded[31] = 1;
+
+    assert!(zk_login_address_matches(
+        &zk_inputs(),
+        derive_address(&padded)
+    ));
+}
+
+#[test]
+fn mismatching_address_is_rejected() {
+    assert!(!zk_login_address_matches(
+        &zk_inputs(),
+        derive_address(&[2])
+    ));
+    assert!(!zk_login_address_matches(
+        &zk_inputs(),
+        SuiAddress::random_for_testing_only()
+    ));
+}
@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use fastcrypto_zkp::bn254::{utils::gen_address_seed, zk_login::ZkLoginInputs};
+use serde_json::json;
+use squad_connect::{
+    service::zkp::compute_address_with_salt, utils::address::zk_login_address_matches,
+};
+use sui_sdk::types::base_types::SuiAddress;
+
+const ISSUER: &str = "https://accounts.google.com";
+const SUB: &str = "106294049240999307923";
+const SALT: &str = "2455937816256448139";
+const AUD: &str = "25769832374-famecqrhe2gkebt5fvqms2263046lj96.apps.googleusercontent.com";
+
+/// Salt and address of fastcrypto's Google zkLogin test vector for `SUB` and `AUD`
+const VECTOR_SALT: &str = "206703048842351542647799591018316385612";
+const VECTOR_ADDRESS: &str = "0xa64ae946d5efd2dea396cb2fe81837f028c32f2b2f211176b65a3a152deb35a2";
+
+/// Google proof inputs for `SUB`, `SALT` and `AUD`; only the address inputs matter here
+fn zk_inputs() -> ZkLoginInputs {
+    let address_seed = gen_address_seed(SALT, "sub", SUB, AUD).expect("seed is derived");
+
+    serde_json::from_value(json!({
+        "proofPoints": {
+            "a": ["1", "2", "1"],
+            "b": [["1", "0"], ["1", "0"], ["1", "0"]],
+            "c": ["1", "2", "1"]
+        },
+        "issBase64Details": {
+            "value": "yJpc3MiOiJodHRwczovL2FjY291bnRzLmdvb2dsZS5jb20iLC",
+            "indexMod4": 1
+        },
+        "headerBase64": "eyJhbGciOiJSUzI1NiIsInR5cCI6IkpXVCJ9",
+        "addressSeed": address_seed
+    }))
+    .expect("inputs deserialize")
+}
+
+#[test]
+fn address_matches_the_one_proven_by_the_inputs() {
+    let address = compute_address_with_salt(SUB, SALT, ISSUER, AUD).expect("address is derived");
+
+    assert!(zk_login_address_matches(&zk_inputs(), address));
+}
+
+#[test]
+fn address_matches_the_known_google_vector() {
+    let address = compute_address_with_salt(SUB, VECTOR_SALT, ISSUER, AUD).unwrap();
+
+    assert_eq!(address, SuiAddress::from_str(VECTOR_ADDRESS).unwrap());
+}
+
+#[test]
+fn address_depends_on_salt_and_audience() {
+    let address = compute_address_with_salt(SUB, SALT, ISSUER, AUD).unwrap();
+
+    assert_ne!(
+        compute_address_with_salt(SUB, "2455937816256448140", ISSUER, AUD).unwrap(),
+        address
+    );
+    assert_ne!(
+        compute_address_with_salt(SUB, SALT, ISSUER, "other-client-id").unwrap(),
+        address
+    );
+    assert_eq!(
+        compute_address_with_salt(SUB, SALT, ISSUER, AUD).unwrap(),
+        address
+    );
+}
+
+#[test]
+fn non_numeric_salt_is_rejected() {
+    assert!(compute_address_with_salt(SUB, "not-a-salt", ISSUER, AUD).is_err());
+}